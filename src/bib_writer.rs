@@ -0,0 +1,289 @@
+use crate::entry::{Entry, RawValue};
+use crate::s;
+use crate::strings::unicode_to_latex;
+use clap::ValueEnum;
+
+/// Which delimiters field values are preferably written with.
+#[derive(Debug, PartialEq, Clone, Copy, Default, ValueEnum)]
+pub(crate) enum ValueDelimiter {
+    #[default]
+    Braces,
+    Quotes,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct WriterOptions {
+    pub(crate) indent: String,
+    pub(crate) align_equals: bool,
+    pub(crate) trailing_comma: bool,
+    pub(crate) delimiter: ValueDelimiter,
+}
+
+impl Default for WriterOptions {
+    fn default() -> Self {
+        WriterOptions {
+            indent: s!("  "),
+            align_equals: false,
+            trailing_comma: false,
+            delimiter: ValueDelimiter::default(),
+        }
+    }
+}
+
+/// Writes entries back to BibTeX, so that parsing the output yields the same entries.
+pub(crate) struct BibWriter {
+    options: WriterOptions,
+}
+
+impl BibWriter {
+    pub(crate) fn new() -> BibWriter {
+        Self::with_options(WriterOptions::default())
+    }
+
+    pub(crate) fn with_options(options: WriterOptions) -> BibWriter {
+        BibWriter { options }
+    }
+
+    pub(crate) fn write(&self, entries: &[Entry]) -> String {
+        entries
+            .iter()
+            .map(|entry| self.write_entry(entry))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Writes the fields read from a `.bib` file unchanged as they were written, and the others
+    /// from their text, escaping the characters with a meaning in LaTeX, e.g. `&` to `\&`.
+    pub(crate) fn write_entry(&self, entry: &Entry) -> String {
+        let fields: Vec<(String, RawValue)> = entry
+            .fields()
+            .iter()
+            .flat_map(|field| match entry.written_value(field) {
+                Some(value) => vec![(field.name(), value.clone())],
+                None => field
+                    .bib_fields()
+                    .into_iter()
+                    .map(|(name, value)| match field.is_verbatim() {
+                        true => (name, RawValue::Delimited(value)),
+                        false => (name, RawValue::Delimited(unicode_to_latex(&value))),
+                    })
+                    .collect(),
+            })
+            .collect();
+        self.write_fields(&entry.entry_type().to_string(), entry.symbol(), &fields)
+    }
+
+    /// Lays out an entry from its type, symbol and `(name, value)` pairs without delimiters.
+    /// Bare values, e.g. `2020` or the macro `jan`, are written without them too.
+    pub(crate) fn write_fields(
+        &self,
+        entry_type: &str,
+        symbol: &str,
+        fields: &[(String, RawValue)],
+    ) -> String {
+        let width = match self.options.align_equals {
+            true => fields.iter().map(|(name, _)| name.chars().count()).max(),
+            false => None,
+        }
+        .unwrap_or_default();
+        let mut lines: String = fields
+            .iter()
+            .map(|(name, value)| {
+                format!(
+                    "{}{:width$} = {}",
+                    self.options.indent,
+                    name,
+                    match value {
+                        RawValue::Delimited(value) => self.delimited(value),
+                        RawValue::Bare(value) => s!(value),
+                    },
+                    width = width
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",\n");
+        if self.options.trailing_comma && !lines.is_empty() {
+            lines.push(',');
+        }
+        match lines.is_empty() {
+            true => format!("@{}{{{},\n}}\n", entry_type, symbol),
+            false => format!("@{}{{{},\n{}\n}}\n", entry_type, symbol, lines),
+        }
+    }
+
+    /// Uses the preferred delimiters where the value allows it. Values with unbalanced braces
+    /// are quoted, and double quotes in those are written as `\textquotedbl`.
+    fn delimited(&self, value: &str) -> String {
+        let fits_braces = Self::has_balanced_braces(value);
+        let fits_quotes = !value.contains('"');
+        match (self.options.delimiter, fits_braces, fits_quotes) {
+            (ValueDelimiter::Quotes, _, true) | (ValueDelimiter::Braces, false, true) => {
+                format!("\"{}\"", value)
+            }
+            (_, true, _) => format!("{{{}}}", value),
+            (_, false, false) => format!("\"{}\"", value.replace('"', "{\\textquotedbl}")),
+        }
+    }
+
+    /// Whether the braces are balanced as the tokenizer counts them, i.e. escapes included.
+    fn has_balanced_braces(value: &str) -> bool {
+        let mut depth = 0;
+        for c in value.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => return false,
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        depth == 0
+    }
+}
+
+#[cfg(test)]
+mod bib_writer_test {
+    use super::*;
+    use crate::csl_json::CslJsonReader;
+    use crate::entry::Parser;
+    use crate::tokenizer::Tokenizer;
+    use std::io::Cursor;
+
+    #[test]
+    fn write_entry() {
+        // given
+        let input = r#"@book{beck-2004,
+            title = {Extreme Programming Explained},
+            author = {Beck, Kent and {Barnes and Noble}},
+            pages = {1--5, 7ff.},
+            eprint = {2101.00001v2}, eprinttype = {arXiv},
+        }"#;
+        let expected = r#"@book{beck-2004,
+  title = {Extreme Programming Explained},
+  author = {Beck, Kent and {Barnes and Noble}},
+  pages = {1--5, 7ff.},
+  eprint = {2101.00001v2},
+  eprinttype = {arxiv}
+}
+"#;
+
+        // when
+        let entries = parse(input);
+        let actual = BibWriter::new().write_entry(&entries[0]);
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn choose_delimiters() {
+        // given
+        [
+            ((ValueDelimiter::Braces, "a \"b\""), "{a \"b\"}"),
+            ((ValueDelimiter::Braces, "a \\{b"), "\"a \\{b\""),
+            ((ValueDelimiter::Quotes, "a"), "\"a\""),
+            ((ValueDelimiter::Quotes, "a \"b\""), "{a \"b\"}"),
+            ((ValueDelimiter::Quotes, "\\{\""), "\"\\{{\\textquotedbl}\""),
+        ]
+        .iter()
+        .for_each(|((delimiter, value), expected)| {
+            // when
+            let writer = BibWriter::with_options(WriterOptions {
+                delimiter: *delimiter,
+                ..WriterOptions::default()
+            });
+            let actual = writer.delimited(value);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn round_trip() {
+        // given
+        let input = r#"
+            @book{beck-2004,
+                title     = {Extreme Programming Explained: Embrace Change},
+                edition   = {2nd revised},
+                isbn      = {978-0-13-405199-4},
+                series    = {{XP} Series},
+                pagetotal = {189},
+                publisher = {{Barnes and Noble} and Penguin and others},
+                author    = {Beck, Kent and Martin, Robert C. and {IEEE}},
+                date      = {2004-03-02},
+                month     = {mar},
+                keywords  = {agile; xp},
+                options   = {useprefix=true, skipbib},
+                gender    = {sm},
+                note      = "A \{ note",
+                url       = {https://example.com/{a}},
+                ids       = {beck, xp},
+            }
+            @article{ieee-802-3-2018,
+                journal = {IEEE Std 802.3-2018},
+                year    = {ca. 2018},
+                pages   = {iv--xii, e12, 15+},
+                doi     = {10.1109/IEEESTD.2018.8457469},
+                eprint  = {hep-th/9901001}, eprinttype = {arxiv},
+                pubstate = {inpress},
+                sortyear = {2018},
+                location = {xdata=places-location-2},
+                custom  = {Unknown},
+            }
+            @set{set, entryset = {beck-2004, ieee-802-3-2018}}"#;
+        let expected = parse(input);
+
+        // when
+        let written = BibWriter::new().write(&expected);
+        let actual = parse(&written);
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn round_trip_text() {
+        // given
+        let input = r#"@book{knuth-1984,
+  title = {The {\TeX}book: {XP} in {NASA}},
+  author = {Donald E. Knuth and {Barnes and Noble} and {IEEE} and van Beethoven, Ludwig},
+  publisher = {{Barnes and Noble} and {ACM} Press},
+  keywords = {{model checking, SAT}; {NASA}}
+}
+"#;
+
+        // when
+        let actual = BibWriter::new().write(&parse(input));
+
+        // then
+        assert_eq!(actual, input);
+    }
+
+    #[test]
+    fn escape_special_characters() {
+        // given
+        let input = r#"[{
+            "id": "q-and-a", "type": "book",
+            "title": "Q&A: 100% of #1 for $5 at ~user_name^2",
+            "URL": "https://example.com/~user_name?a=1&b=100%25"
+        }]"#;
+        let expected = r#"@book{q-and-a,
+  url = {https://example.com/~user_name?a=1&b=100%25},
+  title = {Q\&A: 100\% of \#1 for \$5 at {\textasciitilde}user\_name{\textasciicircum}2}
+}
+"#;
+
+        // when
+        let entries = CslJsonReader::read(input).unwrap();
+        let actual = BibWriter::new().write(&entries);
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    fn parse(input: &str) -> Vec<Entry> {
+        Parser::new(Tokenizer::new(Box::new(Cursor::new(s!(input)))))
+            .parse()
+            .unwrap()
+    }
+}
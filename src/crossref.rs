@@ -0,0 +1,369 @@
+use crate::entry::Entry;
+use crate::entry_field::EntryField;
+use crate::entry_type::EntryType;
+use crate::s;
+use std::collections::HashMap;
+
+/// Copies the fields a child entry is missing from the entry named in its `crossref` field,
+/// following biblatex's default inheritance rules.
+pub(crate) struct CrossrefResolver<'a> {
+    entries: &'a [Entry],
+    index: HashMap<&'a str, usize>,
+    resolved: Vec<Option<Vec<EntryField>>>,
+    path: Vec<usize>,
+    warnings: Vec<(String, String)>,
+}
+
+/// Where the parent's `title` ends up in the child, e.g. proceedings `title` → `booktitle`.
+enum TitleTarget {
+    Main,
+    Book,
+    Journal,
+}
+
+impl<'a> CrossrefResolver<'a> {
+    /// Returns the resolved entries and the warnings as `(symbol, warning)` pairs.
+    /// With `min_crossrefs`, as in BibTeX, parents referenced by fewer children
+    /// are dropped from the output, and their children no longer point to them.
+    pub(crate) fn resolve(
+        entries: Vec<Entry>,
+        min_crossrefs: Option<usize>,
+    ) -> (Vec<Entry>, Vec<(String, String)>) {
+        let mut resolver = CrossrefResolver::new(&entries);
+        let fields: Vec<Vec<EntryField>> = (0..entries.len())
+            .map(|i| resolver.resolve_entry(i))
+            .collect();
+        let warnings = resolver.warnings;
+
+        let dropped = match min_crossrefs {
+            Some(min) => Self::rarely_referenced(&entries, min),
+            None => vec![],
+        };
+        let entries = entries
+            .into_iter()
+            .zip(fields)
+            .filter(|(entry, _)| !dropped.iter().any(|s| s == entry.symbol()))
+            .map(|(entry, fields)| {
+                let fields = fields
+                    .into_iter()
+                    .filter(
+                        |f| !matches!(f, EntryField::CrossRef(parent) if dropped.contains(parent)),
+                    )
+                    .collect();
+                entry.with_fields(fields)
+            })
+            .collect();
+        (entries, warnings)
+    }
+
+    fn new(entries: &'a [Entry]) -> CrossrefResolver<'a> {
+        let mut index = HashMap::new();
+        entries.iter().enumerate().for_each(|(i, entry)| {
+            index.entry(entry.symbol()).or_insert(i);
+        });
+        CrossrefResolver {
+            entries,
+            index,
+            resolved: vec![None; entries.len()],
+            path: vec![],
+            warnings: vec![],
+        }
+    }
+
+    fn resolve_entry(&mut self, i: usize) -> Vec<EntryField> {
+        if let Some(fields) = &self.resolved[i] {
+            return fields.clone();
+        }
+        let entries = self.entries;
+        let entry = &entries[i];
+        let mut fields = entry.fields().clone();
+        if let Some(parent) = Self::crossref(entry) {
+            match self.index.get(parent).copied() {
+                None => self.warn(entry, format!("Crossref '{}' not found", parent)),
+                Some(p) if p == i || self.path.contains(&p) => {
+                    let cycle = self.cycle(p, i);
+                    self.warn(entry, format!("Crossref cycle {}", cycle))
+                }
+                Some(p) => {
+                    self.path.push(i);
+                    let parent_fields = self.resolve_entry(p);
+                    self.path.pop();
+                    Self::inherit(
+                        &mut fields,
+                        &parent_fields,
+                        entries[p].entry_type(),
+                        entry.entry_type(),
+                    );
+                }
+            }
+        }
+        self.resolved[i] = Some(fields.clone());
+        fields
+    }
+
+    /// Adds the inherited fields the child does not have yet. Renamed fields
+    /// (e.g. `title` → `booktitle`) win over a parent field of the same name.
+    fn inherit(
+        fields: &mut Vec<EntryField>,
+        parent_fields: &[EntryField],
+        parent_type: &EntryType,
+        child_type: &EntryType,
+    ) {
+        let parent_type = parent_type.clone().into_biblatex().0;
+        let child_type = child_type.clone().into_biblatex().0;
+        let (renamed, same): (Vec<_>, Vec<_>) = parent_fields
+            .iter()
+            .flat_map(|source| {
+                Self::inherited(source, &parent_type, &child_type)
+                    .into_iter()
+                    .map(move |field| (source, field))
+            })
+            .partition(|(source, field)| !field.is_same_field(source));
+        for (_, field) in renamed.into_iter().chain(same) {
+            if !fields.iter().any(|f| f.is_same_field(&field)) {
+                fields.push(field);
+            }
+        }
+    }
+
+    /// The fields a child gets from a single parent field.
+    fn inherited(field: &EntryField, parent: &EntryType, child: &EntryType) -> Vec<EntryField> {
+        use EntryType::*;
+        let title_target = match (parent, child) {
+            (MvBook, Book | InBook | BookInBook | SuppBook)
+            | (
+                MvCollection | MvReference,
+                Collection | Reference | InCollection | InReference | SuppCollection,
+            )
+            | (MvProceedings, Proceedings | InProceedings) => Some(TitleTarget::Main),
+            (Book, InBook | BookInBook | SuppBook)
+            | (Collection | Reference, InCollection | InReference | SuppCollection)
+            | (Proceedings, InProceedings) => Some(TitleTarget::Book),
+            (Periodical, Article | SuppPeriodical) => Some(TitleTarget::Journal),
+            _ => None,
+        };
+        let is_book_part = matches!(
+            (parent, child),
+            (MvBook | Book, InBook | BookInBook | SuppBook)
+        );
+
+        match (field, title_target) {
+            (EntryField::Title(t), Some(TitleTarget::Main)) => {
+                vec![EntryField::MainTitle(t.clone())]
+            }
+            (EntryField::Subtitle(t), Some(TitleTarget::Main)) => {
+                vec![EntryField::MainSubtitle(t.clone())]
+            }
+            (EntryField::Title(t), Some(TitleTarget::Book)) => {
+                vec![EntryField::BookTitle(t.clone())]
+            }
+            (EntryField::Subtitle(t), Some(TitleTarget::Book)) => {
+                vec![EntryField::BookSubtitle(t.clone())]
+            }
+            (EntryField::Title(t), Some(TitleTarget::Journal)) => {
+                vec![EntryField::JournalTitle(t.clone())]
+            }
+            (EntryField::Subtitle(t), Some(TitleTarget::Journal)) => {
+                vec![EntryField::JournalSubtitle(t.clone())]
+            }
+            (
+                EntryField::ShortTitle(_) | EntryField::SortTitle(_) | EntryField::IndexTitle(_),
+                Some(_),
+            ) => vec![],
+            (EntryField::Author(a), _) if is_book_part => {
+                vec![
+                    EntryField::Author(a.clone()),
+                    EntryField::BookAuthor(a.clone()),
+                ]
+            }
+            (field, _) if Self::is_inheritable(field) => vec![field.clone()],
+            _ => vec![],
+        }
+    }
+
+    /// Fields biblatex never inherits, as they only make sense for the entry itself.
+    fn is_inheritable(field: &EntryField) -> bool {
+        !matches!(
+            field,
+            EntryField::CrossRef(_)
+                | EntryField::EntrySet(_)
+                | EntryField::EntrySubtype(_)
+                | EntryField::Execute(_)
+                | EntryField::Ids(_)
+                | EntryField::Label(_)
+                | EntryField::Options(_)
+                | EntryField::Presort(_)
+                | EntryField::Related(_)
+                | EntryField::RelatedOptions(_)
+                | EntryField::RelatedType(_)
+                | EntryField::Shorthand(_)
+                | EntryField::ShorthandIntro(_)
+                | EntryField::SortKey(_)
+                | EntryField::XData(_)
+                | EntryField::XRef(_)
+        )
+    }
+
+    fn crossref(entry: &Entry) -> Option<&str> {
+        entry.fields().iter().find_map(|f| match f {
+            EntryField::CrossRef(parent) => Some(parent.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Symbols of the parents referenced by at least one, but fewer than `min` children.
+    fn rarely_referenced(entries: &[Entry], min: usize) -> Vec<String> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        entries
+            .iter()
+            .filter_map(Self::crossref)
+            .for_each(|parent| *counts.entry(parent).or_default() += 1);
+        entries
+            .iter()
+            .map(Entry::symbol)
+            .filter(|symbol| counts.get(symbol).is_some_and(|count| *count < min))
+            .map(|symbol| s!(symbol))
+            .collect()
+    }
+
+    /// Renders the chain of symbols from `parent` down to `child`, e.g. `'a' -> 'b' -> 'a'`.
+    fn cycle(&self, parent: usize, child: usize) -> String {
+        let start = self
+            .path
+            .iter()
+            .position(|i| *i == parent)
+            .unwrap_or(self.path.len());
+        self.path[start..]
+            .iter()
+            .chain([child, parent].iter())
+            .map(|i| format!("'{}'", self.entries[*i].symbol()))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+
+    fn warn(&mut self, entry: &Entry, warning: String) {
+        self.warnings.push((s!(entry.symbol()), warning));
+    }
+}
+
+#[cfg(test)]
+mod crossref_test {
+    use super::*;
+    use crate::entry::{Parser, ParserOptions};
+    use crate::literal_list::LiteralList;
+    use crate::person::Person;
+    use crate::tokenizer::Tokenizer;
+    use crate::year::Year;
+
+    #[test]
+    fn inherit_from_proceedings() {
+        // given
+        let input = r#"
+            @inproceedings{paper,
+                title    = {Paper},
+                crossref = {icse2021},
+            }
+            @proceedings{icse2021,
+                title     = {ICSE 2021},
+                shorttitle = {ICSE},
+                editor    = {Doe, Jane},
+                publisher = {IEEE},
+                year      = {2021},
+                ids       = {icse},
+            }"#;
+        let expected = vec![
+            EntryField::Title(s!("Paper")),
+            EntryField::CrossRef(s!("icse2021")),
+            EntryField::BookTitle(s!("ICSE 2021")),
+            EntryField::Editor(vec![Person::FirstLast {
+                first_name: s!("Jane"),
+                last_name: s!("Doe"),
+            }]),
+            EntryField::Publisher(LiteralList::from_str("IEEE")),
+            EntryField::Year(Year::Numeric(2021)),
+        ];
+
+        // when
+        let (entries, warnings) = parse(input, None);
+
+        // then
+        assert_eq!(*entries[0].fields(), expected);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn inherit_book_author_and_main_title() {
+        // given
+        let input = r#"
+            @mvbook{vol, title = {Collected Works}, author = {Doe, Jane}}
+            @book{part, title = {Part One}, crossref = {vol}}
+            @inbook{chapter, crossref = {part}}"#;
+
+        // when
+        let (entries, _) = parse(input, None);
+
+        // then
+        assert!(entries[1]
+            .fields()
+            .contains(&EntryField::MainTitle(s!("Collected Works"))));
+        assert!(entries[2]
+            .fields()
+            .contains(&EntryField::BookTitle(s!("Part One"))));
+        assert!(entries[2]
+            .fields()
+            .iter()
+            .any(|f| matches!(f, EntryField::BookAuthor(_))));
+    }
+
+    #[test]
+    fn warn_on_dangling_and_cyclic_crossrefs() {
+        // given
+        let input = r#"
+            @inproceedings{dangling, crossref = {missing}}
+            @proceedings{a, crossref = {b}}
+            @proceedings{b, crossref = {a}}"#;
+        let expected = vec![
+            s!("Entry 'dangling': Crossref 'missing' not found"),
+            s!("Entry 'b': Crossref cycle 'a' -> 'b' -> 'a'"),
+        ];
+
+        // when
+        let (_, warnings) = parse(input, None);
+
+        // then
+        assert_eq!(warnings, expected);
+    }
+
+    #[test]
+    fn drop_rarely_referenced_parents() {
+        // given
+        let input = r#"
+            @inproceedings{one, crossref = {conf}}
+            @proceedings{conf, title = {Conf}}
+            @inproceedings{two, crossref = {popular}}
+            @inproceedings{three, crossref = {popular}}
+            @proceedings{popular, title = {Popular}}"#;
+
+        // when
+        let (entries, _) = parse(input, Some(2));
+
+        // then
+        let symbols: Vec<&str> = entries.iter().map(Entry::symbol).collect();
+        assert_eq!(symbols, vec!["one", "two", "three", "popular"]);
+        assert_eq!(
+            *entries[0].fields(),
+            vec![EntryField::BookTitle(s!("Conf"))]
+        );
+    }
+
+    fn parse(input: &'static str, min_crossrefs: Option<usize>) -> (Vec<Entry>, Vec<String>) {
+        let options = ParserOptions {
+            resolve_crossrefs: true,
+            min_crossrefs,
+            ..ParserOptions::default()
+        };
+        let mut parser = Parser::with_options(Tokenizer::new(Box::new(input.as_bytes())), options);
+        let entries = parser.parse().unwrap();
+        (entries, parser.warnings().clone())
+    }
+}
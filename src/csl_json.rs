@@ -0,0 +1,865 @@
+use crate::date::Date;
+use crate::entry::Entry;
+use crate::entry_field::{EntryField, UnknownField};
+use crate::entry_type::EntryType;
+use crate::eprint::EPrint;
+use crate::literal_list::LiteralList;
+use crate::pages::Pages;
+use crate::person::Person;
+use crate::s;
+use crate::strings::protect_list_item;
+use crate::year::Year;
+use serde_json::{json, Map, Value};
+
+/// A CSL variable a field maps to. A fallback is only used when no other field sets it,
+/// e.g. `journal` when there is a `journaltitle`.
+enum Variable {
+    Primary(&'static str, Value),
+    Fallback(&'static str, Value),
+}
+
+/// Writes entries as CSL-JSON, the input format of citeproc-js and other CSL processors.
+pub(crate) struct CslJsonWriter;
+
+impl CslJsonWriter {
+    pub(crate) fn write(entries: &[Entry]) -> String {
+        serde_json::to_string(&Self::items(entries)).unwrap()
+    }
+
+    fn items(entries: &[Entry]) -> Vec<Value> {
+        entries.iter().map(Self::item).collect()
+    }
+
+    fn item(entry: &Entry) -> Value {
+        let mut item = Map::new();
+        item.insert(s!("id"), json!(entry.symbol()));
+        item.insert(s!("type"), json!(Self::item_type(entry)));
+        for field in entry.fields() {
+            match Self::variable(entry, field) {
+                Some(Variable::Primary(name, value)) => {
+                    item.insert(s!(name), value);
+                }
+                Some(Variable::Fallback(name, value)) => {
+                    item.entry(name).or_insert(value);
+                }
+                None => {}
+            }
+        }
+        if let Some(genre) = Self::genre(entry.entry_type()) {
+            item.entry("genre").or_insert(json!(genre));
+        }
+        Value::Object(item)
+    }
+
+    fn item_type(entry: &Entry) -> &'static str {
+        match entry.entry_type() {
+            EntryType::Article => match Self::find(entry, Self::entry_subtype).as_deref() {
+                Some("magazine") => "article-magazine",
+                Some("newspaper") => "article-newspaper",
+                _ => "article-journal",
+            },
+            EntryType::Book
+            | EntryType::MvBook
+            | EntryType::Collection
+            | EntryType::MvCollection
+            | EntryType::Proceedings
+            | EntryType::MvProceedings
+            | EntryType::Reference
+            | EntryType::MvReference
+            | EntryType::Manual
+            | EntryType::Commentary => "book",
+            EntryType::InBook
+            | EntryType::BookInBook
+            | EntryType::SuppBook
+            | EntryType::InCollection
+            | EntryType::SuppCollection => "chapter",
+            EntryType::InProceedings | EntryType::Conference => "paper-conference",
+            EntryType::InReference => "entry-encyclopedia",
+            EntryType::Periodical => "periodical",
+            EntryType::SuppPeriodical => "article-journal",
+            EntryType::Booklet => "pamphlet",
+            EntryType::Online | EntryType::Electronic | EntryType::Www => "webpage",
+            EntryType::Patent => "patent",
+            EntryType::Report | EntryType::TechReport => "report",
+            EntryType::Thesis | EntryType::MastersThesis | EntryType::PhdThesis => "thesis",
+            EntryType::Unpublished => "manuscript",
+            EntryType::Dataset | EntryType::DataType => "dataset",
+            EntryType::Software => "software",
+            EntryType::Artwork => "graphic",
+            EntryType::Audio | EntryType::Music => "song",
+            EntryType::Image => "figure",
+            EntryType::Jurisdiction => "legal_case",
+            EntryType::Legislation => "legislation",
+            EntryType::Legal => "treaty",
+            EntryType::Letter => "personal_communication",
+            EntryType::Movie | EntryType::Video => "motion_picture",
+            EntryType::Performance => "performance",
+            EntryType::Review => "review",
+            EntryType::Standard => "standard",
+            EntryType::Misc
+            | EntryType::Set
+            | EntryType::Custom
+            | EntryType::BibNote
+            | EntryType::XData
+            | EntryType::Other(_) => "document",
+        }
+    }
+
+    /// The genre of BibTeX types folded into a CSL type, unless a `type` field names it.
+    fn genre(entry_type: &EntryType) -> Option<&'static str> {
+        match entry_type {
+            EntryType::MastersThesis => Some("Master's thesis"),
+            EntryType::PhdThesis => Some("PhD thesis"),
+            EntryType::TechReport => Some("Technical report"),
+            _ => None,
+        }
+    }
+
+    fn variable(entry: &Entry, field: &EntryField) -> Option<Variable> {
+        use Variable::{Fallback, Primary};
+        match field {
+            EntryField::Abstract(s) => Some(Primary("abstract", json!(s))),
+            EntryField::Address(list) => Some(Fallback("publisher-place", Self::list(list))),
+            EntryField::Annotation(s) | EntryField::Annote(s) => Some(Primary("annote", json!(s))),
+            EntryField::Author(people) => Some(Primary("author", Self::names(people))),
+            EntryField::BookAuthor(people) => {
+                Some(Primary("container-author", Self::names(people)))
+            }
+            EntryField::BookTitle(title) => Some(Primary(
+                "container-title",
+                Self::title(title, Self::find(entry, Self::book_subtitle)),
+            )),
+            EntryField::Chapter(s) => Some(Primary("chapter-number", json!(s))),
+            EntryField::Date(date) => Self::date(date).map(|date| Primary("issued", date)),
+            EntryField::Doi(doi) => Some(Primary("DOI", json!(doi.to_string()))),
+            EntryField::Edition(edition) => Some(Primary("edition", json!(edition.to_string()))),
+            EntryField::Editor(people) => Some(Primary("editor", Self::names(people))),
+            EntryField::Eid(s) => Some(Fallback("number", json!(s))),
+            EntryField::EPrint(EPrint::PubMed(id)) => Some(Primary("PMID", json!(id))),
+            EntryField::EPrint(EPrint::PubMedCentral(id)) => Some(Primary("PMCID", json!(id))),
+            EntryField::EPrint(eprint) => eprint.to_url().map(|url| Fallback("URL", json!(url))),
+            EntryField::EventDate(date) => {
+                let date = match (date, Self::find(entry, Self::event_end_date)) {
+                    (Date::Range(..), _) | (_, None) => date.clone(),
+                    (start, Some(end)) => Date::Range(Box::new(start.clone()), Some(Box::new(end))),
+                };
+                Self::date(&date).map(|date| Primary("event-date", date))
+            }
+            EntryField::EventTitle(s) => Some(Primary("event-title", json!(s))),
+            EntryField::Institution(list)
+            | EntryField::School(list)
+            | EntryField::Organization(list) => Some(Fallback("publisher", Self::list(list))),
+            EntryField::Isbn(isbn) => Some(Primary("ISBN", json!(isbn.to_string()))),
+            EntryField::Issn(issn) => Some(Primary("ISSN", json!(issn.to_string()))),
+            EntryField::Issue(s) => Some(Fallback("issue", json!(s))),
+            EntryField::Journal(title) => Some(Fallback(
+                "container-title",
+                Self::title(title, Self::find(entry, Self::journal_subtitle)),
+            )),
+            EntryField::JournalTitle(title) => Some(Primary(
+                "container-title",
+                Self::title(title, Self::find(entry, Self::journal_subtitle)),
+            )),
+            EntryField::Keywords(keywords) => {
+                Some(Primary("keyword", json!(keywords.items().join(", "))))
+            }
+            EntryField::Language(list) => Some(Primary("language", Self::list(list))),
+            EntryField::Location(list) => Some(Primary("publisher-place", Self::list(list))),
+            EntryField::Note(s) => Some(Primary("note", json!(s))),
+            EntryField::Number(s) => match entry.entry_type() {
+                EntryType::Article | EntryType::SuppPeriodical => Some(Primary("issue", json!(s))),
+                _ => Some(Primary("number", json!(s))),
+            },
+            EntryField::OrigDate(date) => {
+                Self::date(date).map(|date| Primary("original-date", date))
+            }
+            EntryField::OrigLocation(list) => {
+                Some(Primary("original-publisher-place", Self::list(list)))
+            }
+            EntryField::OrigPublisher(list) => {
+                Some(Primary("original-publisher", Self::list(list)))
+            }
+            EntryField::OrigTitle(s) => Some(Primary("original-title", json!(s))),
+            EntryField::Pages(pages) => Some(Primary("page", json!(Self::pages(pages)))),
+            EntryField::PageTotal(n) => Some(Primary("number-of-pages", json!(n.to_string()))),
+            EntryField::Part(s) => Some(Primary("part-number", json!(s))),
+            EntryField::Publisher(list) => Some(Primary("publisher", Self::list(list))),
+            EntryField::PubState(state) => Some(Primary("status", json!(state.to_string()))),
+            EntryField::Series(s) => Some(Primary("collection-title", json!(s))),
+            EntryField::ShortJournal(s) => Some(Primary("container-title-short", json!(s))),
+            EntryField::ShortTitle(s) => Some(Primary("title-short", json!(s))),
+            EntryField::Title(title) => Some(Primary(
+                "title",
+                Self::title(title, Self::find(entry, Self::subtitle)),
+            )),
+            EntryField::Translator(people) => Some(Primary("translator", Self::names(people))),
+            EntryField::Type(s) => Some(Primary("genre", json!(s))),
+            EntryField::Url(s) => Some(Primary("URL", json!(s))),
+            EntryField::UrlDate(date) => Self::date(date).map(|date| Primary("accessed", date)),
+            EntryField::Venue(s) => Some(Primary("event-place", json!(s))),
+            EntryField::Version(s) => Some(Primary("version", json!(s))),
+            EntryField::Volume(s) => Some(Primary("volume", json!(s))),
+            EntryField::Year(Year::PubState(state)) => {
+                Some(Fallback("status", json!(state.to_string())))
+            }
+            EntryField::Year(year) => Self::year(year, Self::find(entry, Self::month))
+                .map(|date| Fallback("issued", date)),
+            _ => None,
+        }
+    }
+
+    fn find<'a, T>(entry: &'a Entry, f: fn(&'a EntryField) -> Option<T>) -> Option<T> {
+        entry.fields().iter().find_map(f)
+    }
+
+    fn entry_subtype(field: &EntryField) -> Option<String> {
+        match field {
+            EntryField::EntrySubtype(s) => Some(s.to_lowercase()),
+            _ => None,
+        }
+    }
+
+    fn subtitle(field: &EntryField) -> Option<&String> {
+        match field {
+            EntryField::Subtitle(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn book_subtitle(field: &EntryField) -> Option<&String> {
+        match field {
+            EntryField::BookSubtitle(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn journal_subtitle(field: &EntryField) -> Option<&String> {
+        match field {
+            EntryField::JournalSubtitle(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn event_end_date(field: &EntryField) -> Option<Date> {
+        match field {
+            EntryField::EventEndDate(date) => Some(date.clone()),
+            _ => None,
+        }
+    }
+
+    fn month(field: &EntryField) -> Option<u8> {
+        match field {
+            EntryField::Month(Date::Month(m)) => Some(*m),
+            _ => None,
+        }
+    }
+
+    fn title(title: &str, subtitle: Option<&String>) -> Value {
+        match subtitle {
+            Some(subtitle) => json!(format!("{}: {}", title, subtitle)),
+            None => json!(title),
+        }
+    }
+
+    fn list(list: &LiteralList) -> Value {
+        json!(list.items().join("; "))
+    }
+
+    fn pages(pages: &[Pages]) -> String {
+        pages
+            .iter()
+            .map(|pages| pages.to_string().replace("--", "-"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn names(people: &[Person]) -> Value {
+        Value::Array(people.iter().map(Self::name).collect())
+    }
+
+    /// Only names written in braces are literal, others are split even without a given name,
+    /// e.g. `Aristotle`.
+    fn name(person: &Person) -> Value {
+        if let Person::Literal(name) = person {
+            return json!({ "literal": name });
+        }
+        let mut name = Map::new();
+        let (particle, family) = person.von_and_last();
+        name.insert(s!("family"), json!(family));
+        if let Some(given) = person.given_names() {
+            name.insert(s!("given"), json!(given));
+        }
+        if let Some(particle) = particle {
+            name.insert(s!("non-dropping-particle"), json!(particle));
+        }
+        if let Some(suffix) = person.suffix() {
+            name.insert(s!("suffix"), json!(suffix));
+        }
+        Value::Object(name)
+    }
+
+    fn date(date: &Date) -> Option<Value> {
+        let parts: Vec<Value> = match date {
+            Date::Range(start, end) => std::iter::once(start)
+                .chain(end)
+                .map(|date| Self::date_parts(date))
+                .collect::<Option<Vec<Value>>>()?,
+            date => vec![Self::date_parts(date)?],
+        };
+        Some(json!({ "date-parts": parts }))
+    }
+
+    fn date_parts(date: &Date) -> Option<Value> {
+        match date {
+            Date::YearMonthDay(y, m, d) => Some(json!([y, m, d])),
+            Date::YearMonth(y, m) => Some(json!([y, m])),
+            Date::Year(y) => Some(json!([y])),
+            Date::Month(_) => None,
+            Date::Range(start, _) => Self::date_parts(start),
+        }
+    }
+
+    fn year(year: &Year, month: Option<u8>) -> Option<Value> {
+        let parts = |y: &i32| match month {
+            Some(m) => json!([[y, m]]),
+            None => json!([[y]]),
+        };
+        match year {
+            Year::Numeric(y) | Year::Suffixed(y, _) | Year::Uncertain(y) => {
+                Some(json!({ "date-parts": parts(y) }))
+            }
+            Year::Circa(y) => Some(json!({ "date-parts": parts(y), "circa": true })),
+            Year::Literal(s) => Some(json!({ "literal": s })),
+            Year::PubState(_) | Year::NoDate => None,
+        }
+    }
+}
+
+/// Writes entries as CSL-YAML, a `references` list pandoc reads both from bibliography
+/// files and from the front matter of Markdown documents.
+pub(crate) struct CslYamlWriter;
+
+impl CslYamlWriter {
+    pub(crate) fn write(entries: &[Entry]) -> String {
+        let references = json!({ "references": CslJsonWriter::items(entries) });
+        format!("---\n{}...\n", serde_yaml::to_string(&references).unwrap())
+    }
+}
+
+/// Reads CSL-JSON, e.g. as exported by Zotero, into entries. Variables without
+/// a biblatex field are kept as `Other` fields under their CSL name.
+pub(crate) struct CslJsonReader;
+
+impl CslJsonReader {
+    /// CSL variables holding plain values, with the biblatex fields they are read into.
+    const VARIABLES: [(&'static str, &'static str); 26] = [
+        ("abstract", "abstract"),
+        ("annote", "annotation"),
+        ("chapter-number", "chapter"),
+        ("collection-title", "series"),
+        ("container-title-short", "shortjournal"),
+        ("DOI", "doi"),
+        ("edition", "edition"),
+        ("event", "eventtitle"),
+        ("event-place", "venue"),
+        ("event-title", "eventtitle"),
+        ("genre", "type"),
+        ("ISBN", "isbn"),
+        ("ISSN", "issn"),
+        ("keyword", "keywords"),
+        ("language", "language"),
+        ("note", "note"),
+        ("number-of-pages", "pagetotal"),
+        ("original-title", "origtitle"),
+        ("page", "pages"),
+        ("part-number", "part"),
+        ("status", "pubstate"),
+        ("title", "title"),
+        ("title-short", "shorttitle"),
+        ("URL", "url"),
+        ("version", "version"),
+        ("volume", "volume"),
+    ];
+
+    /// CSL variables holding `; `-separated lists, with their biblatex fields.
+    const LIST_VARIABLES: [(&'static str, &'static str); 4] = [
+        ("publisher", "publisher"),
+        ("publisher-place", "location"),
+        ("original-publisher", "origpublisher"),
+        ("original-publisher-place", "origlocation"),
+    ];
+
+    pub(crate) fn read(input: &str) -> Result<Vec<Entry>, String> {
+        let items: Vec<Map<String, Value>> =
+            serde_json::from_str(input).map_err(|err| err.to_string())?;
+        items.iter().map(Self::entry).collect()
+    }
+
+    fn entry(item: &Map<String, Value>) -> Result<Entry, String> {
+        let symbol = item
+            .get("citation-key")
+            .or_else(|| item.get("id"))
+            .map(Self::text)
+            .ok_or_else(|| s!("CSL item without an id"))?;
+        let csl_type = item.get("type").map(Self::text).unwrap_or_default();
+        let (entry_type, subtype) = Self::entry_type(&csl_type);
+
+        let mut fields: Vec<EntryField> = subtype
+            .map(|subtype| EntryField::EntrySubtype(s!(subtype)))
+            .into_iter()
+            .collect();
+        for (name, value) in item {
+            if ["id", "citation-key", "type"].contains(&name.as_str()) {
+                continue;
+            }
+            let field = Self::field(&entry_type, name, value)
+                .map_err(|err| format!("Entry '{}': {}", symbol, err))?;
+            fields.extend(field);
+        }
+        Ok(Entry::new(entry_type, symbol, fields))
+    }
+
+    fn entry_type(csl_type: &str) -> (EntryType, Option<&'static str>) {
+        let entry_type = match csl_type {
+            "article" | "article-journal" | "article-magazine" | "article-newspaper" => {
+                EntryType::Article
+            }
+            "book" => EntryType::Book,
+            "chapter" => EntryType::InCollection,
+            "paper-conference" => EntryType::InProceedings,
+            "entry" | "entry-dictionary" | "entry-encyclopedia" => EntryType::InReference,
+            "periodical" => EntryType::Periodical,
+            "pamphlet" => EntryType::Booklet,
+            "webpage" | "post" | "post-weblog" => EntryType::Online,
+            "patent" => EntryType::Patent,
+            "report" => EntryType::Report,
+            "thesis" => EntryType::Thesis,
+            "manuscript" => EntryType::Unpublished,
+            "dataset" => EntryType::Dataset,
+            "software" => EntryType::Software,
+            "graphic" => EntryType::Artwork,
+            "song" => EntryType::Audio,
+            "figure" => EntryType::Image,
+            "legal_case" => EntryType::Jurisdiction,
+            "legislation" | "bill" => EntryType::Legislation,
+            "treaty" => EntryType::Legal,
+            "personal_communication" => EntryType::Letter,
+            "motion_picture" => EntryType::Movie,
+            "performance" => EntryType::Performance,
+            "review" | "review-book" => EntryType::Review,
+            "standard" => EntryType::Standard,
+            _ => EntryType::Misc,
+        };
+        let subtype = match csl_type {
+            "article-magazine" => Some("magazine"),
+            "article-newspaper" => Some("newspaper"),
+            _ => None,
+        };
+        (entry_type, subtype)
+    }
+
+    fn field(
+        entry_type: &EntryType,
+        name: &str,
+        value: &Value,
+    ) -> Result<Option<EntryField>, String> {
+        let is_article = matches!(entry_type, EntryType::Article | EntryType::SuppPeriodical);
+        let bib_field = |field_name: &str, raw: &str| {
+            EntryField::from_field_name_and_text(field_name, raw).map(Some)
+        };
+        match name {
+            "author" => Ok(Some(EntryField::Author(Self::names(value)))),
+            "editor" => Ok(Some(EntryField::Editor(Self::names(value)))),
+            "translator" => Ok(Some(EntryField::Translator(Self::names(value)))),
+            "container-author" => Ok(Some(EntryField::BookAuthor(Self::names(value)))),
+            "issued" => Ok(Self::issued(value)),
+            "accessed" => Ok(Self::date(value).map(EntryField::UrlDate)),
+            "event-date" => Ok(Self::date(value).map(EntryField::EventDate)),
+            "original-date" => Ok(Self::date(value).map(EntryField::OrigDate)),
+            "PMID" => Ok(Some(EntryField::EPrint(EPrint::parse(
+                &Self::text(value),
+                Some("pubmed"),
+                None,
+            )))),
+            "PMCID" => Ok(Some(EntryField::EPrint(EPrint::parse(
+                &Self::text(value),
+                Some("pmcid"),
+                None,
+            )))),
+            "container-title" if is_article => bib_field("journaltitle", &Self::text(value)),
+            "container-title" => bib_field("booktitle", &Self::text(value)),
+            "issue" if is_article => bib_field("number", &Self::text(value)),
+            "number" if is_article => bib_field("eid", &Self::text(value)),
+            "issue" | "number" => bib_field(name, &Self::text(value)),
+            _ => {
+                if let Some((_, field_name)) = Self::VARIABLES.iter().find(|(v, _)| *v == name) {
+                    return bib_field(field_name, &Self::text(value));
+                }
+                if let Some((_, field_name)) = Self::LIST_VARIABLES.iter().find(|(v, _)| *v == name)
+                {
+                    return bib_field(field_name, &Self::list(value));
+                }
+                Ok(Some(EntryField::Other(UnknownField::new(
+                    name,
+                    &Self::text(value),
+                ))))
+            }
+        }
+    }
+
+    /// Strings as they are, anything else, e.g. numbers, as JSON.
+    fn text(value: &Value) -> String {
+        match value {
+            Value::String(s) => s.clone(),
+            value => value.to_string(),
+        }
+    }
+
+    /// A `; `-separated list as a BibTeX `and`-list, with items containing `and` braced.
+    fn list(value: &Value) -> String {
+        Self::text(value)
+            .split(';')
+            .map(|item| protect_list_item(item.trim()))
+            .collect::<Vec<String>>()
+            .join(" and ")
+    }
+
+    fn names(value: &Value) -> Vec<Person> {
+        value
+            .as_array()
+            .map(|names| names.iter().filter_map(Self::name).collect())
+            .unwrap_or_default()
+    }
+
+    fn name(name: &Value) -> Option<Person> {
+        let part = |key: &str| name.get(key).map(Self::text).filter(|it| !it.is_empty());
+        if let Some(literal) = part("literal") {
+            return Some(Person::Literal(literal));
+        }
+        let family = part("family")?;
+        let last_name = [part("dropping-particle"), part("non-dropping-particle")]
+            .into_iter()
+            .flatten()
+            .chain([family])
+            .collect::<Vec<String>>()
+            .join(" ");
+        Some(Person::from_name_parts(
+            &last_name,
+            &part("given").unwrap_or_default(),
+            part("suffix").as_deref(),
+        ))
+    }
+
+    /// The date of `issued`, or its year if it is only given as circa or as a literal.
+    fn issued(value: &Value) -> Option<EntryField> {
+        let circa = value.get("circa").is_some_and(|circa| *circa != false);
+        match Self::date(value) {
+            Some(Date::Year(y)) if circa => Some(EntryField::Year(Year::Circa(y))),
+            Some(date) => Some(EntryField::Date(date)),
+            None => value
+                .get("literal")
+                .map(|literal| EntryField::Year(Year::parse(&Self::text(literal)))),
+        }
+    }
+
+    /// The `date-parts` as a date, or a range for two of them, falling back on a `raw` date.
+    fn date(value: &Value) -> Option<Date> {
+        let parts: Vec<Date> = value
+            .get("date-parts")
+            .and_then(Value::as_array)
+            .map(|parts| parts.iter().filter_map(Self::date_parts).collect())
+            .unwrap_or_default();
+        match &parts[..] {
+            [date] => Some(date.clone()),
+            [start, end, ..] => Some(Date::Range(
+                Box::new(start.clone()),
+                Some(Box::new(end.clone())),
+            )),
+            [] => value
+                .get("raw")
+                .and_then(|raw| Date::parse_date_from_str(&Self::text(raw)).ok()),
+        }
+    }
+
+    /// Parts may be numbers or strings. Seasons, given as months 21 to 24, are left out.
+    fn date_parts(parts: &Value) -> Option<Date> {
+        let parts: Vec<i64> = parts
+            .as_array()?
+            .iter()
+            .map(|part| match part {
+                Value::String(s) => s.trim().parse().ok(),
+                part => part.as_i64(),
+            })
+            .collect::<Option<Vec<i64>>>()?;
+        let year = i32::try_from(*parts.first()?).ok()?;
+        let month = parts
+            .get(1)
+            .and_then(|m| u8::try_from(*m).ok())
+            .filter(|m| (1..=12).contains(m));
+        let day = parts
+            .get(2)
+            .and_then(|d| u8::try_from(*d).ok())
+            .filter(|d| (1..=31).contains(d));
+        match (month, day) {
+            (Some(m), Some(d)) => Some(Date::YearMonthDay(year, m, d)),
+            (Some(m), None) => Some(Date::YearMonth(year, m)),
+            (None, _) => Some(Date::Year(year)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod csl_json_test {
+    use super::*;
+    use crate::doi::Doi;
+    use crate::entry::Parser;
+    use crate::tokenizer::Tokenizer;
+    use std::io::Cursor;
+
+    #[test]
+    fn write_items() {
+        // given
+        let input = r#"
+            @article{knuth-1984,
+                author = {Knuth, Donald E.},
+                title = {Literate Programming},
+                journaltitle = {The Computer Journal},
+                journal = {Comput. J.},
+                volume = {27}, number = {2},
+                pages = {97--111},
+                date = {1984-05},
+                doi = {10.1093/comjnl/27.2.97},
+                eprint = {6350001}, eprinttype = {pubmed},
+            }
+            @phdthesis{doe-thesis,
+                author = {Doe, John},
+                title = {Thesis}, subtitle = {A Study},
+                school = {MIT},
+                year = {ca. 2001}, month = {mar},
+            }"#;
+        let expected = json!([
+            {
+                "id": "knuth-1984",
+                "type": "article-journal",
+                "author": [{ "family": "Knuth", "given": "Donald E." }],
+                "title": "Literate Programming",
+                "container-title": "The Computer Journal",
+                "volume": "27",
+                "issue": "2",
+                "page": "97-111",
+                "issued": { "date-parts": [[1984, 5]] },
+                "DOI": "10.1093/comjnl/27.2.97",
+                "PMID": "6350001",
+            },
+            {
+                "id": "doe-thesis",
+                "type": "thesis",
+                "genre": "PhD thesis",
+                "author": [{ "family": "Doe", "given": "John" }],
+                "title": "Thesis: A Study",
+                "publisher": "MIT",
+                "issued": { "date-parts": [[2001, 3]], "circa": true },
+            },
+        ]);
+
+        // when
+        let actual = CslJsonWriter::write(&parse(input));
+
+        // then
+        assert_eq!(serde_json::from_str::<Value>(&actual).unwrap(), expected);
+    }
+
+    #[test]
+    fn write_yaml_references() {
+        // given
+        let input = r#"@book{knuth-tex,
+            author = {Knuth, Donald E.},
+            title = {The {\TeX}book: Computers \& Typesetting},
+            date = {1984},
+        }"#;
+        let expected = json!({
+            "references": [{
+                "id": "knuth-tex",
+                "type": "book",
+                "author": [{ "family": "Knuth", "given": "Donald E." }],
+                "title": "The TeXbook: Computers & Typesetting",
+                "issued": { "date-parts": [[1984]] },
+            }],
+        });
+
+        // when
+        let actual = CslYamlWriter::write(&parse(input));
+
+        // then
+        assert!(actual.starts_with("---\nreferences:\n- "));
+        assert!(actual.ends_with("\n...\n"));
+        assert_eq!(serde_yaml::from_str::<Value>(&actual).unwrap(), expected);
+    }
+
+    #[test]
+    fn decode_latex() {
+        // given
+        let input = r#"@article{mueller,
+            author = {M{\"u}ller, J{\"o}rg and {\O}stergaard, S{\o}ren},
+            title = {Caf{\'e} \& Bar: {NASA} in 1990--2000},
+            url = {https://example.com/caf\_e},
+        }"#;
+        let expected = json!([{
+            "id": "mueller",
+            "type": "article-journal",
+            "author": [
+                { "family": "Müller", "given": "Jörg" },
+                { "family": "Østergaard", "given": "Søren" },
+            ],
+            "title": "Café & Bar: NASA in 1990–2000",
+            "URL": "https://example.com/caf\\_e",
+        }]);
+
+        // when
+        let actual = CslJsonWriter::write(&parse(input));
+
+        // then
+        assert_eq!(serde_json::from_str::<Value>(&actual).unwrap(), expected);
+    }
+
+    #[test]
+    fn map_names() {
+        // given
+        [
+            (
+                "van Gogh, Vincent Willem",
+                json!({ "family": "Gogh", "given": "Vincent Willem", "non-dropping-particle": "van" }),
+            ),
+            (
+                "Ford, Jr., Henry",
+                json!({ "family": "Ford", "given": "Henry", "suffix": "Jr." }),
+            ),
+            ("{World Health Organization}", json!({ "literal": "World Health Organization" })),
+            (
+                "Ludwig van Beethoven",
+                json!({ "family": "Beethoven", "given": "Ludwig", "non-dropping-particle": "van" }),
+            ),
+            ("Aristotle", json!({ "family": "Aristotle" })),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let people = Person::people_from_str(input).unwrap();
+            let actual = CslJsonWriter::name(&people[0]);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn map_dates() {
+        // given
+        [
+            ("2004-03-02", Some(json!({ "date-parts": [[2004, 3, 2]] }))),
+            (
+                "2004/2005-06",
+                Some(json!({ "date-parts": [[2004], [2005, 6]] })),
+            ),
+            ("2004/", Some(json!({ "date-parts": [[2004]] }))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = CslJsonWriter::date(&Date::parse_date_from_str(input).unwrap());
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn read_items() {
+        // given
+        let input = r#"[{
+            "id": "http://zotero.org/users/1/items/ABCD",
+            "citation-key": "knuth-1984",
+            "type": "article-magazine",
+            "author": [
+                { "family": "Knuth", "given": "Donald E." },
+                { "family": "Gogh", "given": "Vincent", "non-dropping-particle": "van" },
+                { "family": "Humboldt", "given": "Alexander", "dropping-particle": "von" },
+                { "literal": "ACM" }
+            ],
+            "title": "Literate Programming",
+            "container-title": "The Computer Journal",
+            "issue": 2,
+            "page": "97-111",
+            "issued": { "date-parts": [["1984", "5"]] },
+            "publisher": "Barnes and Noble; Penguin",
+            "DOI": "10.1093/comjnl/27.2.97",
+            "archive": "Internet Archive"
+        }]"#;
+        let expected = vec![Entry::new(
+            EntryType::Article,
+            s!("knuth-1984"),
+            vec![
+                EntryField::EntrySubtype(s!("magazine")),
+                EntryField::Doi(Doi::parse("10.1093/comjnl/27.2.97")),
+                EntryField::Other(UnknownField::new("archive", "Internet Archive")),
+                EntryField::Author(vec![
+                    Person::FirstMiddleLast {
+                        first_name: s!("Donald"),
+                        middle_names: vec![s!("E")],
+                        last_name: s!("Knuth"),
+                    },
+                    Person::FirstLast {
+                        first_name: s!("Vincent"),
+                        last_name: s!("van Gogh"),
+                    },
+                    Person::FirstLast {
+                        first_name: s!("Alexander"),
+                        last_name: s!("von Humboldt"),
+                    },
+                    Person::Literal(s!("ACM")),
+                ]),
+                EntryField::JournalTitle(s!("The Computer Journal")),
+                EntryField::Number(s!("2")),
+                EntryField::Date(Date::YearMonth(1984, 5)),
+                EntryField::Pages(Pages::pages_from_str("97--111")),
+                EntryField::Publisher(LiteralList::from_str("{Barnes and Noble} and Penguin")),
+                EntryField::Title(s!("Literate Programming")),
+            ],
+        )];
+
+        // when
+        let actual = CslJsonReader::read(input);
+
+        // then
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn round_trip() {
+        // given
+        let input = r#"
+            @inproceedings{a,
+                author = {de la Fontaine, Jean and Ford, Jr., Henry and {IEEE}},
+                title = {Title},
+                booktitle = {Proceedings},
+                eventdate = {2020-06-01/2020-06-03},
+                date = {2020},
+                location = {New York and London},
+                eprint = {PMC1234567}, eprinttype = {pmcid},
+            }
+            @thesis{b, author = {Doe, John}, title = {Thesis}, type = {phdthesis}, year = {ca. 2001}}"#;
+        let expected = CslJsonWriter::write(&parse(input));
+
+        // when
+        let actual = CslJsonWriter::write(&CslJsonReader::read(&expected).unwrap());
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    fn parse(input: &str) -> Vec<Entry> {
+        Parser::new(Tokenizer::new(Box::new(Cursor::new(s!(input)))))
+            .parse()
+            .unwrap()
+    }
+}
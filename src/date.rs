@@ -1,13 +1,15 @@
 use chrono::Month;
 use num_traits::FromPrimitive;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Debug, Copy, Clone, Serialize)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub(crate) enum Date {
     YearMonthDay(i32, u8, u8),
     YearMonth(i32, u8),
     Year(i32),
     Month(u8),
+    /// A `start/end` range, open-ended (`start/` or `start/..`) without an end.
+    Range(Box<Date>, Option<Box<Date>>),
 }
 
 impl Date {
@@ -31,6 +33,20 @@ impl Date {
     }
 
     pub(crate) fn parse_date_from_str(s: &str) -> Result<Date, String> {
+        match s.split_once('/') {
+            Some((start, "" | "..")) => Ok(Date::Range(
+                Box::new(Self::parse_single_date_from_str(start)?),
+                None,
+            )),
+            Some((start, end)) => Ok(Date::Range(
+                Box::new(Self::parse_single_date_from_str(start)?),
+                Some(Box::new(Self::parse_single_date_from_str(end)?)),
+            )),
+            None => Self::parse_single_date_from_str(s),
+        }
+    }
+
+    fn parse_single_date_from_str(s: &str) -> Result<Date, String> {
         let sections = s.split('-').collect::<Vec<&str>>();
         match sections[..] {
             [year] => Self::parse_year_from_str(year),
@@ -72,8 +88,44 @@ impl Date {
         }
     }
 
-    #[allow(dead_code)]
-    fn merge(date1: Date, date2: Date) -> Date {
+    /// A `YYYY/MM/DD/other` date, as used by RIS and EndNote, with any of the parts
+    /// after the year left empty, or an ISO `YYYY-MM-DD` date.
+    pub(crate) fn parse_slashed_from_str(s: &str) -> Option<Date> {
+        let parts: Vec<&str> = s.split(['/', '-']).map(str::trim).collect();
+        let year = parts.first()?.parse::<i32>().ok()?;
+        let part = |i: usize, max: u8| {
+            parts
+                .get(i)
+                .and_then(|it| it.parse::<u8>().ok())
+                .filter(|it| (1..=max).contains(it))
+        };
+        match (part(1, 12), part(2, 31)) {
+            (Some(month), Some(day)) => Some(Date::YearMonthDay(year, month, day)),
+            (Some(month), None) => Some(Date::YearMonth(year, month)),
+            (None, _) => Some(Date::Year(year)),
+        }
+    }
+
+    /// The date as `YYYY/MM/DD`, with the parts not known left out, and ranges by their start.
+    pub(crate) fn to_slashed(&self) -> Option<String> {
+        match self {
+            Date::YearMonthDay(y, m, d) => Some(format!("{}/{:02}/{:02}", y, m, d)),
+            Date::YearMonth(y, m) => Some(format!("{}/{:02}", y, m)),
+            Date::Year(y) => Some(y.to_string()),
+            Date::Month(_) => None,
+            Date::Range(start, _) => start.to_slashed(),
+        }
+    }
+
+    pub(crate) fn year(&self) -> Option<i32> {
+        match self {
+            Date::YearMonthDay(y, _, _) | Date::YearMonth(y, _) | Date::Year(y) => Some(*y),
+            Date::Month(_) => None,
+            Date::Range(start, _) => start.year(),
+        }
+    }
+
+    pub(crate) fn merge(date1: Date, date2: Date) -> Date {
         match date1 {
             Date::Year(y1) => match date2 {
                 Date::Month(m2) => Date::YearMonth(y1, m2),
@@ -88,9 +140,23 @@ impl Date {
     }
 }
 
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Date::YearMonthDay(y, m, d) => write!(f, "{}-{:02}-{:02}", y, m, d),
+            Date::YearMonth(y, m) => write!(f, "{}-{:02}", y, m),
+            Date::Year(y) => write!(f, "{}", y),
+            Date::Month(m) => write!(f, "{}", m),
+            Date::Range(start, Some(end)) => write!(f, "{}/{}", start, end),
+            Date::Range(start, None) => write!(f, "{}/", start),
+        }
+    }
+}
+
 #[cfg(test)]
 mod date_test {
     use super::*;
+    use crate::s;
 
     #[test]
     fn parse_date_full() {
@@ -131,11 +197,36 @@ mod date_test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn parse_date_range() {
+        // given
+        [
+            (
+                "2004-03/2005",
+                Ok(Date::Range(
+                    Box::new(Date::YearMonth(2004, 3)),
+                    Some(Box::new(Date::Year(2005))),
+                )),
+            ),
+            ("2004/", Ok(Date::Range(Box::new(Date::Year(2004)), None))),
+            ("2004/..", Ok(Date::Range(Box::new(Date::Year(2004)), None))),
+            ("/2005", Err(s!("Could not parse Year from ''"))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Date::parse_date_from_str(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
     #[test]
     fn parse_date_month() {
         // given
         let expected = Ok(Date::Month(3));
-        vec!["03", "3", "mar", "march", "Mar", "March"]
+        ["03", "3", "mar", "march", "Mar", "March"]
             .iter()
             .for_each(|input| {
                 // when
@@ -161,7 +252,7 @@ mod date_test {
     #[test]
     fn merge() {
         // given
-        vec![
+        [
             ((Date::Year(2004), Date::Month(3)), Date::YearMonth(2004, 3)),
             ((Date::Month(3), Date::Year(2004)), Date::YearMonth(2004, 3)),
             (
@@ -172,7 +263,7 @@ mod date_test {
         .iter()
         .for_each(|((date1, date2), expected)| {
             // when
-            let actual = Date::merge(*date1, *date2);
+            let actual = Date::merge(date1.clone(), date2.clone());
 
             // then
             assert_eq!(actual, *expected);
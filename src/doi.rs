@@ -0,0 +1,238 @@
+use crate::s;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum Doi {
+    Valid(String),
+    Invalid(String),
+}
+
+impl Doi {
+    const DIRECTORY_INDICATOR: &'static str = "10.";
+    const RESOLVER: &'static str = "https://doi.org/";
+    const PREFIXES: [&'static str; 8] = [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "https://www.doi.org/",
+        "doi.org/",
+        "doi:",
+        "doi",
+    ];
+    const TRAILING_PUNCTUATION: [char; 5] = ['.', ',', ';', ')', ']'];
+
+    pub(crate) fn parse(s: &str) -> Doi {
+        let trimmed = s.trim();
+        let without_prefix = Self::strip_resolver_prefix(trimmed);
+        match Self::is_valid(without_prefix) {
+            true => Doi::Valid(s!(without_prefix)),
+            false => Doi::Invalid(s!(trimmed)),
+        }
+    }
+
+    /// Finds the first valid DOI embedded in free text or a URL, e.g. in `url` or `note`.
+    pub(crate) fn find_in(text: &str) -> Option<Doi> {
+        let decoded = text.replace("%2F", "/").replace("%2f", "/");
+        decoded
+            .match_indices(Self::DIRECTORY_INDICATOR)
+            .filter(|(i, _)| {
+                decoded[..*i]
+                    .chars()
+                    .next_back()
+                    .is_none_or(|c| !c.is_alphanumeric() && c != '.')
+            })
+            .map(|(i, _)| {
+                decoded[i..]
+                    .split(|c: char| c.is_whitespace() || matches!(c, '"' | '<' | '>' | '?' | '#'))
+                    .next()
+                    .unwrap_or_default()
+                    .trim_end_matches(Self::TRAILING_PUNCTUATION)
+            })
+            .find(|candidate| Self::is_valid(candidate))
+            .map(|doi| Doi::Valid(s!(doi)))
+    }
+
+    pub(crate) fn to_url(&self) -> Option<String> {
+        match self {
+            Doi::Valid(doi) => Some(format!("{}{}", Self::RESOLVER, Self::url_encode(doi))),
+            Doi::Invalid(_) => None,
+        }
+    }
+
+    pub(crate) fn validation_warning(&self) -> Option<String> {
+        match self {
+            Doi::Invalid(s) => Some(format!("Invalid DOI '{}'", s)),
+            _ => None,
+        }
+    }
+
+    fn strip_resolver_prefix(s: &str) -> &str {
+        let lowercase = s.to_lowercase();
+        Self::PREFIXES
+            .iter()
+            .find(|prefix| lowercase.starts_with(*prefix))
+            .map(|prefix| s[prefix.len()..].trim_start())
+            .unwrap_or(s)
+    }
+
+    /// Checks the `10.<registrant>/<suffix>` syntax, where the registrant code
+    /// is a dot-separated sequence of numbers.
+    fn is_valid(s: &str) -> bool {
+        let Some((prefix, suffix)) = s.split_once('/') else {
+            return false;
+        };
+        let Some(registrant) = prefix.strip_prefix(Self::DIRECTORY_INDICATOR) else {
+            return false;
+        };
+        let is_valid_registrant = registrant
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+        let is_valid_suffix = !suffix.is_empty() && !suffix.chars().any(char::is_whitespace);
+        is_valid_registrant && is_valid_suffix
+    }
+
+    fn url_encode(doi: &str) -> String {
+        doi.chars()
+            .map(|c| match c {
+                '%' => s!("%25"),
+                '"' => s!("%22"),
+                '#' => s!("%23"),
+                '?' => s!("%3F"),
+                '<' => s!("%3C"),
+                '>' => s!("%3E"),
+                '{' => s!("%7B"),
+                '}' => s!("%7D"),
+                '^' => s!("%5E"),
+                c => c.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Doi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Doi::Valid(s) | Doi::Invalid(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl PartialEq for Doi {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Doi::Valid(a), Doi::Valid(b)) => a.to_lowercase() == b.to_lowercase(),
+            (Doi::Invalid(a), Doi::Invalid(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod doi_test {
+    use super::*;
+
+    #[test]
+    fn parse_from_str() {
+        // given
+        let expected = Doi::Valid(s!("10.1145/3368089.3409765"));
+        [
+            "10.1145/3368089.3409765",
+            "https://doi.org/10.1145/3368089.3409765",
+            "http://dx.doi.org/10.1145/3368089.3409765",
+            "doi:10.1145/3368089.3409765",
+            "DOI 10.1145/3368089.3409765",
+            "DOI: 10.1145/3368089.3409765",
+        ]
+        .iter()
+        .for_each(|input| {
+            // when
+            let actual = Doi::parse(input);
+
+            // then
+            assert_eq!(actual, expected);
+        });
+    }
+
+    #[test]
+    fn parse_invalid_from_str() {
+        // given
+        [
+            "11.1145/3368089.3409765",
+            "10.1145",
+            "10.abc/def",
+            "10.1145/ with space",
+        ]
+        .iter()
+        .for_each(|input| {
+            // when
+            let actual = Doi::parse(input);
+
+            // then
+            assert_eq!(actual, Doi::Invalid(s!(*input)));
+        });
+    }
+
+    #[test]
+    fn compare_case_insensitive() {
+        // given
+        let lowercase = Doi::parse("10.1109/ieeestd.2018.8457469");
+        let uppercase = Doi::parse("10.1109/IEEESTD.2018.8457469");
+
+        // then
+        assert_eq!(lowercase, uppercase);
+    }
+
+    #[test]
+    fn render_as_url() {
+        // given
+        [
+            (
+                "10.1145/3368089.3409765",
+                Some("https://doi.org/10.1145/3368089.3409765"),
+            ),
+            (
+                "10.1002/(SICI)1097-4571<3::AID>",
+                Some("https://doi.org/10.1002/(SICI)1097-4571%3C3::AID%3E"),
+            ),
+            ("not a doi", None),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Doi::parse(input).to_url();
+
+            // then
+            assert_eq!(actual, expected.map(|it| s!(it)));
+        });
+    }
+
+    #[test]
+    fn find_in_text() {
+        // given
+        [
+            (
+                "https://dl.acm.org/doi/10.1145/3368089.3409765",
+                Some(Doi::Valid(s!("10.1145/3368089.3409765"))),
+            ),
+            (
+                "https://example.com/resolve?id=10.1145%2F3368089.3409765",
+                Some(Doi::Valid(s!("10.1145/3368089.3409765"))),
+            ),
+            (
+                "Available at doi:10.1145/3368089.3409765.",
+                Some(Doi::Valid(s!("10.1145/3368089.3409765"))),
+            ),
+            ("Version 10.2 of the manual", None),
+            ("https://example.com/v2.10.1/x", None),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Doi::find_in(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+}
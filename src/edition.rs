@@ -1,21 +1,155 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Debug, Clone, Serialize)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub(crate) enum Edition {
     Numeric(u32),
+    Qualified(u32, String),
     Literal(String),
 }
 
+/// Language specific words used to recognise editions like `2nd`, `Second` or `zweite Auflage`.
+pub(crate) struct OrdinalTable {
+    pub(crate) words: &'static [(&'static str, u32)],
+    pub(crate) suffixes: &'static [&'static str],
+    pub(crate) edition_words: &'static [&'static str],
+}
+
+impl OrdinalTable {
+    pub(crate) const ENGLISH: OrdinalTable = OrdinalTable {
+        words: &[
+            ("first", 1),
+            ("second", 2),
+            ("third", 3),
+            ("fourth", 4),
+            ("fifth", 5),
+            ("sixth", 6),
+            ("seventh", 7),
+            ("eighth", 8),
+            ("ninth", 9),
+            ("tenth", 10),
+            ("eleventh", 11),
+            ("twelfth", 12),
+            ("thirteenth", 13),
+            ("fourteenth", 14),
+            ("fifteenth", 15),
+            ("sixteenth", 16),
+            ("seventeenth", 17),
+            ("eighteenth", 18),
+            ("nineteenth", 19),
+            ("twentieth", 20),
+        ],
+        suffixes: &["st", "nd", "rd", "th"],
+        edition_words: &["edition", "ed", "edn"],
+    };
+
+    pub(crate) const GERMAN: OrdinalTable = OrdinalTable {
+        words: &[
+            ("erste", 1),
+            ("zweite", 2),
+            ("dritte", 3),
+            ("vierte", 4),
+            ("fünfte", 5),
+            ("sechste", 6),
+            ("siebte", 7),
+            ("achte", 8),
+            ("neunte", 9),
+            ("zehnte", 10),
+        ],
+        suffixes: &[],
+        edition_words: &["auflage", "aufl", "ausgabe"],
+    };
+
+    pub(crate) const FRENCH: OrdinalTable = OrdinalTable {
+        words: &[
+            ("première", 1),
+            ("premier", 1),
+            ("deuxième", 2),
+            ("seconde", 2),
+            ("troisième", 3),
+            ("quatrième", 4),
+            ("cinquième", 5),
+            ("sixième", 6),
+            ("septième", 7),
+            ("huitième", 8),
+            ("neuvième", 9),
+            ("dixième", 10),
+        ],
+        suffixes: &["e", "ème", "re", "er"],
+        edition_words: &["édition", "éd"],
+    };
+
+    pub(crate) const DEFAULT_TABLES: [OrdinalTable; 3] =
+        [Self::ENGLISH, Self::GERMAN, Self::FRENCH];
+}
+
 impl Edition {
     pub(crate) fn parse(s: &str) -> Edition {
-        let without_trailing_dot = s
-            .to_string()
-            .strip_suffix('.')
-            .map(|it| it.to_string())
-            .unwrap_or(s.to_string());
-        match without_trailing_dot.parse::<u32>() {
-            Ok(i) => Edition::Numeric(i),
-            Err(_) => Edition::Literal(without_trailing_dot),
+        Self::parse_with_tables(s, &OrdinalTable::DEFAULT_TABLES)
+    }
+
+    pub(crate) fn parse_with_tables(s: &str, tables: &[OrdinalTable]) -> Edition {
+        let trimmed = s.trim();
+        let mut words = trimmed.split_whitespace();
+        let number = words
+            .next()
+            .and_then(|first| Self::parse_ordinal(first, tables));
+        let qualifier = words
+            .filter(|word| !Self::is_edition_word(word, tables))
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        match (number, qualifier.is_empty()) {
+            (Some(n), true) => Edition::Numeric(n),
+            (Some(n), false) => Edition::Qualified(n, qualifier),
+            (None, _) => Edition::Literal(Self::without_trailing_dot(trimmed).to_string()),
+        }
+    }
+
+    fn parse_ordinal(word: &str, tables: &[OrdinalTable]) -> Option<u32> {
+        let normalized = Self::without_punctuation(word).to_lowercase();
+        let digits_end = normalized
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(normalized.len());
+        let (digits, suffix) = normalized.split_at(digits_end);
+
+        if !digits.is_empty() {
+            let is_known_suffix =
+                suffix.is_empty() || tables.iter().any(|table| table.suffixes.contains(&suffix));
+            return match is_known_suffix {
+                true => digits.parse::<u32>().ok(),
+                false => None,
+            };
+        }
+
+        tables
+            .iter()
+            .flat_map(|table| table.words.iter())
+            .find(|(ordinal, _)| *ordinal == normalized)
+            .map(|(_, n)| *n)
+    }
+
+    fn is_edition_word(word: &str, tables: &[OrdinalTable]) -> bool {
+        let normalized = Self::without_punctuation(word).to_lowercase();
+        tables
+            .iter()
+            .any(|table| table.edition_words.contains(&normalized.as_str()))
+    }
+
+    fn without_punctuation(s: &str) -> &str {
+        s.trim_end_matches([',', '.'])
+    }
+
+    fn without_trailing_dot(s: &str) -> &str {
+        s.strip_suffix('.').unwrap_or(s)
+    }
+}
+
+impl std::fmt::Display for Edition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Edition::Numeric(n) => write!(f, "{}", n),
+            Edition::Qualified(n, qualifier) => write!(f, "{} {}", n, qualifier),
+            Edition::Literal(s) => write!(f, "{}", s),
         }
     }
 }
@@ -28,10 +162,12 @@ mod edition_test {
     #[test]
     fn parse_from_str() {
         // given
-        vec![
+        [
             ("2", Edition::Numeric(2)),
             ("2.", Edition::Numeric(2)),
-            ("Second", Edition::Literal(s!("Second"))),
+            ("Second", Edition::Numeric(2)),
+            ("Limited", Edition::Literal(s!("Limited"))),
+            ("Collector's ed.", Edition::Literal(s!("Collector's ed"))),
         ]
         .iter()
         .for_each(|(input, expected)| {
@@ -42,4 +178,72 @@ mod edition_test {
             assert_eq!(actual, *expected);
         });
     }
+
+    #[test]
+    fn parse_ordinal_from_str() {
+        // given
+        [
+            ("2nd", Edition::Numeric(2)),
+            ("21st", Edition::Numeric(21)),
+            ("3rd ed.", Edition::Numeric(3)),
+            ("Third edition", Edition::Numeric(3)),
+            ("zweite Auflage", Edition::Numeric(2)),
+            ("2. Aufl.", Edition::Numeric(2)),
+            ("2e", Edition::Numeric(2)),
+            ("2e éd.", Edition::Numeric(2)),
+            ("2x", Edition::Literal(s!("2x"))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Edition::parse(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn parse_qualified_from_str() {
+        // given
+        [
+            ("3rd rev.", Edition::Qualified(3, s!("rev."))),
+            ("2nd rev. ed.", Edition::Qualified(2, s!("rev."))),
+            (
+                "Second revised and expanded edition",
+                Edition::Qualified(2, s!("revised and expanded")),
+            ),
+            (
+                "zweite, überarbeitete Auflage",
+                Edition::Qualified(2, s!("überarbeitete")),
+            ),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Edition::parse(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn parse_with_custom_table() {
+        // given
+        const POLISH: OrdinalTable = OrdinalTable {
+            words: &[("pierwsze", 1), ("drugie", 2)],
+            suffixes: &[],
+            edition_words: &["wydanie", "wyd"],
+        };
+        let input = "Drugie wydanie";
+
+        // when
+        let with_polish = Edition::parse_with_tables(input, &[POLISH]);
+        let with_defaults = Edition::parse(input);
+
+        // then
+        assert_eq!(with_polish, Edition::Numeric(2));
+        assert_eq!(with_defaults, Edition::Literal(s!("Drugie wydanie")));
+    }
 }
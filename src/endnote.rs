@@ -0,0 +1,987 @@
+use crate::date::Date;
+use crate::entry::Entry;
+use crate::entry_field::{EntryField, UnknownField};
+use crate::entry_type::EntryType;
+use crate::keywords::Keywords;
+use crate::literal_list::LiteralList;
+use crate::pages::Pages;
+use crate::person::Person;
+use crate::s;
+use crate::standard_number::Isbn;
+use crate::strings::escape_xml;
+use crate::year::Year;
+use roxmltree::Node;
+
+/// An EndNote record as `(key, value)` pairs, keyed by the EndNote XML element names.
+type Record = Vec<(&'static str, String)>;
+
+/// The mapping between EndNote records and entries, shared by the XML and the Refer format.
+/// Fields without a biblatex field are kept as `Other` fields named after their key.
+struct EndNote;
+
+impl EndNote {
+    /// The fields as `(key, Refer tag, XML path)`, in the order EndNote writes them.
+    const FIELDS: [(&'static str, &'static str, &'static str); 50] = [
+        ("ref-type", "%0", "ref-type"),
+        ("authors", "%A", "contributors/authors/author"),
+        (
+            "secondary-authors",
+            "%E",
+            "contributors/secondary-authors/author",
+        ),
+        (
+            "tertiary-authors",
+            "%Y",
+            "contributors/tertiary-authors/author",
+        ),
+        (
+            "subsidiary-authors",
+            "%?",
+            "contributors/subsidiary-authors/author",
+        ),
+        (
+            "translated-authors",
+            "%H",
+            "contributors/translated-authors/author",
+        ),
+        ("auth-address", "%+", "auth-address"),
+        ("title", "%T", "titles/title"),
+        ("secondary-title", "%B", "titles/secondary-title"),
+        ("tertiary-title", "%S", "titles/tertiary-title"),
+        ("alt-title", "%O", "titles/alt-title"),
+        ("short-title", "%!", "titles/short-title"),
+        ("translated-title", "%Q", "titles/translated-title"),
+        ("full-title", "%J", "periodical/full-title"),
+        ("pages", "%P", "pages"),
+        ("volume", "%V", "volume"),
+        ("number", "%N", "number"),
+        ("num-vols", "%6", "num-vols"),
+        ("edition", "%7", "edition"),
+        ("section", "%&", "section"),
+        ("keywords", "%K", "keywords/keyword"),
+        ("year", "%D", "dates/year"),
+        ("pub-dates", "%8", "dates/pub-dates/date"),
+        ("pub-location", "%C", "pub-location"),
+        ("publisher", "%I", "publisher"),
+        ("isbn", "%@", "isbn"),
+        ("accession-num", "%M", "accession-num"),
+        ("call-num", "%L", "call-num"),
+        ("label", "%F", "label"),
+        ("work-type", "%9", "work-type"),
+        ("abstract", "%X", "abstract"),
+        ("notes", "%Z", "notes"),
+        ("research-notes", "%<", "research-notes"),
+        ("urls", "%U", "urls/related-urls/url"),
+        ("electronic-resource-num", "%R", "electronic-resource-num"),
+        ("language", "%G", "language"),
+        ("access-date", "%[", "access-date"),
+        ("custom1", "%1", "custom1"),
+        ("custom2", "%2", "custom2"),
+        ("custom3", "%3", "custom3"),
+        ("custom4", "%4", "custom4"),
+        ("custom5", "%#", "custom5"),
+        ("custom6", "%$", "custom6"),
+        ("custom7", "%]", "custom7"),
+        ("caption", "%^", "caption"),
+        ("orig-pub", "%(", "orig-pub"),
+        ("reprint-edition", "%)", "reprint-edition"),
+        ("reviewed-item", "%*", "reviewed-item"),
+        ("remote-database-name", "%~", "remote-database-name"),
+        ("remote-database-provider", "%W", "remote-database-provider"),
+    ];
+
+    /// Keys read into their own fields, or used for the type and key.
+    const MAPPED_KEYS: [&'static str; 28] = [
+        "ref-type",
+        "authors",
+        "secondary-authors",
+        "translated-authors",
+        "title",
+        "secondary-title",
+        "full-title",
+        "alt-title",
+        "tertiary-title",
+        "short-title",
+        "year",
+        "pub-dates",
+        "access-date",
+        "pages",
+        "volume",
+        "number",
+        "edition",
+        "electronic-resource-num",
+        "abstract",
+        "work-type",
+        "notes",
+        "urls",
+        "pub-location",
+        "publisher",
+        "language",
+        "keywords",
+        "isbn",
+        "label",
+    ];
+
+    /// The reference types as `(name, number)`, the XML format giving both.
+    const TYPES: [(&'static str, u8); 36] = [
+        ("Artwork", 2),
+        ("Audiovisual Material", 3),
+        ("Bill", 4),
+        ("Book Section", 5),
+        ("Book", 6),
+        ("Case", 7),
+        ("Computer Program", 9),
+        ("Conference Proceedings", 10),
+        ("Web Page", 12),
+        ("Generic", 13),
+        ("Journal Article", 17),
+        ("Magazine Article", 19),
+        ("Film or Broadcast", 21),
+        ("Newspaper Article", 23),
+        ("Pamphlet", 24),
+        ("Patent", 25),
+        ("Personal Communication", 26),
+        ("Report", 27),
+        ("Edited Book", 28),
+        ("Statute", 31),
+        ("Thesis", 32),
+        ("Unpublished Work", 34),
+        ("Manuscript", 36),
+        ("Figure", 37),
+        ("Electronic Article", 43),
+        ("Electronic Book", 44),
+        ("Government Document", 46),
+        ("Conference Paper", 47),
+        ("Dictionary", 52),
+        ("Encyclopedia", 53),
+        ("Blog", 56),
+        ("Serial", 57),
+        ("Standard", 58),
+        ("Dataset", 59),
+        ("Electronic Book Section", 60),
+        ("Music", 61),
+    ];
+
+    fn entry(record: &[(&'static str, String)], symbols: &[String]) -> Result<Entry, String> {
+        let values = |key: &str| -> Vec<&str> {
+            record
+                .iter()
+                .filter(|(it, value)| *it == key && !value.trim().is_empty())
+                .map(|(_, value)| value.trim())
+                .collect()
+        };
+        let first = |key: &str| values(key).first().copied();
+        let other = |key: &str, value: &str| EntryField::Other(UnknownField::new(key, value));
+        let (entry_type, subtype) = Self::entry_type(first("ref-type").unwrap_or_default());
+        let is_article = entry_type == EntryType::Article;
+        let bib_field = EntryField::from_field_name_and_text;
+
+        let mut fields: Vec<EntryField> = vec![];
+        if let Some(subtype) = subtype {
+            fields.push(EntryField::EntrySubtype(s!(subtype)));
+        }
+        for (key, field) in [
+            (
+                "authors",
+                EntryField::Author as fn(Vec<Person>) -> EntryField,
+            ),
+            ("secondary-authors", EntryField::Editor),
+            ("translated-authors", EntryField::Translator),
+        ] {
+            let people: Vec<Person> = values(key)
+                .iter()
+                .map(|name| Person::from_last_first(name))
+                .collect();
+            if !people.is_empty() {
+                fields.push(field(people));
+            }
+        }
+        if let Some(title) = first("title") {
+            fields.push(bib_field("title", title)?);
+        }
+        let secondary_title = first("secondary-title");
+        match first("full-title").or(secondary_title.filter(|_| is_article)) {
+            Some(journal) => fields.push(bib_field("journaltitle", journal)?),
+            None => {
+                if let Some(book_title) = secondary_title {
+                    fields.push(bib_field("booktitle", book_title)?);
+                }
+            }
+        }
+        match (first("alt-title"), is_article) {
+            (Some(short), true) => fields.push(bib_field("shortjournal", short)?),
+            (Some(alt), false) => fields.push(other("alt-title", alt)),
+            (None, _) => {}
+        }
+        let date = first("pub-dates")
+            .and_then(Date::parse_slashed_from_str)
+            .filter(|date| !matches!(date, Date::Year(_)));
+        match date {
+            Some(date) => fields.push(EntryField::Date(date)),
+            None => {
+                fields.extend(first("year").map(|year| EntryField::Year(Year::parse(year))));
+                fields.extend(first("pub-dates").map(|date| other("pub-dates", date)));
+            }
+        }
+        if let Some(access_date) = first("access-date") {
+            fields.push(match Date::parse_slashed_from_str(access_date) {
+                Some(date) => EntryField::UrlDate(date),
+                None => other("access-date", access_date),
+            });
+        }
+        if let Some(pages) = first("pages") {
+            fields.push(EntryField::Pages(Pages::pages_from_str(pages)));
+        }
+        for (key, field_name) in [
+            ("tertiary-title", "series"),
+            ("short-title", "shorttitle"),
+            ("volume", "volume"),
+            ("number", "number"),
+            ("edition", "edition"),
+            ("electronic-resource-num", "doi"),
+            ("abstract", "abstract"),
+            ("work-type", "type"),
+        ] {
+            if let Some(value) = first(key) {
+                fields.push(bib_field(field_name, value)?);
+            }
+        }
+        for (key, field) in [
+            (
+                "pub-location",
+                EntryField::Location as fn(LiteralList) -> EntryField,
+            ),
+            ("publisher", EntryField::Publisher),
+            ("language", EntryField::Language),
+        ] {
+            let items = values(key);
+            if !items.is_empty() {
+                fields.push(field(LiteralList::from_items(&items)));
+            }
+        }
+        let notes = values("notes");
+        if !notes.is_empty() {
+            fields.push(EntryField::Note(notes.join("\n")));
+        }
+        if let Some((url, others)) = values("urls").split_first() {
+            fields.push(EntryField::Url(s!(*url)));
+            fields.extend(others.iter().map(|url| other("urls", url)));
+        }
+        let keywords: Vec<&str> = values("keywords")
+            .iter()
+            .flat_map(|keywords| keywords.lines())
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .collect();
+        if !keywords.is_empty() {
+            fields.push(EntryField::Keywords(Keywords::from_items(&keywords)));
+        }
+        let (isbn, issn) = Isbn::split_isbn_and_issn(&values("isbn"));
+        fields.extend(isbn.map(EntryField::Isbn));
+        fields.extend(issn.map(EntryField::Issn));
+        fields.extend(
+            record
+                .iter()
+                .filter(|(key, value)| !Self::MAPPED_KEYS.contains(key) && !value.trim().is_empty())
+                .map(|(key, value)| other(key, value.trim())),
+        );
+
+        let symbol = first("label")
+            .map(|label| s!(label))
+            .unwrap_or_else(|| Entry::generated_symbol(&fields, symbols, "anon"));
+        Ok(Entry::new(entry_type, symbol, fields))
+    }
+
+    fn entry_type(name: &str) -> (EntryType, Option<&'static str>) {
+        let entry_type = match name {
+            "Journal Article" | "Electronic Article" | "Magazine Article" | "Newspaper Article" => {
+                EntryType::Article
+            }
+            "Book" | "Electronic Book" => EntryType::Book,
+            "Edited Book" => EntryType::Collection,
+            "Book Section" | "Electronic Book Section" => EntryType::InCollection,
+            "Conference Proceedings" => EntryType::Proceedings,
+            "Conference Paper" => EntryType::InProceedings,
+            "Encyclopedia" | "Dictionary" => EntryType::InReference,
+            "Serial" => EntryType::Periodical,
+            "Thesis" => EntryType::Thesis,
+            "Report" | "Government Document" => EntryType::Report,
+            "Patent" => EntryType::Patent,
+            "Web Page" | "Blog" => EntryType::Online,
+            "Computer Program" => EntryType::Software,
+            "Dataset" => EntryType::Dataset,
+            "Unpublished Work" | "Manuscript" => EntryType::Unpublished,
+            "Pamphlet" => EntryType::Booklet,
+            "Standard" => EntryType::Standard,
+            "Music" => EntryType::Music,
+            "Audiovisual Material" => EntryType::Video,
+            "Film or Broadcast" => EntryType::Movie,
+            "Artwork" => EntryType::Artwork,
+            "Figure" => EntryType::Image,
+            "Case" => EntryType::Jurisdiction,
+            "Statute" | "Bill" => EntryType::Legislation,
+            "Personal Communication" => EntryType::Letter,
+            _ => EntryType::Misc,
+        };
+        let subtype = match name {
+            "Magazine Article" => Some("magazine"),
+            "Newspaper Article" => Some("newspaper"),
+            _ => None,
+        };
+        (entry_type, subtype)
+    }
+
+    fn record(entry: &Entry) -> Record {
+        let mut record: Record = vec![
+            ("ref-type", s!(Self::type_name(entry))),
+            ("label", s!(entry.symbol())),
+        ];
+        record.extend(
+            entry
+                .fields()
+                .iter()
+                .flat_map(|field| Self::values(entry, field)),
+        );
+        record.sort_by_key(|(key, _)| Self::FIELDS.iter().position(|(it, _, _)| it == key));
+        record
+    }
+
+    fn type_name(entry: &Entry) -> &'static str {
+        let subtype = entry.fields().iter().find_map(|field| match field {
+            EntryField::EntrySubtype(subtype) => Some(subtype.to_lowercase()),
+            _ => None,
+        });
+        match entry.entry_type() {
+            EntryType::Article => match subtype.as_deref() {
+                Some("magazine") => "Magazine Article",
+                Some("newspaper") => "Newspaper Article",
+                _ => "Journal Article",
+            },
+            EntryType::Book
+            | EntryType::MvBook
+            | EntryType::Reference
+            | EntryType::MvReference
+            | EntryType::Manual
+            | EntryType::Commentary => "Book",
+            EntryType::Collection | EntryType::MvCollection => "Edited Book",
+            EntryType::InBook
+            | EntryType::BookInBook
+            | EntryType::SuppBook
+            | EntryType::InCollection
+            | EntryType::SuppCollection => "Book Section",
+            EntryType::Proceedings | EntryType::MvProceedings => "Conference Proceedings",
+            EntryType::InProceedings | EntryType::Conference => "Conference Paper",
+            EntryType::InReference => "Encyclopedia",
+            EntryType::Periodical | EntryType::SuppPeriodical => "Serial",
+            EntryType::Thesis | EntryType::MastersThesis | EntryType::PhdThesis => "Thesis",
+            EntryType::Report | EntryType::TechReport => "Report",
+            EntryType::Patent => "Patent",
+            EntryType::Online | EntryType::Electronic | EntryType::Www => "Web Page",
+            EntryType::Software => "Computer Program",
+            EntryType::Dataset | EntryType::DataType => "Dataset",
+            EntryType::Unpublished => "Unpublished Work",
+            EntryType::Booklet => "Pamphlet",
+            EntryType::Standard => "Standard",
+            EntryType::Music => "Music",
+            EntryType::Video => "Audiovisual Material",
+            EntryType::Movie => "Film or Broadcast",
+            EntryType::Artwork => "Artwork",
+            EntryType::Image => "Figure",
+            EntryType::Jurisdiction => "Case",
+            EntryType::Legislation => "Statute",
+            EntryType::Letter => "Personal Communication",
+            _ => "Generic",
+        }
+    }
+
+    /// The year, and the date as `pub-dates` if more precise, e.g. `2020/03`.
+    fn date_values(date: &Date) -> Vec<(&'static str, String)> {
+        let mut values = vec![];
+        if let Some(year) = date.year() {
+            values.push(("year", year.to_string()));
+        }
+        if !matches!(date, Date::Year(_)) {
+            values.extend(date.to_slashed().map(|date| ("pub-dates", date)));
+        }
+        values
+    }
+
+    fn values(entry: &Entry, field: &EntryField) -> Vec<(&'static str, String)> {
+        let has = |f: fn(&EntryField) -> bool| entry.fields().iter().any(f);
+        let value = |key: &'static str, value: String| vec![(key, value)];
+        match field {
+            EntryField::Author(people) => Self::names("authors", people),
+            EntryField::Editor(people) => Self::names("secondary-authors", people),
+            EntryField::Translator(people) => Self::names("translated-authors", people),
+            EntryField::Title(title) => value("title", Self::title(entry, title)),
+            EntryField::JournalTitle(s) | EntryField::BookTitle(s) => {
+                value("secondary-title", s.clone())
+            }
+            EntryField::Journal(s) if !has(|f| matches!(f, EntryField::JournalTitle(_))) => {
+                value("secondary-title", s.clone())
+            }
+            EntryField::ShortJournal(s) => value("alt-title", s.clone()),
+            EntryField::Series(s) => value("tertiary-title", s.clone()),
+            EntryField::ShortTitle(s) => value("short-title", s.clone()),
+            EntryField::Date(date) => Self::date_values(date),
+            EntryField::Year(year) if !has(|f| matches!(f, EntryField::Date(_))) => {
+                match entry.year_month() {
+                    Some(date) => Self::date_values(&date),
+                    None => value("year", year.to_string()),
+                }
+            }
+            EntryField::UrlDate(date) => date
+                .to_slashed()
+                .map(|date| value("access-date", date))
+                .unwrap_or_default(),
+            EntryField::Pages(pages) => value(
+                "pages",
+                pages
+                    .iter()
+                    .map(|pages| pages.to_string().replace("--", "-"))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            ),
+            EntryField::Volume(s) => value("volume", s.clone()),
+            EntryField::Number(s) | EntryField::Issue(s) => value("number", s.clone()),
+            EntryField::Edition(edition) => value("edition", edition.to_string()),
+            EntryField::Location(list) | EntryField::Address(list) => {
+                Self::items("pub-location", list.items())
+            }
+            EntryField::Publisher(list)
+            | EntryField::Institution(list)
+            | EntryField::School(list)
+            | EntryField::Organization(list) => Self::items("publisher", list.items()),
+            EntryField::Language(list) => Self::items("language", list.items()),
+            EntryField::Keywords(keywords) => Self::items("keywords", keywords.items()),
+            EntryField::Isbn(isbn) => value("isbn", isbn.to_string()),
+            EntryField::Issn(issn) => value("isbn", issn.to_string()),
+            EntryField::Doi(doi) => value("electronic-resource-num", doi.to_string()),
+            EntryField::Url(s) => value("urls", s.clone()),
+            EntryField::Abstract(s) => value("abstract", s.clone()),
+            EntryField::Note(s) => value("notes", s.clone()),
+            EntryField::Type(s) => value("work-type", s.clone()),
+            EntryField::Other(other) => Self::FIELDS
+                .iter()
+                .find(|(key, _, _)| *key == field.name())
+                .map(|(key, _, _)| value(key, s!(other.value())))
+                .unwrap_or_default(),
+            _ => vec![],
+        }
+    }
+
+    fn title(entry: &Entry, title: &str) -> String {
+        let subtitle = entry.fields().iter().find_map(|field| match field {
+            EntryField::Subtitle(subtitle) => Some(subtitle),
+            _ => None,
+        });
+        match subtitle {
+            Some(subtitle) => format!("{}: {}", title, subtitle),
+            None => s!(title),
+        }
+    }
+
+    fn items(key: &'static str, items: &[String]) -> Vec<(&'static str, String)> {
+        items.iter().map(|item| (key, item.clone())).collect()
+    }
+
+    fn names(key: &'static str, people: &[Person]) -> Vec<(&'static str, String)> {
+        people
+            .iter()
+            .map(|person| (key, person.to_last_first()))
+            .collect()
+    }
+}
+
+/// Reads EndNote XML, as exported by EndNote, into entries.
+pub(crate) struct EndNoteXmlReader;
+
+impl EndNoteXmlReader {
+    pub(crate) fn read(input: &str) -> Result<Vec<Entry>, String> {
+        let document = roxmltree::Document::parse(input.trim_start_matches('\u{feff}'))
+            .map_err(|e| format!("Invalid EndNote XML: {}", e))?;
+        let mut symbols: Vec<String> = vec![];
+        document
+            .descendants()
+            .filter(|node| node.has_tag_name("record"))
+            .map(|node| {
+                let mut record = vec![];
+                Self::values(node, "", &mut record);
+                let entry = EndNote::entry(&record, &symbols)?;
+                symbols.push(s!(entry.symbol()));
+                Ok(entry)
+            })
+            .collect()
+    }
+
+    /// Collects the values of the elements below `node` whose path is in the field table.
+    fn values(node: Node, parent: &str, record: &mut Record) {
+        for child in node.children().filter(Node::is_element) {
+            let path = match parent {
+                "" => s!(child.tag_name().name()),
+                parent => format!("{}/{}", parent, child.tag_name().name()),
+            };
+            let prefix = format!("{}/", path);
+            match EndNote::FIELDS.iter().find(|(_, _, it)| *it == path) {
+                Some((key, _, _)) => record.push((key, Self::text(child))),
+                None if EndNote::FIELDS
+                    .iter()
+                    .any(|(_, _, it)| it.starts_with(&prefix)) =>
+                {
+                    Self::values(child, &path, record)
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// The text of the element, which EndNote wraps in `<style>` elements, and for the
+    /// reference type its name, given as attribute or by its number.
+    fn text(node: Node) -> String {
+        let text: String = node
+            .descendants()
+            .filter(Node::is_text)
+            .filter_map(|it| it.text())
+            .collect();
+        match node.tag_name().name() {
+            "ref-type" => node
+                .attribute("name")
+                .or_else(|| {
+                    EndNote::TYPES
+                        .iter()
+                        .find(|(_, number)| number.to_string() == text.trim())
+                        .map(|(name, _)| *name)
+                })
+                .map(|name| s!(name))
+                .unwrap_or(text),
+            _ => text,
+        }
+    }
+}
+
+/// Writes entries as EndNote XML.
+pub(crate) struct EndNoteXmlWriter;
+
+impl EndNoteXmlWriter {
+    pub(crate) fn write(entries: &[Entry]) -> String {
+        let records: String = entries.iter().map(Self::record).collect();
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<xml><records>\n{}</records></xml>\n",
+            records
+        )
+    }
+
+    fn record(entry: &Entry) -> String {
+        let record = EndNote::record(entry);
+        let paths: Vec<Vec<&str>> = record
+            .iter()
+            .map(|(key, _)| {
+                EndNote::FIELDS
+                    .iter()
+                    .find(|(it, _, _)| it == key)
+                    .map(|(_, _, path)| path.split('/').collect())
+                    .unwrap_or_default()
+            })
+            .collect();
+        let elements: Vec<(&[&str], &str)> = paths
+            .iter()
+            .zip(&record)
+            .map(|(path, (_, value))| (&path[..], value.as_str()))
+            .collect();
+        format!("<record>{}</record>\n", Self::elements(&elements))
+    }
+
+    /// The elements for the `(path, value)` pairs, with consecutive ones sharing
+    /// their parent element, e.g. all `authors/author` in one `authors`.
+    fn elements(elements: &[(&[&str], &str)]) -> String {
+        elements
+            .chunk_by(|(a, _), (b, _)| a.len() > 1 && b.len() > 1 && a[0] == b[0])
+            .map(|chunk| match chunk[0] {
+                ([name], value) => Self::element(name, value),
+                ([name, ..], _) => {
+                    let children: Vec<(&[&str], &str)> = chunk
+                        .iter()
+                        .map(|(path, value)| (&path[1..], *value))
+                        .collect();
+                    format!("<{0}>{1}</{0}>", name, Self::elements(&children))
+                }
+                ([], _) => s!(""),
+            })
+            .collect()
+    }
+
+    fn element(name: &str, value: &str) -> String {
+        match name {
+            "ref-type" => format!(
+                "<ref-type name=\"{}\">{}</ref-type>",
+                escape_xml(value),
+                EndNote::TYPES
+                    .iter()
+                    .find(|(it, _)| *it == value)
+                    .map(|(_, number)| *number)
+                    .unwrap_or(13)
+            ),
+            name => format!("<{0}>{1}</{0}>", name, escape_xml(value)),
+        }
+    }
+}
+
+/// Reads the tagged Refer format EndNote exports as `.enw` files into entries.
+pub(crate) struct ReferReader;
+
+impl ReferReader {
+    pub(crate) fn read(input: &str) -> Result<Vec<Entry>, String> {
+        let mut symbols: Vec<String> = vec![];
+        Self::records(input)
+            .iter()
+            .map(|record| {
+                let entry = EndNote::entry(record, &symbols)?;
+                symbols.push(s!(entry.symbol()));
+                Ok(entry)
+            })
+            .collect()
+    }
+
+    /// Splits the input into records. A record starts with `%0`, and lines without a tag,
+    /// blank ones included, continue the last value. Unknown tags are skipped.
+    fn records(input: &str) -> Vec<Record> {
+        let mut records = vec![];
+        let mut record: Option<Vec<(&str, String)>> = None;
+        for line in input.trim_start_matches('\u{feff}').lines() {
+            match Self::tag_line(line) {
+                Some(("%0", value)) => {
+                    records.extend(record.take());
+                    record = Some(vec![("%0", s!(value))]);
+                }
+                Some((tag, value)) => {
+                    if let Some(record) = record.as_mut() {
+                        record.push((tag, s!(value)));
+                    }
+                }
+                None => {
+                    if let Some((_, value)) = record.as_mut().and_then(|record| record.last_mut()) {
+                        value.push('\n');
+                        value.push_str(line.trim_end());
+                    }
+                }
+            }
+        }
+        records.extend(record);
+        records
+            .into_iter()
+            .map(|record| {
+                record
+                    .into_iter()
+                    .filter_map(|(tag, value)| {
+                        EndNote::FIELDS
+                            .iter()
+                            .find(|(_, it, _)| *it == tag)
+                            .map(|(key, _, _)| (*key, value))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The tag and value of a `%X value` line.
+    fn tag_line(line: &str) -> Option<(&str, &str)> {
+        let tag = line.get(..2).filter(|tag| tag.starts_with('%'))?;
+        let value = &line[2..];
+        match value.is_empty() || value.starts_with(' ') {
+            true => Some((tag, value.trim())),
+            false => None,
+        }
+    }
+}
+
+/// Writes entries in the tagged Refer format EndNote imports from `.enw` files.
+pub(crate) struct ReferWriter;
+
+impl ReferWriter {
+    pub(crate) fn write(entries: &[Entry]) -> String {
+        entries
+            .iter()
+            .map(Self::record)
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// The record's lines. The journal of an article, its secondary title, goes in `%J`.
+    fn record(entry: &Entry) -> String {
+        let is_article = *entry.entry_type() == EntryType::Article;
+        EndNote::record(entry)
+            .iter()
+            .map(|(key, value)| {
+                let tag = match (*key, is_article) {
+                    ("secondary-title", true) => "%J",
+                    (key, _) => EndNote::FIELDS
+                        .iter()
+                        .find(|(it, _, _)| *it == key)
+                        .map(|(_, tag, _)| *tag)
+                        .unwrap_or_default(),
+                };
+                format!("{} {}\n", tag, value)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod endnote_test {
+    use super::*;
+    use crate::doi::Doi;
+    use crate::entry::Parser;
+    use crate::standard_number::Issn;
+    use crate::tokenizer::Tokenizer;
+    use std::io::Cursor;
+
+    #[test]
+    fn read_xml_records() {
+        // given
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <xml><records>
+            <record>
+                <rec-number>1</rec-number>
+                <ref-type name="Journal Article">17</ref-type>
+                <contributors><authors>
+                    <author><style face="normal" font="default" size="100%">Knuth, Donald E.</style></author>
+                    <author>Ford, Henry, Jr.</author>
+                </authors></contributors>
+                <titles>
+                    <title><style face="normal">Literate </style><style face="italic">Programming</style></title>
+                    <secondary-title>The Computer Journal</secondary-title>
+                </titles>
+                <periodical><full-title>The Computer Journal</full-title></periodical>
+                <pages>97-111</pages>
+                <keywords><keyword>literate programming</keyword><keyword>WEB</keyword></keywords>
+                <dates><year>1984</year><pub-dates><date>1984/05/01</date></pub-dates></dates>
+                <isbn>0010-4620 (Print)</isbn>
+                <accession-num>12345</accession-num>
+                <electronic-resource-num>10.1093/comjnl/27.2.97</electronic-resource-num>
+            </record>
+            <record>
+                <ref-type>6</ref-type>
+                <dates><year>2004</year><pub-dates><date>Spring</date></pub-dates></dates>
+                <publisher>Barnes &amp; Noble</publisher>
+            </record>
+            </records></xml>"#;
+        let expected = vec![
+            Entry::new(
+                EntryType::Article,
+                s!("knuth1984"),
+                vec![
+                    EntryField::Author(vec![
+                        Person::FirstMiddleLast {
+                            first_name: s!("Donald"),
+                            middle_names: vec![s!("E")],
+                            last_name: s!("Knuth"),
+                        },
+                        Person::Suffixed {
+                            first_name: s!("Henry"),
+                            middle_names: vec![],
+                            last_name: s!("Ford"),
+                            suffix: s!("Jr."),
+                        },
+                    ]),
+                    EntryField::Title(s!("Literate Programming")),
+                    EntryField::JournalTitle(s!("The Computer Journal")),
+                    EntryField::Date(Date::YearMonthDay(1984, 5, 1)),
+                    EntryField::Pages(Pages::pages_from_str("97--111")),
+                    EntryField::Doi(Doi::parse("10.1093/comjnl/27.2.97")),
+                    EntryField::Keywords(Keywords::from_str("literate programming; WEB", &[';'])),
+                    EntryField::Issn(Issn::parse("0010-4620")),
+                    EntryField::Other(UnknownField::new("accession-num", "12345")),
+                ],
+            ),
+            Entry::new(
+                EntryType::Book,
+                s!("anon2004"),
+                vec![
+                    EntryField::Year(Year::Numeric(2004)),
+                    EntryField::Other(UnknownField::new("pub-dates", "Spring")),
+                    EntryField::Publisher(LiteralList::from_items(&["Barnes & Noble"])),
+                ],
+            ),
+        ];
+
+        // when
+        let actual = EndNoteXmlReader::read(input);
+
+        // then
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn read_refer_records() {
+        // given
+        let input = "%0 Book Section\r\n\
+                     %A Beck, Kent\r\n\
+                     %E Doe, John\r\n\
+                     %T Extreme Programming\r\n\
+                     %B Agile Methods\r\n\
+                     %D 2004\r\n\
+                     %X First paragraph.\r\n\
+                     \r\n\
+                     Second paragraph.\r\n\
+                     %K XP\r\n\
+                     agile\r\n\
+                     %F beck-2004\r\n\
+                     %1 custom\r\n\
+                     \r\n\
+                     %0 Journal Article\n\
+                     %T Title\n\
+                     %J Journal\n\
+                     %O J.\n";
+        let expected = vec![
+            Entry::new(
+                EntryType::InCollection,
+                s!("beck-2004"),
+                vec![
+                    EntryField::Author(vec![Person::FirstLast {
+                        first_name: s!("Kent"),
+                        last_name: s!("Beck"),
+                    }]),
+                    EntryField::Editor(vec![Person::FirstLast {
+                        first_name: s!("John"),
+                        last_name: s!("Doe"),
+                    }]),
+                    EntryField::Title(s!("Extreme Programming")),
+                    EntryField::BookTitle(s!("Agile Methods")),
+                    EntryField::Year(Year::Numeric(2004)),
+                    EntryField::Abstract(s!("First paragraph.\n\nSecond paragraph.")),
+                    EntryField::Keywords(Keywords::from_str("XP; agile", &[';'])),
+                    EntryField::Other(UnknownField::new("custom1", "custom")),
+                ],
+            ),
+            Entry::new(
+                EntryType::Article,
+                s!("anon"),
+                vec![
+                    EntryField::Title(s!("Title")),
+                    EntryField::JournalTitle(s!("Journal")),
+                    EntryField::ShortJournal(s!("J.")),
+                ],
+            ),
+        ];
+
+        // when
+        let actual = ReferReader::read(input);
+
+        // then
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn write_records() {
+        // given
+        let input = r#"@article{knuth-1984,
+            author = {Knuth, Donald E.},
+            title = {Literate Programming},
+            journaltitle = {The Computer Journal},
+            date = {1984-05},
+            pages = {97--111},
+            publisher = {Oxford <UP>},
+        }"#;
+        let expected_xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                            <xml><records>\n\
+                            <record>\
+                            <ref-type name=\"Journal Article\">17</ref-type>\
+                            <contributors><authors><author>Knuth, Donald E.</author></authors></contributors>\
+                            <titles><title>Literate Programming</title>\
+                            <secondary-title>The Computer Journal</secondary-title></titles>\
+                            <pages>97-111</pages>\
+                            <dates><year>1984</year><pub-dates><date>1984/05</date></pub-dates></dates>\
+                            <publisher>Oxford &lt;UP&gt;</publisher>\
+                            <label>knuth-1984</label>\
+                            </record>\n\
+                            </records></xml>\n";
+        let expected_refer = "%0 Journal Article\n\
+                              %A Knuth, Donald E.\n\
+                              %T Literate Programming\n\
+                              %J The Computer Journal\n\
+                              %P 97-111\n\
+                              %D 1984\n\
+                              %8 1984/05\n\
+                              %I Oxford <UP>\n\
+                              %F knuth-1984\n";
+
+        // when
+        let entries = parse(input);
+        let actual_xml = EndNoteXmlWriter::write(&entries);
+        let actual_refer = ReferWriter::write(&entries);
+
+        // then
+        assert_eq!(actual_xml, expected_xml);
+        assert_eq!(actual_refer, expected_refer);
+    }
+
+    #[test]
+    fn decode_latex() {
+        // given
+        let input = r#"@article{mueller,
+            author = {M{\"u}ller, J{\"o}rg},
+            title = {Caf{\'e} \& Bar},
+            url = {https://example.com/caf\_e},
+        }"#;
+        let expected = "%0 Journal Article\n\
+                        %A Müller, Jörg\n\
+                        %T Café & Bar\n\
+                        %F mueller\n\
+                        %U https://example.com/caf\\_e\n";
+
+        // when
+        let actual = ReferWriter::write(&parse(input));
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn round_trip() {
+        // given
+        let input = r#"
+            @article{a,
+                author = {de la Fontaine, Jean and Ford, Jr., Henry and {IEEE}},
+                title = {Title}, subtitle = {Subtitle},
+                journaltitle = {Journal}, shortjournal = {J.},
+                date = {2020-06-01},
+                volume = {3}, number = {4},
+                pages = {iv--xii, 15+},
+                doi = {10.1109/IEEESTD.2018.8457469},
+                keywords = {a, b},
+                url = {https://example.com},
+                urldate = {2021-01-02},
+                abstract = {One & two.},
+            }
+            @incollection{b,
+                author = {Doe, Jane}, editor = {Doe, John},
+                title = {Chapter}, booktitle = {Book}, series = {Series},
+                year = {2001}, month = mar, edition = {2},
+                location = {New York and London}, publisher = {Publisher},
+                isbn = {978-0-13-405199-4},
+            }
+            @thesis{c, author = {Roe, Richard}, title = {Thesis}, type = {phdthesis}, institution = {University}}"#;
+        let entries = parse(input);
+        let expected_xml = EndNoteXmlWriter::write(&entries);
+        let expected_refer = ReferWriter::write(&entries);
+
+        // when
+        let read_xml = EndNoteXmlReader::read(&expected_xml).unwrap();
+        let read_refer = ReferReader::read(&expected_refer).unwrap();
+        let actual_xml = EndNoteXmlWriter::write(&read_xml);
+        let actual_refer = ReferWriter::write(&read_refer);
+
+        // then
+        assert_eq!(actual_xml, expected_xml);
+        assert_eq!(actual_refer, expected_refer);
+        let month = EntryField::Date(Date::YearMonth(2001, 3));
+        assert!(read_xml[1].fields().contains(&month));
+        assert!(read_refer[1].fields().contains(&month));
+    }
+
+    fn parse(input: &str) -> Vec<Entry> {
+        Parser::new(Tokenizer::new(Box::new(Cursor::new(s!(input)))))
+            .parse()
+            .unwrap()
+    }
+}
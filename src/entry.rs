@@ -1,53 +1,321 @@
-use crate::entry_field::EntryField;
-use crate::entry_type::EntryType;
+use crate::crossref::CrossrefResolver;
+use crate::date::Date;
+use crate::entry_field::{EntryField, FieldNaming};
+use crate::entry_set::EntrySetResolver;
+use crate::entry_type::{EntryType, TypeNaming};
+use crate::eprint::EPrint;
+use crate::keywords::Keywords;
 use crate::s;
+use crate::standard_number::IsbnForm;
 use crate::tokenizer::{EntryToken, Tokenizer};
-use serde::Serialize;
+use crate::xdata::XDataResolver;
+use crate::year::Year;
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct Entry {
     r#type: EntryType,
     symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    set: Option<String>,
     fields: Vec<EntryField>,
+    /// The fields read from a `.bib` file with their values as written, braces and LaTeX kept.
+    #[serde(skip)]
+    written: Vec<(EntryField, RawValue)>,
+}
+
+/// Entries are equal by their fields, however these were written.
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.r#type == other.r#type
+            && self.symbol == other.symbol
+            && self.set == other.set
+            && self.fields == other.fields
+    }
 }
 
 impl Entry {
-    fn new(t: EntryType, symbol: String, fields: Vec<EntryField>) -> Entry {
+    pub(crate) fn new(t: EntryType, symbol: String, fields: Vec<EntryField>) -> Entry {
         Entry {
             r#type: t,
             symbol,
+            set: None,
             fields,
+            written: vec![],
+        }
+    }
+
+    /// A key from the first author's last name, or `fallback` without one, and the year,
+    /// e.g. `knuth1984`, for formats without keys, made unique among the `symbols` taken so far.
+    pub(crate) fn generated_symbol(
+        fields: &[EntryField],
+        symbols: &[String],
+        fallback: &str,
+    ) -> String {
+        let name = fields.iter().find_map(|field| match field {
+            EntryField::Author(people) | EntryField::Editor(people) => people
+                .first()
+                .map(|person| person.last_name().to_lowercase()),
+            _ => None,
+        });
+        let year = fields.iter().find_map(|field| match field {
+            EntryField::Date(date) => date.year(),
+            EntryField::Year(year) => year.value(),
+            _ => None,
+        });
+        let base: String = format!(
+            "{}{}",
+            name.unwrap_or(s!(fallback)),
+            year.map(|year| year.to_string()).unwrap_or_default()
+        )
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect();
+        (1..)
+            .map(|n| match n {
+                1 => base.clone(),
+                n => format!("{}-{}", base, n),
+            })
+            .find(|symbol| !symbols.contains(symbol))
+            .unwrap_or(base)
+    }
+
+    pub(crate) fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub(crate) fn entry_type(&self) -> &EntryType {
+        &self.r#type
+    }
+
+    pub(crate) fn fields(&self) -> &Vec<EntryField> {
+        &self.fields
+    }
+
+    pub(crate) fn with_fields(self, fields: Vec<EntryField>) -> Entry {
+        Entry { fields, ..self }
+    }
+
+    /// The symbol of the `@set` entry this entry is a member of.
+    pub(crate) fn set(&self) -> Option<&str> {
+        self.set.as_deref()
+    }
+
+    pub(crate) fn with_set(self, set: Option<String>) -> Entry {
+        Entry { set, ..self }
+    }
+
+    /// The value the field was written with, if it was read from a `.bib` file unchanged.
+    pub(crate) fn written_value(&self, field: &EntryField) -> Option<&RawValue> {
+        self.written
+            .iter()
+            .find(|(written, _)| written == field)
+            .map(|(_, value)| value)
+    }
+
+    /// A numeric `year` with the `month`, e.g. `2020-03` for `year = 2020, month = mar`,
+    /// for formats with a single publication date.
+    pub(crate) fn year_month(&self) -> Option<Date> {
+        let year = self.fields.iter().find_map(|field| match field {
+            EntryField::Year(Year::Numeric(year)) => Some(*year),
+            _ => None,
+        })?;
+        let month = self.fields.iter().find_map(|field| match field {
+            EntryField::Month(month) => Some(month.clone()),
+            _ => None,
+        })?;
+        Some(Date::merge(Date::Year(year), month))
+    }
+
+    /// Whether the entry is tagged with the keyword in its `keywords` or `groups` field.
+    pub(crate) fn has_keyword(&self, keyword: &str) -> bool {
+        self.fields.iter().any(|field| match field {
+            EntryField::Keywords(keywords) | EntryField::Groups(keywords) => {
+                keywords.contains(keyword)
+            }
+            _ => false,
+        })
+    }
+
+    /// Keeps the entries tagged with any of the keywords, or all entries if there are none.
+    pub(crate) fn filter_by_keywords(entries: Vec<Entry>, keywords: &[String]) -> Vec<Entry> {
+        match keywords.is_empty() {
+            true => entries,
+            false => entries
+                .into_iter()
+                .filter(|entry| keywords.iter().any(|keyword| entry.has_keyword(keyword)))
+                .collect(),
+        }
+    }
+
+    /// Converts the ISBNs to the form, where they have one, e.g. ISBN-10s to ISBN-13s.
+    pub(crate) fn convert_isbns(entries: Vec<Entry>, form: IsbnForm) -> Vec<Entry> {
+        entries
+            .into_iter()
+            .map(|mut entry| {
+                entry.fields = entry
+                    .fields
+                    .into_iter()
+                    .map(|field| match field {
+                        EntryField::Isbn(isbn) => EntryField::Isbn(isbn.to_form(form)),
+                        field => field,
+                    })
+                    .collect();
+                entry
+            })
+            .collect()
+    }
+}
+
+/// An entry as written, with the inner braces of the field values kept.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct RawEntry {
+    pub(crate) r#type: String,
+    pub(crate) symbol: String,
+    pub(crate) fields: Vec<(String, RawValue)>,
+}
+
+/// A field value as written, in braces or quotes, or bare, e.g. `2020` or the macro `jan`.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) enum RawValue {
+    Delimited(String),
+    Bare(String),
+}
+
+impl RawValue {
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            RawValue::Delimited(value) | RawValue::Bare(value) => value,
+        }
+    }
+}
+
+impl RawEntry {
+    fn new(t: String, symbol: String, fields: Vec<(String, RawValue)>) -> RawEntry {
+        RawEntry {
+            r#type: t,
+            symbol,
+            fields,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ParserOptions {
+    pub(crate) detect_doi: bool,
+    pub(crate) field_naming: FieldNaming,
+    pub(crate) type_naming: TypeNaming,
+    pub(crate) keyword_separators: Vec<char>,
+    pub(crate) resolve_xdata: bool,
+    pub(crate) drop_xdata: bool,
+    pub(crate) resolve_crossrefs: bool,
+    pub(crate) min_crossrefs: Option<usize>,
+    pub(crate) resolve_sets: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            detect_doi: false,
+            field_naming: FieldNaming::default(),
+            type_naming: TypeNaming::default(),
+            keyword_separators: Keywords::DEFAULT_SEPARATORS.to_vec(),
+            resolve_xdata: false,
+            drop_xdata: false,
+            resolve_crossrefs: false,
+            min_crossrefs: None,
+            resolve_sets: false,
         }
     }
 }
 
 pub(crate) struct Parser {
     tokenizer: Tokenizer,
+    options: ParserOptions,
+    warnings: Vec<String>,
 }
 
 impl Parser {
+    #[allow(dead_code)]
     pub(crate) fn new(tokenizer: Tokenizer) -> Parser {
-        Parser { tokenizer }
+        Self::with_options(tokenizer, ParserOptions::default())
+    }
+
+    pub(crate) fn with_options(tokenizer: Tokenizer, options: ParserOptions) -> Parser {
+        Parser {
+            tokenizer,
+            options,
+            warnings: vec![],
+        }
     }
+
+    pub(crate) fn warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+
     pub(crate) fn parse(&mut self) -> Result<Vec<Entry>, String> {
-        let tokens = self.tokenizer.tokenize()?;
-        let tokens_iter = tokens.iter();
         let mut entries: Vec<Entry> = vec![];
+        for raw_entry in self.parse_raw()? {
+            let mut fields: Vec<EntryField> = vec![];
+            let mut written: Vec<(EntryField, RawValue)> = vec![];
+            for (field_name, value) in raw_entry.fields.into_iter() {
+                let field = self.field(&field_name, value.as_str())?;
+                if let Some(warning) = field.validation_warning() {
+                    self.warn(Some(raw_entry.symbol.as_str()), warning);
+                }
+                written.push((field.clone(), value));
+                fields.push(field);
+            }
+            let t = EntryType::from_str(raw_entry.r#type.as_str());
+            let entry = self.finish_entry(t, raw_entry.symbol, fields);
+            entries.push(Entry { written, ..entry });
+        }
 
-        let mut entry_type: Option<EntryType> = None;
+        if self.options.resolve_xdata {
+            let (resolved, warnings) = XDataResolver::resolve(entries, self.options.drop_xdata);
+            warnings
+                .into_iter()
+                .for_each(|(symbol, warning)| self.warn(Some(symbol.as_str()), warning));
+            entries = resolved;
+        }
+        if self.options.resolve_crossrefs {
+            let (resolved, warnings) =
+                CrossrefResolver::resolve(entries, self.options.min_crossrefs);
+            warnings
+                .into_iter()
+                .for_each(|(symbol, warning)| self.warn(Some(symbol.as_str()), warning));
+            entries = resolved;
+        }
+        if self.options.resolve_sets {
+            let (resolved, warnings) = EntrySetResolver::resolve(entries);
+            warnings
+                .into_iter()
+                .for_each(|(symbol, warning)| self.warn(Some(symbol.as_str()), warning));
+            entries = resolved;
+        }
+
+        Ok(entries)
+    }
+
+    /// Reads the entries without interpreting the fields, keeping the values as written.
+    pub(crate) fn parse_raw(&mut self) -> Result<Vec<RawEntry>, String> {
+        let tokens = self.tokenizer.tokenize()?;
+        let mut entries: Vec<RawEntry> = vec![];
+
+        let mut entry_type: Option<String> = None;
         let mut symbol: Option<String> = None;
-        let mut fields: Vec<EntryField> = vec![];
+        let mut fields: Vec<(String, RawValue)> = vec![];
 
         let mut field_name: Option<String> = None;
 
-        for token in tokens_iter {
+        for token in tokens.iter() {
             match token {
                 EntryToken::Type(t) => match entry_type {
-                    None => entry_type = Some(EntryType::from_str(t.as_str())),
+                    None => entry_type = Some(t.clone()),
                     Some(previous_type) => {
                         let s = symbol.clone().ok_or(s!("Symbol was missing from Entry"))?;
-                        entries.push(Entry::new(previous_type, s, fields.clone()));
-                        entry_type = Some(EntryType::from_str(t.as_str()));
+                        entries.push(RawEntry::new(previous_type, s, fields.clone()));
+                        entry_type = Some(t.clone());
                         symbol = None;
                         fields = vec![];
                     }
@@ -69,12 +337,13 @@ impl Parser {
                         ))
                     }
                 },
-                EntryToken::Value(v) => match field_name.clone() {
+                EntryToken::Value(v) | EntryToken::BareValue(v) => match field_name.clone() {
                     Some(f) => {
-                        fields.push(EntryField::from_field_name_and_value(
-                            f.as_str(),
-                            v.as_str(),
-                        )?);
+                        let value = match token {
+                            EntryToken::BareValue(_) => RawValue::Bare(v.clone()),
+                            _ => RawValue::Delimited(v.clone()),
+                        };
+                        fields.push((f, value));
                         field_name = None;
                     }
                     None => {
@@ -90,21 +359,100 @@ impl Parser {
 
         if let Some(t) = entry_type {
             if let Some(s) = symbol {
-                entries.push(Entry::new(t, s, fields.clone()))
+                entries.push(RawEntry::new(t, s, fields.clone()))
             };
         };
 
         Ok(entries)
     }
+
+    fn field(&self, field_name: &str, value: &str) -> Result<EntryField, String> {
+        let field = EntryField::from_field_name_and_value_with_separators(
+            field_name,
+            value,
+            &self.options.keyword_separators,
+        )?;
+        match self.options.field_naming {
+            FieldNaming::AsWritten => Ok(field),
+            FieldNaming::Biblatex => Ok(field.into_biblatex()),
+        }
+    }
+
+    fn finish_entry(&mut self, t: EntryType, symbol: String, fields: Vec<EntryField>) -> Entry {
+        let mut fields = Self::combine_eprint(fields);
+        for field in fields.iter() {
+            if let EntryField::EPrint(eprint) = field {
+                if let Some(warning) = eprint.validation_warning() {
+                    self.warn(Some(symbol.as_str()), warning);
+                }
+            }
+        }
+        if self.options.detect_doi && !fields.iter().any(|f| matches!(f, EntryField::Doi(_))) {
+            if let Some(doi) = fields.iter().find_map(EntryField::embedded_doi) {
+                fields.push(EntryField::Doi(doi));
+            }
+        }
+        let (t, implied_type) = match self.options.type_naming {
+            TypeNaming::AsWritten => (t, None),
+            TypeNaming::Biblatex => t.into_biblatex(),
+        };
+        if let Some(implied_type) = implied_type {
+            if !fields.iter().any(|f| matches!(f, EntryField::Type(_))) {
+                fields.push(EntryField::Type(s!(implied_type)));
+            }
+        }
+        Entry::new(t, symbol, fields)
+    }
+
+    /// Merges `eprinttype` and `eprintclass` (or their `archiveprefix` and `primaryclass`
+    /// aliases) into the typed `eprint` field.
+    pub(crate) fn combine_eprint(fields: Vec<EntryField>) -> Vec<EntryField> {
+        if !fields.iter().any(|f| matches!(f, EntryField::EPrint(_))) {
+            return fields;
+        }
+        let eprint_type = fields.iter().find_map(|f| match f {
+            EntryField::EPrintType(t) => Some(t.clone()),
+            _ => None,
+        });
+        let eprint_class = fields.iter().find_map(|f| match f {
+            EntryField::EPrintClass(c) => Some(c.clone()),
+            _ => None,
+        });
+        fields
+            .into_iter()
+            .filter_map(|field| match field {
+                EntryField::EPrint(eprint) => Some(EntryField::EPrint(EPrint::parse(
+                    &eprint.id(),
+                    eprint_type.as_deref(),
+                    eprint_class.as_deref(),
+                ))),
+                EntryField::EPrintType(_) | EntryField::EPrintClass(_) => None,
+                f => Some(f),
+            })
+            .collect()
+    }
+
+    fn warn(&mut self, symbol: Option<&str>, warning: String) {
+        self.warnings.push(format!(
+            "Entry '{}': {}",
+            symbol.unwrap_or_default(),
+            warning
+        ));
+    }
 }
 
 #[cfg(test)]
 mod entry_test {
     use super::*;
+    use crate::bib_writer::BibWriter;
     use crate::date::Date;
+    use crate::doi::Doi;
     use crate::edition::Edition;
+    use crate::literal_list::LiteralList;
     use crate::person::Person;
     use crate::s;
+    use crate::standard_number::Isbn;
+    use crate::year::Year;
     use std::io::Read;
 
     #[test]
@@ -131,13 +479,15 @@ mod entry_test {
             Entry {
                 r#type: EntryType::Book,
                 symbol: s!("beck-2004"),
+                set: None,
+                written: vec![],
                 fields: vec![
                     EntryField::Title(s!("Extreme Programming Explained: Embrace Change")),
                     EntryField::Edition(Edition::Numeric(2)),
-                    EntryField::Isbn(s!("978-0-13-405199-4")),
+                    EntryField::Isbn(Isbn::Isbn13(s!("9780134051994"))),
                     EntryField::Series(s!("XP Series")),
                     EntryField::PageTotal(189),
-                    EntryField::Publisher(s!("Addison-Wesley Professional")),
+                    EntryField::Publisher(LiteralList::from_str("Addison-Wesley Professional")),
                     EntryField::Author(vec![
                         Person::FirstLast {
                             first_name: s!("Kent"),
@@ -154,13 +504,15 @@ mod entry_test {
             Entry {
                 r#type: EntryType::Article,
                 symbol: s!("ieee-802-3-2018"),
+                set: None,
+                written: vec![],
                 fields: vec![
                     EntryField::Journal(s!(
                         "IEEE Std 802.3-2018 (Revision of IEEE Std 802.3-2015)"
                     )),
                     EntryField::Title(s!("IEEE Standard for Ethernet")),
-                    EntryField::Year(Date::Year(2018)),
-                    EntryField::Doi(s!("10.1109/IEEESTD.2018.8457469")),
+                    EntryField::Year(Year::Numeric(2018)),
+                    EntryField::Doi(Doi::Valid(s!("10.1109/IEEESTD.2018.8457469"))),
                 ],
             },
         ]);
@@ -190,6 +542,290 @@ mod entry_test {
         assert!(actual.is_err());
     }
 
+    #[test]
+    fn parse_entries_warn_on_invalid_identifiers() {
+        // given
+        let input = r#"
+            @book{invalid-2004,
+                isbn = {978-0-13-405199-5},
+                issn = {0378-5955},
+            }
+            @article{invalid-2018,
+                issn = {0378-5954},
+            }"#;
+        let expected = vec![
+            s!("Entry 'invalid-2004': Invalid ISBN '978-0-13-405199-5'"),
+            s!("Entry 'invalid-2018': Invalid ISSN '0378-5954'"),
+        ];
+
+        // when
+        let mut parser = parser_for_str(input);
+        let entries = parser.parse();
+
+        // then
+        assert!(entries.is_ok());
+        assert_eq!(*parser.warnings(), expected);
+    }
+
+    #[test]
+    fn parse_entries_detect_embedded_doi() {
+        // given
+        let input = r#"
+            @inproceedings{with-url,
+                url = {https://dl.acm.org/doi/10.1145/3368089.3409765},
+            }
+            @inproceedings{with-doi,
+                doi = {https://doi.org/10.1145/3368089.3409765},
+                note = {See also 10.1000/other},
+            }"#;
+        let options = ParserOptions {
+            detect_doi: true,
+            ..ParserOptions::default()
+        };
+        let expected_doi = EntryField::Doi(Doi::Valid(s!("10.1145/3368089.3409765")));
+
+        // when
+        let mut parser = Parser::with_options(Tokenizer::new(reader_from_str(input)), options);
+        let actual = parser.parse().unwrap();
+
+        // then
+        assert_eq!(actual[0].fields.last(), Some(&expected_doi));
+        assert_eq!(
+            actual[1].fields,
+            vec![expected_doi, EntryField::Note(s!("See also 10.1000/other")),]
+        );
+    }
+
+    #[test]
+    fn parse_entries_combine_eprint() {
+        // given
+        let input = r#"
+            @online{arxiv-2021,
+                eprint        = {2101.00001v2},
+                archivePrefix = {arXiv},
+                primaryClass  = {cs.SE},
+                title         = {A},
+            }
+            @article{pubmed-2020,
+                eprinttype = {pubmed},
+                eprint     = {not-a-pmid},
+            }"#;
+        let expected = vec![
+            Entry {
+                r#type: EntryType::Online,
+                symbol: s!("arxiv-2021"),
+                set: None,
+                written: vec![],
+                fields: vec![
+                    EntryField::EPrint(EPrint::ArXiv {
+                        id: s!("2101.00001"),
+                        version: Some(2),
+                        class: Some(s!("cs.SE")),
+                    }),
+                    EntryField::Title(s!("A")),
+                ],
+            },
+            Entry {
+                r#type: EntryType::Article,
+                symbol: s!("pubmed-2020"),
+                set: None,
+                written: vec![],
+                fields: vec![EntryField::EPrint(EPrint::Invalid {
+                    eprint_type: s!("pubmed"),
+                    id: s!("not-a-pmid"),
+                })],
+            },
+        ];
+
+        // when
+        let mut parser = parser_for_str(input);
+        let actual = parser.parse().unwrap();
+
+        // then
+        assert_eq!(actual, expected);
+        assert_eq!(
+            *parser.warnings(),
+            vec![s!(
+                "Entry 'pubmed-2020': Invalid pubmed eprint 'not-a-pmid'"
+            )]
+        );
+    }
+
+    #[test]
+    fn parse_entries_with_field_naming() {
+        // given
+        let input = r#"
+            @phdthesis{knuth-1963,
+                school    = {California Institute of Technology},
+                address   = {Pasadena},
+                journal   = {J},
+                booktitle = {B},
+                key       = {K},
+                annote    = {A},
+            }"#;
+        let as_written = vec![
+            EntryField::School(LiteralList::from_str("California Institute of Technology")),
+            EntryField::Address(LiteralList::from_str("Pasadena")),
+            EntryField::Journal(s!("J")),
+            EntryField::BookTitle(s!("B")),
+            EntryField::Key(s!("K")),
+            EntryField::Annote(s!("A")),
+        ];
+        let biblatex = vec![
+            EntryField::Institution(LiteralList::from_str("California Institute of Technology")),
+            EntryField::Location(LiteralList::from_str("Pasadena")),
+            EntryField::JournalTitle(s!("J")),
+            EntryField::BookTitle(s!("B")),
+            EntryField::SortKey(s!("K")),
+            EntryField::Annotation(s!("A")),
+        ];
+        let options = ParserOptions {
+            field_naming: FieldNaming::Biblatex,
+            ..ParserOptions::default()
+        };
+
+        // when
+        let actual_as_written = parser_for_str(input).parse().unwrap();
+        let actual_biblatex = Parser::with_options(Tokenizer::new(reader_from_str(input)), options)
+            .parse()
+            .unwrap();
+
+        // then
+        assert_eq!(actual_as_written[0].fields, as_written);
+        assert_eq!(actual_biblatex[0].fields, biblatex);
+    }
+
+    #[test]
+    fn parse_entries_with_type_naming() {
+        // given
+        let input = r#"
+            @phdthesis{knuth-1963,
+                title = {Finite Semifields and Projective Planes},
+            }
+            @techreport{report-1999,
+                type = {Research Note},
+            }
+            @conference{conf-2001,
+                title = {C},
+            }"#;
+        let expected = vec![
+            Entry {
+                r#type: EntryType::Thesis,
+                symbol: s!("knuth-1963"),
+                set: None,
+                written: vec![],
+                fields: vec![
+                    EntryField::Title(s!("Finite Semifields and Projective Planes")),
+                    EntryField::Type(s!("phdthesis")),
+                ],
+            },
+            Entry {
+                r#type: EntryType::Report,
+                symbol: s!("report-1999"),
+                set: None,
+                written: vec![],
+                fields: vec![EntryField::Type(s!("Research Note"))],
+            },
+            Entry {
+                r#type: EntryType::InProceedings,
+                symbol: s!("conf-2001"),
+                set: None,
+                written: vec![],
+                fields: vec![EntryField::Title(s!("C"))],
+            },
+        ];
+        let options = ParserOptions {
+            type_naming: TypeNaming::Biblatex,
+            ..ParserOptions::default()
+        };
+
+        // when
+        let actual = Parser::with_options(Tokenizer::new(reader_from_str(input)), options).parse();
+
+        // then
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn filter_entries_by_keywords() {
+        // given
+        let input = r#"
+            @article{a,
+                keywords = {verification; model checking, SAT},
+            }
+            @article{b,
+                groups = {SMT},
+            }
+            @article{c,
+                keywords = {testing},
+            }"#;
+        let entries = parser_for_str(input).parse().unwrap();
+        let keywords = vec![s!("sat"), s!("SMT")];
+
+        // when
+        let actual = Entry::filter_by_keywords(entries, &keywords);
+
+        // then
+        let actual_symbols: Vec<&str> = actual.iter().map(|e| e.symbol.as_str()).collect();
+        assert_eq!(actual_symbols, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn convert_isbns() {
+        // given
+        let input = r#"
+            @book{a,
+                isbn = {0-321-27865-8},
+            }
+            @book{b,
+                isbn = {979-10-90636-07-1},
+            }"#;
+        let entries = parser_for_str(input).parse().unwrap();
+
+        // when
+        let actual = Entry::convert_isbns(entries, IsbnForm::Isbn13);
+
+        // then
+        let actual_fields: Vec<&EntryField> = actual.iter().flat_map(|e| &e.fields).collect();
+        assert_eq!(
+            actual_fields,
+            vec![
+                &EntryField::Isbn(Isbn::Isbn13(s!("9780321278654"))),
+                &EntryField::Isbn(Isbn::Isbn13(s!("9791090636071"))),
+            ]
+        );
+        assert!(BibWriter::new()
+            .write(&actual)
+            .contains("isbn = {9780321278654}"));
+    }
+
+    #[test]
+    fn parse_entries_with_keyword_separators() {
+        // given
+        let input = r#"
+            @article{a,
+                keywords = {model checking, SAT / SMT},
+            }"#;
+        let options = ParserOptions {
+            keyword_separators: vec!['/'],
+            ..ParserOptions::default()
+        };
+
+        // when
+        let actual = Parser::with_options(Tokenizer::new(reader_from_str(input)), options)
+            .parse()
+            .unwrap();
+
+        // then
+        assert_eq!(
+            actual[0].fields,
+            vec![EntryField::Keywords(Keywords::from_str(
+                "model checking, SAT/SMT",
+                &['/']
+            ))]
+        );
+    }
+
     fn parser_for_str(input: &'static str) -> Parser {
         let reader = reader_from_str(input);
         Parser::new(Tokenizer::new(reader))
@@ -1,46 +1,68 @@
 use crate::date::Date;
+use crate::doi::Doi;
 use crate::edition::Edition;
+use crate::eprint::EPrint;
+use crate::gender::Gender;
+use crate::keywords::Keywords;
+use crate::literal_list::LiteralList;
 use crate::pages::Pages;
 use crate::person::Person;
+use crate::pub_state::PubState;
 use crate::s;
-use serde::Serialize;
+use crate::standard_number::{Isbn, Ismn, Isrn, Issn};
+use crate::strings::{latex_to_unicode, strip_braces, unicode_to_latex};
+use crate::xdata::XDataReference;
+use crate::year::Year;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub(crate) enum EntryField {
     Abstract(String),
+    Addendum(String),
+    Address(LiteralList),
     Afterword(String),
     Annotation(String),
     Annotator(Vec<Person>),
+    Annote(String),
     Author(Vec<Person>),
     AuthorType(String),
     BookAuthor(Vec<Person>),
     BookPagination(String),
     BookSubtitle(String),
+    BookTitle(String),
     Chapter(String),
     Commentator(Vec<Person>),
+    CrossRef(String),
     Date(Date),
-    Doi(String),
+    Doi(Doi),
     Edition(Edition),
     Editor(Vec<Person>),
     EditorType(String),
     Eid(String),
-    EntrySubtype,
-    EPrint(String),
+    EntrySet(Vec<String>),
+    EntrySubtype(String),
+    EPrint(EPrint),
     EPrintType(String),
     EPrintClass(String),
     EventDate(Date),
+    EventEndDate(Date),
     EventTitle(String),
+    Execute(String),
     File(String),
     Foreword(String),
+    Gender(Gender),
+    Groups(Keywords),
     Holder(Vec<Person>),
     HowPublished(String),
+    Ids(Vec<String>),
     IndexTitle(String),
-    Institution(String),
+    Institution(LiteralList),
     Introduction(String),
     Isan(String),
-    Isbn(String),
-    Ismn(String),
-    Isrn(String),
+    Isbn(Isbn),
+    Ismn(Ismn),
+    Isrn(Isrn),
+    Issn(Issn),
     Issue(String),
     IssueSubtitle(String),
     IssueTitle(String),
@@ -48,29 +70,47 @@ pub(crate) enum EntryField {
     Journal(String),
     JournalSubtitle(String),
     JournalTitle(String),
+    Key(String),
+    Keywords(Keywords),
     Label(String),
     LangId(String),
-    Language(String),
+    Language(LiteralList),
     Library(String),
-    Location(String),
+    ListA(LiteralList),
+    ListB(LiteralList),
+    ListC(LiteralList),
+    ListD(LiteralList),
+    ListE(LiteralList),
+    ListF(LiteralList),
+    Location(LiteralList),
     MainSubtitle(String),
     MainTitle(String),
     Month(Date),
+    NameA(Vec<Person>),
+    NameB(Vec<Person>),
+    NameC(Vec<Person>),
     Note(String),
     Number(String),
-    Organization(String),
+    Options(Vec<EntryOption>),
+    Organization(LiteralList),
     OrigDate(Date),
     OrigLanguage(String),
-    OrigLocation(String),
-    OrigPublisher(String),
+    OrigLocation(LiteralList),
+    OrigPublisher(LiteralList),
     OrigTitle(String),
     Pages(Vec<Pages>),
     PageTotal(u32),
     Pagination(String),
     Part(String),
-    Publisher(String),
-    PubState(String),
+    Pdf(String),
+    Presort(String),
+    Publisher(LiteralList),
+    PubState(PubState),
+    Related(Vec<String>),
+    RelatedOptions(Vec<String>),
+    RelatedType(String),
     ReprintTitle(String),
+    School(LiteralList),
     Series(String),
     ShortAuthor(String),
     ShortEdition(String),
@@ -79,126 +119,744 @@ pub(crate) enum EntryField {
     ShortJournal(String),
     ShortSeries(String),
     ShortTitle(String),
+    SortKey(String),
+    SortName(Vec<Person>),
+    SortTitle(String),
+    SortYear(i32),
     Subtitle(String),
     Title(String),
     Translator(Vec<Person>),
     Type(String),
     Url(String),
     UrlDate(Date),
+    UserA(String),
+    UserB(String),
+    UserC(String),
+    UserD(String),
+    UserE(String),
+    UserF(String),
     Venue(String),
+    VerbA(String),
+    VerbB(String),
+    VerbC(String),
     Version(String),
     Volume(String),
-    Year(Date),
+    XData(Vec<String>),
+    XDataRef(XDataReference),
+    XRef(String),
+    Year(Year),
     Other(UnknownField),
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize)]
+/// How BibTeX-era fields with a biblatex equivalent (e.g. `address`, `school`, `journal`) are kept.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub(crate) enum FieldNaming {
+    #[default]
+    AsWritten,
+    Biblatex,
+}
+
+/// The biblatex data types of fields, as far as they matter for reading and resolving them.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum DataType {
+    NameList,
+    LiteralList,
+    KeyList,
+    Field,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct EntryOption {
+    name: String,
+    value: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub(crate) struct UnknownField {
     name: String,
     value: String,
 }
 
+impl UnknownField {
+    pub(crate) fn new(name: &str, value: &str) -> UnknownField {
+        UnknownField {
+            name: s!(name),
+            value: s!(value),
+        }
+    }
+
+    pub(crate) fn value(&self) -> &str {
+        &self.value
+    }
+}
+
 impl EntryField {
+    const KEY_LIST_SEPARATOR: char = ',';
+    /// Fields kept as written, as LaTeX reads them verbatim or they hold identifiers or keys.
+    const VERBATIM_FIELDS: [&'static str; 20] = [
+        "crossref",
+        "doi",
+        "entryset",
+        "eprint",
+        "eprintclass",
+        "eprinttype",
+        "execute",
+        "file",
+        "ids",
+        "isan",
+        "isrn",
+        "iswc",
+        "pdf",
+        "related",
+        "url",
+        "verba",
+        "verbb",
+        "verbc",
+        "xdata",
+        "xref",
+    ];
+
+    /// Parses the raw `value` as read by the tokenizer, with the inner braces kept,
+    /// so that name and literal lists can be split before the braces are removed.
+    /// Text, e.g. `The {XP} Way \& More`, is decoded to `The XP Way & More`, while
+    /// verbatim fields, e.g. `url`, and unknown fields only lose their braces.
     pub(crate) fn from_field_name_and_value(
         field_name: &str,
-        value: &str,
+        raw: &str,
+    ) -> Result<EntryField, String> {
+        Self::from_field_name_and_value_with_separators(
+            field_name,
+            raw,
+            &Keywords::DEFAULT_SEPARATORS,
+        )
+    }
+
+    /// Like `from_field_name_and_value`, for the plain text of other formats, e.g. `R&D`,
+    /// taken as is instead of decoded from LaTeX.
+    pub(crate) fn from_field_name_and_text(
+        field_name: &str,
+        text: &str,
+    ) -> Result<EntryField, String> {
+        let field = Self::from_field_name_and_value(field_name, &unicode_to_latex(text))?;
+        match field.is_verbatim() {
+            true => Self::from_field_name_and_value(field_name, text),
+            false => Ok(field),
+        }
+    }
+
+    /// Like `from_field_name_and_value`, splitting `keywords` and JabRef's `groups`
+    /// on the given `keyword_separators`.
+    pub(crate) fn from_field_name_and_value_with_separators(
+        field_name: &str,
+        raw: &str,
+        keyword_separators: &[char],
     ) -> Result<EntryField, String> {
-        let entry_field = match field_name {
-            "abstract" => EntryField::Abstract(s!(value)),
-            "afterword" => EntryField::Afterword(s!(value)),
-            "annotation" => EntryField::Annotation(s!(value)),
-            "annotator" => EntryField::Annotator(Person::people_from_str(value)?),
-            "author" => EntryField::Author(Person::people_from_str(value)?),
-            "authortype" => EntryField::AuthorType(s!(value)),
-            "bookauthor" => EntryField::BookAuthor(Person::people_from_str(value)?),
-            "bookpagination" => EntryField::BookPagination(s!(value)),
-            "booksubtitle" => EntryField::BookSubtitle(s!(value)),
-            "booktitle" => EntryField::BookSubtitle(s!(value)),
-            "chapter" => EntryField::Chapter(s!(value)),
-            "commentator" => EntryField::Commentator(Person::people_from_str(value)?),
+        let stripped = strip_braces(raw);
+        let value = stripped.as_str();
+        let text = latex_to_unicode(raw);
+        if let Some(reference) = XDataReference::parse(field_name, value) {
+            return Ok(EntryField::XDataRef(reference));
+        }
+        let entry_field = match field_name.to_lowercase().as_str() {
+            "abstract" => EntryField::Abstract(s!(text)),
+            "addendum" => EntryField::Addendum(s!(text)),
+            "address" => EntryField::Address(LiteralList::from_str(raw)),
+            "afterword" => EntryField::Afterword(s!(text)),
+            "annotation" => EntryField::Annotation(s!(text)),
+            "annotator" => EntryField::Annotator(Person::people_from_str(raw)?),
+            "annote" => EntryField::Annote(s!(text)),
+            "archiveprefix" => EntryField::EPrintType(s!(value)),
+            "author" => EntryField::Author(Person::people_from_str(raw)?),
+            "authortype" => EntryField::AuthorType(s!(text)),
+            "bookauthor" => EntryField::BookAuthor(Person::people_from_str(raw)?),
+            "bookpagination" => EntryField::BookPagination(s!(text)),
+            "booksubtitle" => EntryField::BookSubtitle(s!(text)),
+            "booktitle" => EntryField::BookTitle(s!(text)),
+            "chapter" => EntryField::Chapter(s!(text)),
+            "commentator" => EntryField::Commentator(Person::people_from_str(raw)?),
+            "crossref" => EntryField::CrossRef(s!(value.trim())),
             "date" => EntryField::Date(Date::parse_date_from_str(value)?),
-            "doi" => EntryField::Doi(s!(value)),
+            "doi" => EntryField::Doi(Doi::parse(value)),
             "edition" => EntryField::Edition(Edition::parse(value)),
-            "editor" => EntryField::Editor(Person::people_from_str(value)?),
-            "editortype" => EntryField::EditorType(s!(value)),
-            "eid" => EntryField::Eid(s!(value)),
-            "entrysubtype" => EntryField::EntrySubtype,
-            "eprint" => EntryField::EPrint(s!(value)),
+            "editor" => EntryField::Editor(Person::people_from_str(raw)?),
+            "editortype" => EntryField::EditorType(s!(text)),
+            "eid" => EntryField::Eid(s!(text)),
+            "entryset" => EntryField::EntrySet(Self::key_list_from_str(value)),
+            "entrysubtype" => EntryField::EntrySubtype(s!(text)),
+            "eprint" => EntryField::EPrint(EPrint::parse(value, None, None)),
             "eprinttype" => EntryField::EPrintType(s!(value)),
             "eprintclass" => EntryField::EPrintClass(s!(value)),
             "eventdate" => EntryField::EventDate(Date::parse_date_from_str(value)?),
-            "eventtitle" => EntryField::EventTitle(s!(value)),
+            "eventenddate" => EntryField::EventEndDate(Date::parse_date_from_str(value)?),
+            "eventtitle" => EntryField::EventTitle(s!(text)),
+            "execute" => EntryField::Execute(s!(value)),
             "file" => EntryField::File(s!(value)),
-            "foreword" => EntryField::Foreword(s!(value)),
-            "holder" => EntryField::Holder(Person::people_from_str(value)?),
-            "howpublished" => EntryField::HowPublished(s!(value)),
-            "indextitle" => EntryField::IndexTitle(s!(value)),
-            "institution" => EntryField::Institution(s!(value)),
-            "introduction" => EntryField::Introduction(s!(value)),
+            "foreword" => EntryField::Foreword(s!(text)),
+            "gender" => EntryField::Gender(Gender::from_str(value)),
+            "groups" => EntryField::Groups(Keywords::from_str(raw, keyword_separators)),
+            "holder" => EntryField::Holder(Person::people_from_str(raw)?),
+            "howpublished" => EntryField::HowPublished(s!(text)),
+            "ids" => EntryField::Ids(Self::key_list_from_str(value)),
+            "indextitle" => EntryField::IndexTitle(s!(text)),
+            "institution" => EntryField::Institution(LiteralList::from_str(raw)),
+            "introduction" => EntryField::Introduction(s!(text)),
             "isan" => EntryField::Isan(s!(value)),
-            "isbn" => EntryField::Isbn(s!(value)),
-            "ismn" => EntryField::Ismn(s!(value)),
-            "isrn" => EntryField::Isrn(s!(value)),
-            "issue" => EntryField::Issue(s!(value)),
-            "issuesubtitle" => EntryField::IssueSubtitle(s!(value)),
-            "issuetitle" => EntryField::IssueTitle(s!(value)),
+            "isbn" => EntryField::Isbn(Isbn::parse(value)),
+            "ismn" => EntryField::Ismn(Ismn::parse(value)),
+            "isrn" => EntryField::Isrn(Isrn::parse(value)),
+            "issn" => EntryField::Issn(Issn::parse(value)),
+            "issue" => EntryField::Issue(s!(text)),
+            "issuesubtitle" => EntryField::IssueSubtitle(s!(text)),
+            "issuetitle" => EntryField::IssueTitle(s!(text)),
             "iswc" => EntryField::Iswc(s!(value)),
-            "journal" => EntryField::Journal(s!(value)),
-            "journalsubtitle" => EntryField::JournalSubtitle(s!(value)),
-            "journaltitle" => EntryField::JournalTitle(s!(value)),
-            "label" => EntryField::Label(s!(value)),
-            "langid" => EntryField::LangId(s!(value)),
-            "language" => EntryField::Language(s!(value)),
-            "library" => EntryField::Library(s!(value)),
-            "location" => EntryField::Location(s!(value)),
-            "mainsubtitle" => EntryField::MainSubtitle(s!(value)),
-            "maintitle" => EntryField::MainTitle(s!(value)),
+            "journal" => EntryField::Journal(s!(text)),
+            "journalsubtitle" => EntryField::JournalSubtitle(s!(text)),
+            "journaltitle" => EntryField::JournalTitle(s!(text)),
+            "key" => EntryField::Key(s!(text)),
+            "keywords" => EntryField::Keywords(Keywords::from_str(raw, keyword_separators)),
+            "label" => EntryField::Label(s!(text)),
+            "langid" => EntryField::LangId(s!(text)),
+            "language" => EntryField::Language(LiteralList::from_str(raw)),
+            "library" => EntryField::Library(s!(text)),
+            "lista" => EntryField::ListA(LiteralList::from_str(raw)),
+            "listb" => EntryField::ListB(LiteralList::from_str(raw)),
+            "listc" => EntryField::ListC(LiteralList::from_str(raw)),
+            "listd" => EntryField::ListD(LiteralList::from_str(raw)),
+            "liste" => EntryField::ListE(LiteralList::from_str(raw)),
+            "listf" => EntryField::ListF(LiteralList::from_str(raw)),
+            "location" => EntryField::Location(LiteralList::from_str(raw)),
+            "mainsubtitle" => EntryField::MainSubtitle(s!(text)),
+            "maintitle" => EntryField::MainTitle(s!(text)),
             "month" => EntryField::Month(Date::parse_month_from_str(value)?),
-            "note" => EntryField::Note(s!(value)),
-            "number" => EntryField::Number(s!(value)),
-            "organization" => EntryField::Organization(s!(value)),
+            "namea" => EntryField::NameA(Person::people_from_str(raw)?),
+            "nameb" => EntryField::NameB(Person::people_from_str(raw)?),
+            "namec" => EntryField::NameC(Person::people_from_str(raw)?),
+            "note" => EntryField::Note(s!(text)),
+            "number" => EntryField::Number(s!(text)),
+            "options" => EntryField::Options(EntryOption::options_from_str(value)),
+            "organization" => EntryField::Organization(LiteralList::from_str(raw)),
             "origdate" => EntryField::OrigDate(Date::parse_date_from_str(value)?),
-            "origlanguage" => EntryField::OrigLanguage(s!(value)),
-            "origlocation" => EntryField::OrigLocation(s!(value)),
-            "origpublisher" => EntryField::OrigPublisher(s!(value)),
-            "origtitle" => EntryField::OrigTitle(s!(value)),
+            "origlanguage" => EntryField::OrigLanguage(s!(text)),
+            "origlocation" => EntryField::OrigLocation(LiteralList::from_str(raw)),
+            "origpublisher" => EntryField::OrigPublisher(LiteralList::from_str(raw)),
+            "origtitle" => EntryField::OrigTitle(s!(text)),
             "pages" => EntryField::Pages(Pages::pages_from_str(value)),
             "pagetotal" => EntryField::PageTotal(
                 value
                     .parse()
                     .map_err(|_| format!("Could not parse PageTotal value from '{}'", value))?,
             ),
-            "pagination" => EntryField::Pagination(s!(value)),
-            "part" => EntryField::Part(s!(value)),
-            "publisher" => EntryField::Publisher(s!(value)),
-            "pubstate" => EntryField::PubState(s!(value)),
-            "reprinttitle" => EntryField::ReprintTitle(s!(value)),
-            "series" => EntryField::Series(s!(value)),
-            "shortauthor" => EntryField::ShortAuthor(s!(value)),
-            "shortedition" => EntryField::ShortEdition(s!(value)),
-            "shorthand" => EntryField::Shorthand(s!(value)),
-            "shorthandintro" => EntryField::ShorthandIntro(s!(value)),
-            "shortjournal" => EntryField::ShortJournal(s!(value)),
-            "shortseries" => EntryField::ShortSeries(s!(value)),
-            "shorttitle" => EntryField::ShortTitle(s!(value)),
-            "subtitle" => EntryField::Subtitle(s!(value)),
-            "title" => EntryField::Title(s!(value)),
-            "translator" => EntryField::Translator(Person::people_from_str(value)?),
-            "type" => EntryField::Type(s!(value)),
+            "pagination" => EntryField::Pagination(s!(text)),
+            "part" => EntryField::Part(s!(text)),
+            "pdf" => EntryField::Pdf(s!(value)),
+            "presort" => EntryField::Presort(s!(text)),
+            "primaryclass" => EntryField::EPrintClass(s!(value)),
+            "publisher" => EntryField::Publisher(LiteralList::from_str(raw)),
+            "pubstate" => EntryField::PubState(PubState::from_str(value)),
+            "related" => EntryField::Related(Self::key_list_from_str(value)),
+            "relatedoptions" => EntryField::RelatedOptions(Self::key_list_from_str(value)),
+            "relatedtype" => EntryField::RelatedType(s!(text)),
+            "reprinttitle" => EntryField::ReprintTitle(s!(text)),
+            "school" => EntryField::School(LiteralList::from_str(raw)),
+            "series" => EntryField::Series(s!(text)),
+            "shortauthor" => EntryField::ShortAuthor(s!(text)),
+            "shortedition" => EntryField::ShortEdition(s!(text)),
+            "shorthand" => EntryField::Shorthand(s!(text)),
+            "shorthandintro" => EntryField::ShorthandIntro(s!(text)),
+            "shortjournal" => EntryField::ShortJournal(s!(text)),
+            "shortseries" => EntryField::ShortSeries(s!(text)),
+            "shorttitle" => EntryField::ShortTitle(s!(text)),
+            "sortkey" => EntryField::SortKey(s!(text)),
+            "sortname" => EntryField::SortName(Person::people_from_str(raw)?),
+            "sorttitle" => EntryField::SortTitle(s!(text)),
+            "sortyear" => match value.trim().parse() {
+                Ok(year) => EntryField::SortYear(year),
+                Err(_) => EntryField::Other(UnknownField::new(field_name, value)),
+            },
+            "subtitle" => EntryField::Subtitle(s!(text)),
+            "title" => EntryField::Title(s!(text)),
+            "translator" => EntryField::Translator(Person::people_from_str(raw)?),
+            "type" => EntryField::Type(s!(text)),
             "url" => EntryField::Url(s!(value)),
             "urldate" => EntryField::UrlDate(Date::parse_date_from_str(value)?),
-            "venue" => EntryField::Venue(s!(value)),
-            "version" => EntryField::Version(s!(value)),
-            "volume" => EntryField::Volume(s!(value)),
-            "year" => EntryField::Year(Date::parse_year_from_str(value)?),
-            _ => EntryField::Other(UnknownField {
-                name: s!(field_name),
-                value: s!(value),
-            }),
+            "usera" => EntryField::UserA(s!(text)),
+            "userb" => EntryField::UserB(s!(text)),
+            "userc" => EntryField::UserC(s!(text)),
+            "userd" => EntryField::UserD(s!(text)),
+            "usere" => EntryField::UserE(s!(text)),
+            "userf" => EntryField::UserF(s!(text)),
+            "venue" => EntryField::Venue(s!(text)),
+            "verba" => EntryField::VerbA(s!(value)),
+            "verbb" => EntryField::VerbB(s!(value)),
+            "verbc" => EntryField::VerbC(s!(value)),
+            "version" => EntryField::Version(s!(text)),
+            "volume" => EntryField::Volume(s!(text)),
+            "xdata" => EntryField::XData(Self::key_list_from_str(value)),
+            "xref" => EntryField::XRef(s!(value.trim())),
+            "year" => EntryField::Year(Year::parse(value)),
+            _ => EntryField::Other(UnknownField::new(field_name, value)),
         };
 
         Ok(entry_field)
     }
+
+    /// Splits comma-separated values, e.g. entry keys in `xdata` or `ids`.
+    fn key_list_from_str(value: &str) -> Vec<String> {
+        value
+            .split(Self::KEY_LIST_SEPARATOR)
+            .map(str::trim)
+            .filter(|it| !it.is_empty())
+            .map(|it| s!(it))
+            .collect()
+    }
+
+    /// Applies biblatex's field aliases, e.g. `address` → `location`, `school` → `institution`.
+    pub(crate) fn into_biblatex(self) -> EntryField {
+        match self {
+            EntryField::Address(s) => EntryField::Location(s),
+            EntryField::Annote(s) => EntryField::Annotation(s),
+            EntryField::Journal(s) => EntryField::JournalTitle(s),
+            EntryField::Key(s) => EntryField::SortKey(s),
+            EntryField::Pdf(s) => EntryField::File(s),
+            EntryField::School(s) => EntryField::Institution(s),
+            field => field,
+        }
+    }
+
+    pub(crate) fn validation_warning(&self) -> Option<String> {
+        match self {
+            EntryField::Doi(doi) => doi.validation_warning(),
+            EntryField::EPrint(eprint) => eprint.validation_warning(),
+            EntryField::Isbn(isbn) => isbn.validation_warning(),
+            EntryField::Ismn(ismn) => ismn.validation_warning(),
+            EntryField::Isrn(isrn) => isrn.validation_warning(),
+            EntryField::Issn(issn) => issn.validation_warning(),
+            EntryField::Other(field) if field.name.eq_ignore_ascii_case("sortyear") => {
+                Some(format!("Invalid sortyear '{}'", field.value))
+            }
+            _ => None,
+        }
+    }
+
+    /// The field name as written in a `.bib` file, e.g. `booktitle`.
+    pub(crate) fn name(&self) -> String {
+        let name = match self {
+            EntryField::Other(field) => return field.name.to_lowercase(),
+            EntryField::XDataRef(reference) => return s!(reference.field()),
+            EntryField::Abstract(_) => "abstract",
+            EntryField::Addendum(_) => "addendum",
+            EntryField::Address(_) => "address",
+            EntryField::Afterword(_) => "afterword",
+            EntryField::Annotation(_) => "annotation",
+            EntryField::Annotator(_) => "annotator",
+            EntryField::Annote(_) => "annote",
+            EntryField::Author(_) => "author",
+            EntryField::AuthorType(_) => "authortype",
+            EntryField::BookAuthor(_) => "bookauthor",
+            EntryField::BookPagination(_) => "bookpagination",
+            EntryField::BookSubtitle(_) => "booksubtitle",
+            EntryField::BookTitle(_) => "booktitle",
+            EntryField::Chapter(_) => "chapter",
+            EntryField::Commentator(_) => "commentator",
+            EntryField::CrossRef(_) => "crossref",
+            EntryField::Date(_) => "date",
+            EntryField::Doi(_) => "doi",
+            EntryField::Edition(_) => "edition",
+            EntryField::Editor(_) => "editor",
+            EntryField::EditorType(_) => "editortype",
+            EntryField::Eid(_) => "eid",
+            EntryField::EntrySet(_) => "entryset",
+            EntryField::EntrySubtype(_) => "entrysubtype",
+            EntryField::EPrint(_) => "eprint",
+            EntryField::EPrintType(_) => "eprinttype",
+            EntryField::EPrintClass(_) => "eprintclass",
+            EntryField::EventDate(_) => "eventdate",
+            EntryField::EventEndDate(_) => "eventenddate",
+            EntryField::EventTitle(_) => "eventtitle",
+            EntryField::Execute(_) => "execute",
+            EntryField::File(_) => "file",
+            EntryField::Foreword(_) => "foreword",
+            EntryField::Gender(_) => "gender",
+            EntryField::Groups(_) => "groups",
+            EntryField::Holder(_) => "holder",
+            EntryField::HowPublished(_) => "howpublished",
+            EntryField::Ids(_) => "ids",
+            EntryField::IndexTitle(_) => "indextitle",
+            EntryField::Institution(_) => "institution",
+            EntryField::Introduction(_) => "introduction",
+            EntryField::Isan(_) => "isan",
+            EntryField::Isbn(_) => "isbn",
+            EntryField::Ismn(_) => "ismn",
+            EntryField::Isrn(_) => "isrn",
+            EntryField::Issn(_) => "issn",
+            EntryField::Issue(_) => "issue",
+            EntryField::IssueSubtitle(_) => "issuesubtitle",
+            EntryField::IssueTitle(_) => "issuetitle",
+            EntryField::Iswc(_) => "iswc",
+            EntryField::Journal(_) => "journal",
+            EntryField::JournalSubtitle(_) => "journalsubtitle",
+            EntryField::JournalTitle(_) => "journaltitle",
+            EntryField::Key(_) => "key",
+            EntryField::Keywords(_) => "keywords",
+            EntryField::Label(_) => "label",
+            EntryField::LangId(_) => "langid",
+            EntryField::Language(_) => "language",
+            EntryField::Library(_) => "library",
+            EntryField::ListA(_) => "lista",
+            EntryField::ListB(_) => "listb",
+            EntryField::ListC(_) => "listc",
+            EntryField::ListD(_) => "listd",
+            EntryField::ListE(_) => "liste",
+            EntryField::ListF(_) => "listf",
+            EntryField::Location(_) => "location",
+            EntryField::MainSubtitle(_) => "mainsubtitle",
+            EntryField::MainTitle(_) => "maintitle",
+            EntryField::Month(_) => "month",
+            EntryField::NameA(_) => "namea",
+            EntryField::NameB(_) => "nameb",
+            EntryField::NameC(_) => "namec",
+            EntryField::Note(_) => "note",
+            EntryField::Number(_) => "number",
+            EntryField::Options(_) => "options",
+            EntryField::Organization(_) => "organization",
+            EntryField::OrigDate(_) => "origdate",
+            EntryField::OrigLanguage(_) => "origlanguage",
+            EntryField::OrigLocation(_) => "origlocation",
+            EntryField::OrigPublisher(_) => "origpublisher",
+            EntryField::OrigTitle(_) => "origtitle",
+            EntryField::Pages(_) => "pages",
+            EntryField::PageTotal(_) => "pagetotal",
+            EntryField::Pagination(_) => "pagination",
+            EntryField::Part(_) => "part",
+            EntryField::Pdf(_) => "pdf",
+            EntryField::Presort(_) => "presort",
+            EntryField::Publisher(_) => "publisher",
+            EntryField::PubState(_) => "pubstate",
+            EntryField::Related(_) => "related",
+            EntryField::RelatedOptions(_) => "relatedoptions",
+            EntryField::RelatedType(_) => "relatedtype",
+            EntryField::ReprintTitle(_) => "reprinttitle",
+            EntryField::School(_) => "school",
+            EntryField::Series(_) => "series",
+            EntryField::ShortAuthor(_) => "shortauthor",
+            EntryField::ShortEdition(_) => "shortedition",
+            EntryField::Shorthand(_) => "shorthand",
+            EntryField::ShorthandIntro(_) => "shorthandintro",
+            EntryField::ShortJournal(_) => "shortjournal",
+            EntryField::ShortSeries(_) => "shortseries",
+            EntryField::ShortTitle(_) => "shorttitle",
+            EntryField::SortKey(_) => "sortkey",
+            EntryField::SortName(_) => "sortname",
+            EntryField::SortTitle(_) => "sorttitle",
+            EntryField::SortYear(_) => "sortyear",
+            EntryField::Subtitle(_) => "subtitle",
+            EntryField::Title(_) => "title",
+            EntryField::Translator(_) => "translator",
+            EntryField::Type(_) => "type",
+            EntryField::Url(_) => "url",
+            EntryField::UrlDate(_) => "urldate",
+            EntryField::UserA(_) => "usera",
+            EntryField::UserB(_) => "userb",
+            EntryField::UserC(_) => "userc",
+            EntryField::UserD(_) => "userd",
+            EntryField::UserE(_) => "usere",
+            EntryField::UserF(_) => "userf",
+            EntryField::Venue(_) => "venue",
+            EntryField::VerbA(_) => "verba",
+            EntryField::VerbB(_) => "verbb",
+            EntryField::VerbC(_) => "verbc",
+            EntryField::Version(_) => "version",
+            EntryField::Volume(_) => "volume",
+            EntryField::XData(_) => "xdata",
+            EntryField::XRef(_) => "xref",
+            EntryField::Year(_) => "year",
+        };
+        s!(name)
+    }
+
+    /// The biblatex data type of the field, which granular `xdata` references must keep.
+    pub(crate) fn data_type(&self) -> DataType {
+        match self {
+            EntryField::Annotator(_)
+            | EntryField::Author(_)
+            | EntryField::BookAuthor(_)
+            | EntryField::Commentator(_)
+            | EntryField::Editor(_)
+            | EntryField::Holder(_)
+            | EntryField::NameA(_)
+            | EntryField::NameB(_)
+            | EntryField::NameC(_)
+            | EntryField::SortName(_)
+            | EntryField::Translator(_) => DataType::NameList,
+            EntryField::Address(_)
+            | EntryField::Institution(_)
+            | EntryField::Language(_)
+            | EntryField::ListA(_)
+            | EntryField::ListB(_)
+            | EntryField::ListC(_)
+            | EntryField::ListD(_)
+            | EntryField::ListE(_)
+            | EntryField::ListF(_)
+            | EntryField::Location(_)
+            | EntryField::Organization(_)
+            | EntryField::OrigLocation(_)
+            | EntryField::OrigPublisher(_)
+            | EntryField::Publisher(_)
+            | EntryField::School(_) => DataType::LiteralList,
+            EntryField::EntrySet(_)
+            | EntryField::Ids(_)
+            | EntryField::Related(_)
+            | EntryField::RelatedOptions(_)
+            | EntryField::XData(_) => DataType::KeyList,
+            _ => DataType::Field,
+        }
+    }
+
+    /// The fields as written in a `.bib` file, as `(name, value)` pairs without delimiters.
+    /// A typed `eprint` is written together with its `eprinttype` and `eprintclass`.
+    pub(crate) fn bib_fields(&self) -> Vec<(String, String)> {
+        match self {
+            EntryField::EPrint(eprint) => {
+                let mut fields = vec![(s!("eprint"), eprint.id())];
+                if let Some(eprint_type) = eprint.eprint_type() {
+                    fields.push((s!("eprinttype"), s!(eprint_type)));
+                }
+                if let Some(class) = eprint.class() {
+                    fields.push((s!("eprintclass"), s!(class)));
+                }
+                fields
+            }
+            field => vec![(field.name(), field.bib_value())],
+        }
+    }
+
+    /// Whether the value is kept as written instead of being decoded from LaTeX, e.g. a `url`.
+    pub(crate) fn is_verbatim(&self) -> bool {
+        match self {
+            EntryField::Other(_) | EntryField::XDataRef(_) => true,
+            field => Self::VERBATIM_FIELDS.contains(&field.name().as_str()),
+        }
+    }
+
+    fn bib_value(&self) -> String {
+        match self {
+            EntryField::Abstract(s)
+            | EntryField::Addendum(s)
+            | EntryField::Afterword(s)
+            | EntryField::Annotation(s)
+            | EntryField::Annote(s)
+            | EntryField::AuthorType(s)
+            | EntryField::BookPagination(s)
+            | EntryField::BookSubtitle(s)
+            | EntryField::BookTitle(s)
+            | EntryField::Chapter(s)
+            | EntryField::CrossRef(s)
+            | EntryField::EditorType(s)
+            | EntryField::Eid(s)
+            | EntryField::EntrySubtype(s)
+            | EntryField::EPrintType(s)
+            | EntryField::EPrintClass(s)
+            | EntryField::EventTitle(s)
+            | EntryField::Execute(s)
+            | EntryField::File(s)
+            | EntryField::Foreword(s)
+            | EntryField::HowPublished(s)
+            | EntryField::IndexTitle(s)
+            | EntryField::Introduction(s)
+            | EntryField::Isan(s)
+            | EntryField::Issue(s)
+            | EntryField::IssueSubtitle(s)
+            | EntryField::IssueTitle(s)
+            | EntryField::Iswc(s)
+            | EntryField::Journal(s)
+            | EntryField::JournalSubtitle(s)
+            | EntryField::JournalTitle(s)
+            | EntryField::Key(s)
+            | EntryField::Label(s)
+            | EntryField::LangId(s)
+            | EntryField::Library(s)
+            | EntryField::MainSubtitle(s)
+            | EntryField::MainTitle(s)
+            | EntryField::Note(s)
+            | EntryField::Number(s)
+            | EntryField::OrigLanguage(s)
+            | EntryField::OrigTitle(s)
+            | EntryField::Pagination(s)
+            | EntryField::Part(s)
+            | EntryField::Pdf(s)
+            | EntryField::Presort(s)
+            | EntryField::RelatedType(s)
+            | EntryField::ReprintTitle(s)
+            | EntryField::Series(s)
+            | EntryField::ShortAuthor(s)
+            | EntryField::ShortEdition(s)
+            | EntryField::Shorthand(s)
+            | EntryField::ShorthandIntro(s)
+            | EntryField::ShortJournal(s)
+            | EntryField::ShortSeries(s)
+            | EntryField::ShortTitle(s)
+            | EntryField::SortKey(s)
+            | EntryField::SortTitle(s)
+            | EntryField::Subtitle(s)
+            | EntryField::Title(s)
+            | EntryField::Type(s)
+            | EntryField::Url(s)
+            | EntryField::UserA(s)
+            | EntryField::UserB(s)
+            | EntryField::UserC(s)
+            | EntryField::UserD(s)
+            | EntryField::UserE(s)
+            | EntryField::UserF(s)
+            | EntryField::Venue(s)
+            | EntryField::VerbA(s)
+            | EntryField::VerbB(s)
+            | EntryField::VerbC(s)
+            | EntryField::Version(s)
+            | EntryField::Volume(s)
+            | EntryField::XRef(s) => s.clone(),
+            EntryField::Address(list)
+            | EntryField::Institution(list)
+            | EntryField::Language(list)
+            | EntryField::ListA(list)
+            | EntryField::ListB(list)
+            | EntryField::ListC(list)
+            | EntryField::ListD(list)
+            | EntryField::ListE(list)
+            | EntryField::ListF(list)
+            | EntryField::Location(list)
+            | EntryField::Organization(list)
+            | EntryField::OrigLocation(list)
+            | EntryField::OrigPublisher(list)
+            | EntryField::Publisher(list)
+            | EntryField::School(list) => list.to_bib(),
+            EntryField::Annotator(people)
+            | EntryField::Author(people)
+            | EntryField::BookAuthor(people)
+            | EntryField::Commentator(people)
+            | EntryField::Editor(people)
+            | EntryField::Holder(people)
+            | EntryField::NameA(people)
+            | EntryField::NameB(people)
+            | EntryField::NameC(people)
+            | EntryField::SortName(people)
+            | EntryField::Translator(people) => Person::people_to_bib(people),
+            EntryField::Date(date)
+            | EntryField::EventDate(date)
+            | EntryField::EventEndDate(date)
+            | EntryField::Month(date)
+            | EntryField::OrigDate(date)
+            | EntryField::UrlDate(date) => date.to_string(),
+            EntryField::EntrySet(keys)
+            | EntryField::Ids(keys)
+            | EntryField::Related(keys)
+            | EntryField::RelatedOptions(keys)
+            | EntryField::XData(keys) => keys.join(", "),
+            EntryField::Groups(keywords) | EntryField::Keywords(keywords) => keywords.to_string(),
+            EntryField::Doi(doi) => doi.to_string(),
+            EntryField::Edition(edition) => edition.to_string(),
+            EntryField::EPrint(eprint) => eprint.id(),
+            EntryField::Gender(gender) => gender.to_string(),
+            EntryField::Isbn(isbn) => isbn.to_string(),
+            EntryField::Ismn(ismn) => ismn.to_string(),
+            EntryField::Isrn(isrn) => isrn.to_string(),
+            EntryField::Issn(issn) => issn.to_string(),
+            EntryField::Options(options) => options
+                .iter()
+                .map(EntryOption::to_string)
+                .collect::<Vec<String>>()
+                .join(", "),
+            EntryField::Pages(pages) => pages
+                .iter()
+                .map(Pages::to_string)
+                .collect::<Vec<String>>()
+                .join(", "),
+            EntryField::PageTotal(n) => n.to_string(),
+            EntryField::PubState(state) => state.to_string(),
+            EntryField::SortYear(year) => year.to_string(),
+            EntryField::Year(year) => year.to_string(),
+            EntryField::XDataRef(reference) => reference.to_string(),
+            EntryField::Other(field) => field.value.clone(),
+        }
+    }
+
+    /// Whether both are the same field, regardless of their values.
+    pub(crate) fn is_same_field(&self, other: &EntryField) -> bool {
+        match (self, other) {
+            (EntryField::Other(_) | EntryField::XDataRef(_), _)
+            | (_, EntryField::Other(_) | EntryField::XDataRef(_)) => self.name() == other.name(),
+            (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
+        }
+    }
+
+    /// The `n`-th name or item, counting from zero, of a name or literal list field.
+    pub(crate) fn nth_item(&self, n: usize) -> Option<EntryField> {
+        let person = |people: &Vec<Person>| people.get(n).map(|p| vec![p.clone()]);
+        match self {
+            EntryField::Annotator(p) => person(p).map(EntryField::Annotator),
+            EntryField::Author(p) => person(p).map(EntryField::Author),
+            EntryField::BookAuthor(p) => person(p).map(EntryField::BookAuthor),
+            EntryField::Commentator(p) => person(p).map(EntryField::Commentator),
+            EntryField::Editor(p) => person(p).map(EntryField::Editor),
+            EntryField::Holder(p) => person(p).map(EntryField::Holder),
+            EntryField::NameA(p) => person(p).map(EntryField::NameA),
+            EntryField::NameB(p) => person(p).map(EntryField::NameB),
+            EntryField::NameC(p) => person(p).map(EntryField::NameC),
+            EntryField::SortName(p) => person(p).map(EntryField::SortName),
+            EntryField::Translator(p) => person(p).map(EntryField::Translator),
+            EntryField::Address(l) => l.nth(n).map(EntryField::Address),
+            EntryField::Institution(l) => l.nth(n).map(EntryField::Institution),
+            EntryField::Language(l) => l.nth(n).map(EntryField::Language),
+            EntryField::ListA(l) => l.nth(n).map(EntryField::ListA),
+            EntryField::ListB(l) => l.nth(n).map(EntryField::ListB),
+            EntryField::ListC(l) => l.nth(n).map(EntryField::ListC),
+            EntryField::ListD(l) => l.nth(n).map(EntryField::ListD),
+            EntryField::ListE(l) => l.nth(n).map(EntryField::ListE),
+            EntryField::ListF(l) => l.nth(n).map(EntryField::ListF),
+            EntryField::Location(l) => l.nth(n).map(EntryField::Location),
+            EntryField::Organization(l) => l.nth(n).map(EntryField::Organization),
+            EntryField::OrigLocation(l) => l.nth(n).map(EntryField::OrigLocation),
+            EntryField::OrigPublisher(l) => l.nth(n).map(EntryField::OrigPublisher),
+            EntryField::Publisher(l) => l.nth(n).map(EntryField::Publisher),
+            EntryField::School(l) => l.nth(n).map(EntryField::School),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn embedded_doi(&self) -> Option<Doi> {
+        match self {
+            EntryField::Url(s) | EntryField::Note(s) | EntryField::HowPublished(s) => {
+                Doi::find_in(s)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl EntryOption {
+    fn options_from_str(value: &str) -> Vec<EntryOption> {
+        EntryField::key_list_from_str(value)
+            .iter()
+            .map(|option| match option.split_once('=') {
+                Some((name, value)) => EntryOption {
+                    name: s!(name.trim()),
+                    value: Some(s!(value.trim())),
+                },
+                None => EntryOption {
+                    name: option.clone(),
+                    value: None,
+                },
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for EntryOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{}={}", self.name, value),
+            None => write!(f, "{}", self.name),
+        }
+    }
 }
 
 impl std::fmt::Display for EntryField {
@@ -209,3 +867,116 @@ impl std::fmt::Display for EntryField {
         }
     }
 }
+
+#[cfg(test)]
+mod entry_field_test {
+    use super::*;
+
+    #[test]
+    fn parse_typed_fields() {
+        // given
+        [
+            (
+                "crossref",
+                " icse2021 ",
+                EntryField::CrossRef(s!("icse2021")),
+            ),
+            (
+                "xdata",
+                "acm, springer-lncs",
+                EntryField::XData(vec![s!("acm"), s!("springer-lncs")]),
+            ),
+            (
+                "ids",
+                "knuth1984,knuth:tex",
+                EntryField::Ids(vec![s!("knuth1984"), s!("knuth:tex")]),
+            ),
+            (
+                "lista",
+                "Paris and Berlin",
+                EntryField::ListA(LiteralList::from_str("Paris and Berlin")),
+            ),
+            (
+                "namea",
+                "Beck, Kent",
+                EntryField::NameA(vec![Person::FirstLast {
+                    first_name: s!("Kent"),
+                    last_name: s!("Beck"),
+                }]),
+            ),
+            (
+                "options",
+                "skipbib, useprefix=false",
+                EntryField::Options(vec![
+                    EntryOption {
+                        name: s!("skipbib"),
+                        value: None,
+                    },
+                    EntryOption {
+                        name: s!("useprefix"),
+                        value: Some(s!("false")),
+                    },
+                ]),
+            ),
+            ("gender", "sf", EntryField::Gender(Gender::SingularFemale)),
+            ("sortyear", "1984", EntryField::SortYear(1984)),
+            (
+                "eventenddate",
+                "2021-05-28",
+                EntryField::EventEndDate(Date::YearMonthDay(2021, 5, 28)),
+            ),
+            (
+                "entrysubtype",
+                "magazine",
+                EntryField::EntrySubtype(s!("magazine")),
+            ),
+            ("verba", r"\relax{}", EntryField::VerbA(s!(r"\relax{}"))),
+        ]
+        .into_iter()
+        .for_each(|(field_name, value, expected)| {
+            // when
+            let actual = EntryField::from_field_name_and_value(field_name, value);
+
+            // then
+            assert_eq!(actual, Ok(expected));
+        });
+    }
+
+    #[test]
+    fn field_names() {
+        // given
+        [
+            (EntryField::BookTitle(s!("Title")), "booktitle"),
+            (EntryField::EPrintClass(s!("cs.PL")), "eprintclass"),
+            (EntryField::PageTotal(12), "pagetotal"),
+            (EntryField::Other(UnknownField::new("Custom", "")), "custom"),
+        ]
+        .iter()
+        .for_each(|(field, expected)| {
+            // when
+            let actual = field.name();
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn parse_invalid_sort_year() {
+        // given
+        let input = "n.d.";
+
+        // when
+        let actual = EntryField::from_field_name_and_value("sortyear", input);
+
+        // then
+        assert_eq!(
+            actual,
+            Ok(EntryField::Other(UnknownField::new("sortyear", "n.d.")))
+        );
+        assert_eq!(
+            actual.ok().and_then(|field| field.validation_warning()),
+            Some(s!("Invalid sortyear 'n.d.'"))
+        );
+    }
+}
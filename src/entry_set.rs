@@ -0,0 +1,220 @@
+use crate::entry::Entry;
+use crate::entry_field::EntryField;
+use crate::entry_type::EntryType;
+use crate::s;
+use std::collections::HashMap;
+
+/// Links the members of `@set` entries to their set. Entries outside a `@set` that
+/// name a set in their own `entryset` field join it, or form a dynamic set if there
+/// is no `@set` entry with that key.
+pub(crate) struct EntrySetResolver;
+
+impl EntrySetResolver {
+    /// Returns the linked entries, with dynamic sets appended, and the warnings
+    /// as `(symbol, warning)` pairs.
+    pub(crate) fn resolve(entries: Vec<Entry>) -> (Vec<Entry>, Vec<(String, String)>) {
+        let mut warnings: Vec<(String, String)> = vec![];
+        let index: HashMap<&str, &Entry> = entries
+            .iter()
+            .rev()
+            .map(|entry| (entry.symbol(), entry))
+            .collect();
+
+        let mut sets: Vec<(String, Vec<String>)> = entries
+            .iter()
+            .filter(|entry| Self::is_set(entry))
+            .map(|entry| (s!(entry.symbol()), Self::entry_set(entry).to_vec()))
+            .collect();
+        for entry in entries.iter().filter(|entry| !Self::is_set(entry)) {
+            for key in Self::entry_set(entry) {
+                if index
+                    .get(key.as_str())
+                    .is_some_and(|set| !Self::is_set(set))
+                {
+                    warnings.push((
+                        s!(entry.symbol()),
+                        format!("Entry set '{}' is not an @set entry", key),
+                    ));
+                    continue;
+                }
+                match sets.iter_mut().find(|(set, _)| set == key) {
+                    Some((_, members)) if members.iter().any(|m| m == entry.symbol()) => {}
+                    Some((_, members)) => members.push(s!(entry.symbol())),
+                    None => sets.push((key.clone(), vec![s!(entry.symbol())])),
+                }
+            }
+        }
+
+        let mut membership: HashMap<&str, &str> = HashMap::new();
+        for (set, members) in sets.iter() {
+            if members.is_empty() {
+                warnings.push((s!(set), s!("Entry set has no members")));
+            }
+            for member in members {
+                match index.get(member.as_str()) {
+                    None => {
+                        warnings.push((s!(set), format!("Entry set member '{}' not found", member)))
+                    }
+                    Some(entry) if Self::is_set(entry) => warnings.push((
+                        s!(set),
+                        format!("Entry set member '{}' is itself a set", member),
+                    )),
+                    Some(_) => match membership.get(member.as_str()) {
+                        Some(other) => warnings.push((
+                            s!(member),
+                            format!("Entry is a member of both '{}' and '{}'", other, set),
+                        )),
+                        None => {
+                            membership.insert(member, set);
+                        }
+                    },
+                }
+            }
+        }
+        let membership: HashMap<String, String> = membership
+            .into_iter()
+            .map(|(member, set)| (s!(member), s!(set)))
+            .collect();
+
+        let mut linked: Vec<Entry> = entries
+            .into_iter()
+            .map(|entry| {
+                let set = membership.get(entry.symbol()).cloned();
+                let fields = match sets.iter().find(|(s, _)| s == entry.symbol()) {
+                    Some((_, members)) if Self::is_set(&entry) => {
+                        Self::with_members(entry.fields(), members)
+                    }
+                    _ if set.is_some() => entry
+                        .fields()
+                        .iter()
+                        .filter(|f| !matches!(f, EntryField::EntrySet(_)))
+                        .cloned()
+                        .collect(),
+                    _ => entry.fields().clone(),
+                };
+                entry.with_fields(fields).with_set(set)
+            })
+            .collect();
+        let dynamic: Vec<Entry> = sets
+            .into_iter()
+            .filter(|(set, _)| !linked.iter().any(|entry| entry.symbol() == set))
+            .map(|(set, members)| {
+                Entry::new(EntryType::Set, set, vec![EntryField::EntrySet(members)])
+            })
+            .collect();
+        linked.extend(dynamic);
+        (linked, warnings)
+    }
+
+    fn is_set(entry: &Entry) -> bool {
+        *entry.entry_type() == EntryType::Set
+    }
+
+    fn entry_set(entry: &Entry) -> &[String] {
+        entry
+            .fields()
+            .iter()
+            .find_map(|f| match f {
+                EntryField::EntrySet(members) => Some(members.as_slice()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// The set's fields with `entryset` listing all its members, including the dynamic ones.
+    fn with_members(fields: &[EntryField], members: &[String]) -> Vec<EntryField> {
+        let mut fields: Vec<EntryField> = fields
+            .iter()
+            .map(|f| match f {
+                EntryField::EntrySet(_) => EntryField::EntrySet(members.to_vec()),
+                f => f.clone(),
+            })
+            .collect();
+        if !fields.iter().any(|f| matches!(f, EntryField::EntrySet(_))) && !members.is_empty() {
+            fields.push(EntryField::EntrySet(members.to_vec()));
+        }
+        fields
+    }
+}
+
+#[cfg(test)]
+mod entry_set_test {
+    use super::*;
+    use crate::entry::{Parser, ParserOptions};
+    use crate::tokenizer::Tokenizer;
+
+    #[test]
+    fn link_static_and_dynamic_members() {
+        // given
+        let input = r#"
+            @set{static, entryset = {a, b}}
+            @article{a, title = {A}}
+            @article{b, title = {B}}
+            @article{c, title = {C}, entryset = {static}}
+            @article{d, title = {D}, entryset = {dynamic}}"#;
+
+        // when
+        let (entries, warnings) = parse(input);
+
+        // then
+        let sets: Vec<(&str, Option<&str>)> = entries
+            .iter()
+            .map(|entry| (entry.symbol(), entry.set()))
+            .collect();
+        assert_eq!(
+            sets,
+            vec![
+                ("static", None),
+                ("a", Some("static")),
+                ("b", Some("static")),
+                ("c", Some("static")),
+                ("d", Some("dynamic")),
+                ("dynamic", None),
+            ]
+        );
+        assert_eq!(
+            *entries[0].fields(),
+            vec![EntryField::EntrySet(vec![s!("a"), s!("b"), s!("c")])]
+        );
+        assert_eq!(*entries[3].fields(), vec![EntryField::Title(s!("C"))]);
+        assert_eq!(
+            *entries[5].fields(),
+            vec![EntryField::EntrySet(vec![s!("d")])]
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn warn_on_invalid_members() {
+        // given
+        let input = r#"
+            @set{one, entryset = {missing, two, a}}
+            @set{two, entryset = {a}}
+            @set{empty, note = {No members}}
+            @article{a, title = {A}}
+            @article{b, entryset = {a}}"#;
+        let expected = vec![
+            s!("Entry 'b': Entry set 'a' is not an @set entry"),
+            s!("Entry 'one': Entry set member 'missing' not found"),
+            s!("Entry 'one': Entry set member 'two' is itself a set"),
+            s!("Entry 'a': Entry is a member of both 'one' and 'two'"),
+            s!("Entry 'empty': Entry set has no members"),
+        ];
+
+        // when
+        let (_, warnings) = parse(input);
+
+        // then
+        assert_eq!(warnings, expected);
+    }
+
+    fn parse(input: &'static str) -> (Vec<Entry>, Vec<String>) {
+        let options = ParserOptions {
+            resolve_sets: true,
+            ..ParserOptions::default()
+        };
+        let mut parser = Parser::with_options(Tokenizer::new(Box::new(input.as_bytes())), options);
+        let entries = parser.parse().unwrap();
+        (entries, parser.warnings().clone())
+    }
+}
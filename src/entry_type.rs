@@ -1,7 +1,7 @@
 use crate::s;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub(crate) enum EntryType {
     Article,
     Book,
@@ -33,13 +33,40 @@ pub(crate) enum EntryType {
     Custom,
     Conference,
     Electronic,
-    MasterThesis,
+    MastersThesis,
     PhdThesis,
     TechReport,
+    Www,
     DataType,
+    Dataset,
+    Software,
+    Artwork,
+    Audio,
+    BibNote,
+    Commentary,
+    Image,
+    Jurisdiction,
+    Legislation,
+    Legal,
+    Letter,
+    Movie,
+    Music,
+    Performance,
+    Review,
+    Standard,
+    Video,
+    XData,
     Other(String),
 }
 
+/// How BibTeX-era entry types with a biblatex equivalent (e.g. `@phdthesis`) are kept.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub(crate) enum TypeNaming {
+    #[default]
+    AsWritten,
+    Biblatex,
+}
+
 impl EntryType {
     pub(crate) fn from_str(s: &str) -> EntryType {
         match s {
@@ -73,13 +100,45 @@ impl EntryType {
             "custom" => EntryType::Custom,
             "conference" => EntryType::Conference,
             "electronic" => EntryType::Electronic,
-            "masterthesis" => EntryType::MasterThesis,
+            "mastersthesis" | "masterthesis" => EntryType::MastersThesis,
             "phdthesis" => EntryType::PhdThesis,
             "techreport" => EntryType::TechReport,
+            "www" => EntryType::Www,
             "datatype" => EntryType::DataType,
+            "dataset" => EntryType::Dataset,
+            "software" => EntryType::Software,
+            "artwork" => EntryType::Artwork,
+            "audio" => EntryType::Audio,
+            "bibnote" => EntryType::BibNote,
+            "commentary" => EntryType::Commentary,
+            "image" => EntryType::Image,
+            "jurisdiction" => EntryType::Jurisdiction,
+            "legislation" => EntryType::Legislation,
+            "legal" => EntryType::Legal,
+            "letter" => EntryType::Letter,
+            "movie" => EntryType::Movie,
+            "music" => EntryType::Music,
+            "performance" => EntryType::Performance,
+            "review" => EntryType::Review,
+            "standard" => EntryType::Standard,
+            "video" => EntryType::Video,
+            "xdata" => EntryType::XData,
             _ => EntryType::Other(s!(s)),
         }
     }
+
+    /// Applies biblatex's entry type aliases, returning the `type` field value implied by the
+    /// alias, e.g. `@phdthesis` becomes `@thesis` with `type = {phdthesis}`.
+    pub(crate) fn into_biblatex(self) -> (EntryType, Option<&'static str>) {
+        match self {
+            EntryType::Conference => (EntryType::InProceedings, None),
+            EntryType::Electronic | EntryType::Www => (EntryType::Online, None),
+            EntryType::MastersThesis => (EntryType::Thesis, Some("mathesis")),
+            EntryType::PhdThesis => (EntryType::Thesis, Some("phdthesis")),
+            EntryType::TechReport => (EntryType::Report, Some("techreport")),
+            t => (t, None),
+        }
+    }
 }
 
 impl std::fmt::Display for EntryType {
@@ -90,3 +149,72 @@ impl std::fmt::Display for EntryType {
         }
     }
 }
+
+#[cfg(test)]
+mod entry_type_test {
+    use super::*;
+
+    #[test]
+    fn parse_from_str() {
+        // given
+        [
+            ("mastersthesis", EntryType::MastersThesis),
+            ("masterthesis", EntryType::MastersThesis),
+            ("conference", EntryType::Conference),
+            ("www", EntryType::Www),
+            ("patent", EntryType::Patent),
+            ("standard", EntryType::Standard),
+            ("dataset", EntryType::Dataset),
+            ("software", EntryType::Software),
+            ("webpage", EntryType::Other(s!("webpage"))),
+        ]
+        .into_iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = EntryType::from_str(input);
+
+            // then
+            assert_eq!(actual, expected);
+        });
+    }
+
+    #[test]
+    fn into_biblatex() {
+        // given
+        [
+            (EntryType::Conference, (EntryType::InProceedings, None)),
+            (EntryType::Electronic, (EntryType::Online, None)),
+            (EntryType::Www, (EntryType::Online, None)),
+            (
+                EntryType::MastersThesis,
+                (EntryType::Thesis, Some("mathesis")),
+            ),
+            (EntryType::PhdThesis, (EntryType::Thesis, Some("phdthesis"))),
+            (
+                EntryType::TechReport,
+                (EntryType::Report, Some("techreport")),
+            ),
+            (EntryType::Article, (EntryType::Article, None)),
+        ]
+        .into_iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = input.into_biblatex();
+
+            // then
+            assert_eq!(actual, expected);
+        });
+    }
+
+    #[test]
+    fn display_lowercase() {
+        // given
+        let input = EntryType::MastersThesis;
+
+        // when
+        let actual = input.to_string();
+
+        // then
+        assert_eq!(actual, "mastersthesis");
+    }
+}
@@ -0,0 +1,370 @@
+use crate::s;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum EPrint {
+    ArXiv {
+        id: String,
+        version: Option<u32>,
+        class: Option<String>,
+    },
+    PubMed(String),
+    PubMedCentral(String),
+    Jstor(String),
+    Hdl(String),
+    GoogleBooks(String),
+    Other {
+        eprint_type: Option<String>,
+        id: String,
+        class: Option<String>,
+    },
+    Invalid {
+        eprint_type: String,
+        id: String,
+    },
+}
+
+impl EPrint {
+    const ARXIV_PREFIX: &'static str = "arxiv:";
+    const PMCID_PREFIX: &'static str = "PMC";
+
+    pub(crate) fn parse(id: &str, eprint_type: Option<&str>, class: Option<&str>) -> EPrint {
+        let id = id.trim();
+        let class = class.map(str::trim).filter(|it| !it.is_empty());
+        let typed = match eprint_type.map(|it| it.trim().to_lowercase()).as_deref() {
+            Some("arxiv") => Self::parse_arxiv(id, class),
+            Some("pubmed") | Some("pmid") => Self::parse_digits(id).map(EPrint::PubMed),
+            Some("pmcid") | Some("pmc") => Self::parse_pmcid(id),
+            Some("jstor") => Self::parse_digits(id).map(EPrint::Jstor),
+            Some("hdl") | Some("handle") => Self::parse_hdl(id),
+            Some("googlebooks") => Self::parse_google_books(id),
+            Some(_) => None,
+            None if id.to_lowercase().starts_with(Self::ARXIV_PREFIX) => {
+                Self::parse_arxiv(id, class)
+            }
+            None => None,
+        };
+        match (typed, eprint_type) {
+            (Some(eprint), _) => eprint,
+            (None, Some(t)) if Self::is_known_type(t) => EPrint::Invalid {
+                eprint_type: s!(t),
+                id: s!(id),
+            },
+            (None, t) => EPrint::Other {
+                eprint_type: t.map(|it| s!(it)),
+                id: s!(id),
+                class: class.map(|it| s!(it)),
+            },
+        }
+    }
+
+    /// The identifier as it should be written in the `eprint` field.
+    pub(crate) fn id(&self) -> String {
+        match self {
+            EPrint::ArXiv { id, version, .. } => match version {
+                Some(v) => format!("{}v{}", id, v),
+                None => id.clone(),
+            },
+            EPrint::PubMed(id)
+            | EPrint::PubMedCentral(id)
+            | EPrint::Jstor(id)
+            | EPrint::Hdl(id)
+            | EPrint::GoogleBooks(id)
+            | EPrint::Other { id, .. }
+            | EPrint::Invalid { id, .. } => id.clone(),
+        }
+    }
+
+    /// The `eprinttype` the identifier is written with, if any.
+    pub(crate) fn eprint_type(&self) -> Option<&str> {
+        match self {
+            EPrint::ArXiv { .. } => Some("arxiv"),
+            EPrint::PubMed(_) => Some("pubmed"),
+            EPrint::PubMedCentral(_) => Some("pmcid"),
+            EPrint::Jstor(_) => Some("jstor"),
+            EPrint::Hdl(_) => Some("hdl"),
+            EPrint::GoogleBooks(_) => Some("googlebooks"),
+            EPrint::Other { eprint_type, .. } => eprint_type.as_deref(),
+            EPrint::Invalid { eprint_type, .. } => Some(eprint_type),
+        }
+    }
+
+    pub(crate) fn class(&self) -> Option<&str> {
+        match self {
+            EPrint::ArXiv { class, .. } | EPrint::Other { class, .. } => class.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_url(&self) -> Option<String> {
+        match self {
+            EPrint::ArXiv { .. } => Some(format!("https://arxiv.org/abs/{}", self.id())),
+            EPrint::PubMed(id) => Some(format!("https://pubmed.ncbi.nlm.nih.gov/{}/", id)),
+            EPrint::PubMedCentral(id) => {
+                Some(format!("https://www.ncbi.nlm.nih.gov/pmc/articles/{}/", id))
+            }
+            EPrint::Jstor(id) => Some(format!("https://www.jstor.org/stable/{}", id)),
+            EPrint::Hdl(id) => Some(format!("https://hdl.handle.net/{}", id)),
+            EPrint::GoogleBooks(id) => Some(format!("https://books.google.com/books?id={}", id)),
+            EPrint::Other { .. } | EPrint::Invalid { .. } => None,
+        }
+    }
+
+    pub(crate) fn validation_warning(&self) -> Option<String> {
+        match self {
+            EPrint::Invalid { eprint_type, id } => {
+                Some(format!("Invalid {} eprint '{}'", eprint_type, id))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_known_type(eprint_type: &str) -> bool {
+        matches!(
+            eprint_type.trim().to_lowercase().as_str(),
+            "arxiv"
+                | "pubmed"
+                | "pmid"
+                | "pmcid"
+                | "pmc"
+                | "jstor"
+                | "hdl"
+                | "handle"
+                | "googlebooks"
+        )
+    }
+
+    /// Parses new style (`2101.00001v2`) and old style (`hep-th/9901001`) arXiv identifiers.
+    fn parse_arxiv(id: &str, class: Option<&str>) -> Option<EPrint> {
+        let without_prefix = match id.to_lowercase().starts_with(Self::ARXIV_PREFIX) {
+            true => &id[Self::ARXIV_PREFIX.len()..],
+            false => id,
+        };
+        let (id, version) = match without_prefix.rsplit_once('v') {
+            Some((id, v)) if !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()) => {
+                (id, v.parse::<u32>().ok())
+            }
+            _ => (without_prefix, None),
+        };
+
+        if let Some((yymm, number)) = id.split_once('.') {
+            let is_new_style = yymm.len() == 4
+                && (number.len() == 4 || number.len() == 5)
+                && yymm
+                    .chars()
+                    .chain(number.chars())
+                    .all(|c| c.is_ascii_digit());
+            if is_new_style {
+                return Some(EPrint::ArXiv {
+                    id: s!(id),
+                    version,
+                    class: class.map(|it| s!(it)),
+                });
+            }
+        }
+
+        let (archive, number) = id.split_once('/')?;
+        let is_old_style = !archive.is_empty()
+            && archive
+                .chars()
+                .all(|c| c.is_ascii_alphabetic() || c == '-' || c == '.')
+            && number.len() == 7
+            && number.chars().all(|c| c.is_ascii_digit());
+        match is_old_style {
+            true => Some(EPrint::ArXiv {
+                id: s!(id),
+                version,
+                class: Some(s!(class.unwrap_or(archive))),
+            }),
+            false => None,
+        }
+    }
+
+    fn parse_digits(id: &str) -> Option<String> {
+        match !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) {
+            true => Some(s!(id)),
+            false => None,
+        }
+    }
+
+    fn parse_pmcid(id: &str) -> Option<EPrint> {
+        let digits = id.strip_prefix(Self::PMCID_PREFIX).unwrap_or(id);
+        Self::parse_digits(digits)
+            .map(|digits| EPrint::PubMedCentral(format!("{}{}", Self::PMCID_PREFIX, digits)))
+    }
+
+    fn parse_hdl(id: &str) -> Option<EPrint> {
+        let (prefix, suffix) = id.split_once('/')?;
+        let is_valid_prefix = !prefix.is_empty()
+            && prefix
+                .split('.')
+                .all(|part| Self::parse_digits(part).is_some());
+        match is_valid_prefix && !suffix.is_empty() {
+            true => Some(EPrint::Hdl(s!(id))),
+            false => None,
+        }
+    }
+
+    fn parse_google_books(id: &str) -> Option<EPrint> {
+        let is_valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        match is_valid {
+            true => Some(EPrint::GoogleBooks(s!(id))),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod eprint_test {
+    use super::*;
+
+    #[test]
+    fn parse_arxiv() {
+        // given
+        [
+            (
+                ("2101.00001", Some("arXiv"), Some("cs.SE")),
+                EPrint::ArXiv {
+                    id: s!("2101.00001"),
+                    version: None,
+                    class: Some(s!("cs.SE")),
+                },
+            ),
+            (
+                ("arXiv:1501.0001v3", Some("arxiv"), None),
+                EPrint::ArXiv {
+                    id: s!("1501.0001"),
+                    version: Some(3),
+                    class: None,
+                },
+            ),
+            (
+                ("arXiv:2101.00001", None, None),
+                EPrint::ArXiv {
+                    id: s!("2101.00001"),
+                    version: None,
+                    class: None,
+                },
+            ),
+            (
+                ("hep-th/9901001v2", Some("arxiv"), None),
+                EPrint::ArXiv {
+                    id: s!("hep-th/9901001"),
+                    version: Some(2),
+                    class: Some(s!("hep-th")),
+                },
+            ),
+            (
+                ("math.GT/0309136", Some("arxiv"), None),
+                EPrint::ArXiv {
+                    id: s!("math.GT/0309136"),
+                    version: None,
+                    class: Some(s!("math.GT")),
+                },
+            ),
+            (
+                ("21.01", Some("arxiv"), None),
+                EPrint::Invalid {
+                    eprint_type: s!("arxiv"),
+                    id: s!("21.01"),
+                },
+            ),
+        ]
+        .iter()
+        .for_each(|((id, eprint_type, class), expected)| {
+            // when
+            let actual = EPrint::parse(id, *eprint_type, *class);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn parse_other_types() {
+        // given
+        [
+            (("12345678", "pubmed"), EPrint::PubMed(s!("12345678"))),
+            (
+                ("PMC1234567", "pmcid"),
+                EPrint::PubMedCentral(s!("PMC1234567")),
+            ),
+            (
+                ("1234567", "pmcid"),
+                EPrint::PubMedCentral(s!("PMC1234567")),
+            ),
+            (("2289074", "jstor"), EPrint::Jstor(s!("2289074"))),
+            (("1721.1/12345", "hdl"), EPrint::Hdl(s!("1721.1/12345"))),
+            (
+                ("dQw4w9WgXcQC", "googlebooks"),
+                EPrint::GoogleBooks(s!("dQw4w9WgXcQC")),
+            ),
+            (
+                ("abc", "pubmed"),
+                EPrint::Invalid {
+                    eprint_type: s!("pubmed"),
+                    id: s!("abc"),
+                },
+            ),
+            (
+                ("hal-01234567", "HAL"),
+                EPrint::Other {
+                    eprint_type: Some(s!("HAL")),
+                    id: s!("hal-01234567"),
+                    class: None,
+                },
+            ),
+        ]
+        .iter()
+        .for_each(|((id, eprint_type), expected)| {
+            // when
+            let actual = EPrint::parse(id, Some(eprint_type), None);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn canonical_urls() {
+        // given
+        [
+            (
+                EPrint::parse("2101.00001v2", Some("arxiv"), None),
+                Some("https://arxiv.org/abs/2101.00001v2"),
+            ),
+            (
+                EPrint::parse("12345678", Some("pubmed"), None),
+                Some("https://pubmed.ncbi.nlm.nih.gov/12345678/"),
+            ),
+            (
+                EPrint::parse("PMC1234567", Some("pmcid"), None),
+                Some("https://www.ncbi.nlm.nih.gov/pmc/articles/PMC1234567/"),
+            ),
+            (
+                EPrint::parse("2289074", Some("jstor"), None),
+                Some("https://www.jstor.org/stable/2289074"),
+            ),
+            (
+                EPrint::parse("1721.1/12345", Some("hdl"), None),
+                Some("https://hdl.handle.net/1721.1/12345"),
+            ),
+            (
+                EPrint::parse("dQw4w9WgXcQC", Some("googlebooks"), None),
+                Some("https://books.google.com/books?id=dQw4w9WgXcQC"),
+            ),
+            (EPrint::parse("hal-01234567", Some("hal"), None), None),
+        ]
+        .iter()
+        .for_each(|(eprint, expected)| {
+            // when
+            let actual = eprint.to_url();
+
+            // then
+            assert_eq!(actual, expected.map(|it| s!(it)));
+        });
+    }
+}
@@ -0,0 +1,496 @@
+use crate::bib_writer::{BibWriter, WriterOptions};
+use crate::date::Date;
+use crate::entry::{Parser, RawEntry, RawValue};
+use crate::person::Person;
+use crate::s;
+use crate::strings::strip_braces;
+use crate::tokenizer::Tokenizer;
+use crate::year::Year;
+use clap::ValueEnum;
+use std::cmp::Ordering;
+use std::io::Cursor;
+
+#[derive(Debug, PartialEq, Clone, Copy, Default, ValueEnum)]
+pub(crate) enum FieldOrder {
+    #[default]
+    Source,
+    Alphabetical,
+    Canonical,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default, ValueEnum)]
+pub(crate) enum EntryOrder {
+    #[default]
+    Source,
+    Key,
+    Year,
+    Author,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FormatOptions {
+    pub(crate) writer: WriterOptions,
+    pub(crate) field_order: FieldOrder,
+    pub(crate) entry_order: EntryOrder,
+}
+
+/// Rewrites `.bib` files in a canonical style. Values are kept as written,
+/// including the braces protecting their case, and types and field names are lower-cased.
+/// Comments and `@string`, `@preamble` and `@comment` blocks are kept as written, in place;
+/// sorting only moves entries. Values joined with `#` are not supported.
+pub(crate) struct Formatter {
+    options: FormatOptions,
+}
+
+/// A part of a `.bib` file: an entry, or text kept as written.
+enum Block {
+    Entry(RawEntry),
+    Verbatim(String),
+}
+
+impl Formatter {
+    /// biblatex's field order: names, titles, publication details, identifiers, then notes.
+    /// Fields not listed follow in source order.
+    const CANONICAL_ORDER: [&'static str; 78] = [
+        "author",
+        "editor",
+        "editortype",
+        "translator",
+        "annotator",
+        "commentator",
+        "introduction",
+        "foreword",
+        "afterword",
+        "holder",
+        "bookauthor",
+        "title",
+        "subtitle",
+        "titleaddon",
+        "shorttitle",
+        "maintitle",
+        "mainsubtitle",
+        "booktitle",
+        "booksubtitle",
+        "journaltitle",
+        "journal",
+        "journalsubtitle",
+        "shortjournal",
+        "issuetitle",
+        "issuesubtitle",
+        "eventtitle",
+        "eventdate",
+        "venue",
+        "origtitle",
+        "language",
+        "origlanguage",
+        "edition",
+        "version",
+        "type",
+        "series",
+        "volume",
+        "part",
+        "number",
+        "issue",
+        "chapter",
+        "pages",
+        "pagetotal",
+        "howpublished",
+        "organization",
+        "institution",
+        "school",
+        "publisher",
+        "location",
+        "address",
+        "date",
+        "year",
+        "month",
+        "origdate",
+        "isbn",
+        "issn",
+        "ismn",
+        "isrn",
+        "isan",
+        "iswc",
+        "eid",
+        "doi",
+        "eprint",
+        "eprinttype",
+        "archiveprefix",
+        "eprintclass",
+        "primaryclass",
+        "url",
+        "urldate",
+        "crossref",
+        "xref",
+        "xdata",
+        "note",
+        "addendum",
+        "pubstate",
+        "keywords",
+        "abstract",
+        "annotation",
+        "file",
+    ];
+
+    /// Blocks that are not entries, written back unchanged.
+    const VERBATIM_TYPES: [&'static str; 3] = ["string", "preamble", "comment"];
+
+    pub(crate) fn with_options(options: FormatOptions) -> Formatter {
+        Formatter { options }
+    }
+
+    pub(crate) fn format(&self, input: &str) -> Result<String, String> {
+        let blocks = self.sort_blocks(Self::blocks(input)?);
+        let writer = BibWriter::with_options(self.options.writer.clone());
+        Ok(blocks
+            .iter()
+            .map(|block| match block {
+                Block::Entry(entry) => writer.write_fields(
+                    &entry.r#type.to_lowercase(),
+                    &entry.symbol,
+                    &self.ordered_fields(entry),
+                ),
+                Block::Verbatim(text) => format!("{}\n", text),
+            })
+            .collect::<Vec<String>>()
+            .join("\n"))
+    }
+
+    /// Splits the input into entries and the text around them. Text outside entries,
+    /// such as `%` comments, is a comment to BibTeX and kept with its lines trimmed.
+    fn blocks(input: &str) -> Result<Vec<Block>, String> {
+        let mut blocks = vec![];
+        let mut text = String::new();
+        let mut rest = input;
+        while let Some(c) = rest.chars().next() {
+            let end = match c {
+                '%' => rest.find('\n').unwrap_or(rest.len()),
+                '@' => Self::block_end(rest),
+                c => c.len_utf8(),
+            };
+            let (block, remaining) = rest.split_at(end);
+            match c {
+                '@' => {
+                    blocks.extend(Self::comment(&text));
+                    text.clear();
+                    blocks.extend(Self::block(block)?);
+                }
+                _ => text.push_str(block),
+            }
+            rest = remaining;
+        }
+        blocks.extend(Self::comment(&text));
+        Ok(blocks)
+    }
+
+    fn comment(text: &str) -> Option<Block> {
+        let lines: Vec<&str> = text.lines().map(str::trim).collect();
+        Some(lines.join("\n").trim().to_string())
+            .filter(|comment| !comment.is_empty())
+            .map(Block::Verbatim)
+    }
+
+    fn block(block: &str) -> Result<Vec<Block>, String> {
+        let block_type: String = block[1..]
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_alphabetic())
+            .collect();
+        if Self::VERBATIM_TYPES.contains(&block_type.to_lowercase().as_str()) {
+            return Ok(vec![Block::Verbatim(s!(block.trim_end()))]);
+        }
+        Self::check_concatenation(block)?;
+        let reader = Box::new(Cursor::new(block.to_string()));
+        let entries = Parser::new(Tokenizer::new(reader)).parse_raw()?;
+        Ok(entries.into_iter().map(Block::Entry).collect())
+    }
+
+    /// The end of the block starting at `@`, after the brace or parenthesis closing it,
+    /// or the end of the input if it is not closed.
+    fn block_end(block: &str) -> usize {
+        let Some(start) = block.find(['{', '(']) else {
+            return block.len();
+        };
+        let closing = match &block[start..start + 1] {
+            "(" => ')',
+            _ => '}',
+        };
+        let mut depth = 0;
+        for (i, c) in block[start + 1..].char_indices() {
+            match c {
+                c if c == closing && depth == 0 => return start + 1 + i + c.len_utf8(),
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        block.len()
+    }
+
+    /// `#` joins values, e.g. `"Proc. " # jan`, which the raw field values can't hold.
+    fn check_concatenation(block: &str) -> Result<(), String> {
+        let mut depth = 0;
+        let mut is_quoted = false;
+        for c in block.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                '"' if depth == 1 => is_quoted = !is_quoted,
+                '#' if depth == 1 && !is_quoted => {
+                    return Err(format!(
+                        "Values joined with '#' are not supported in '{}'",
+                        block.split(',').next().unwrap_or_default().trim()
+                    ))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Sorts the entries among the blocks, leaving the other blocks in place.
+    fn sort_blocks(&self, blocks: Vec<Block>) -> Vec<Block> {
+        let mut entries: Vec<RawEntry> = blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Entry(entry) => Some(entry.clone()),
+                Block::Verbatim(_) => None,
+            })
+            .collect();
+        self.sort_entries(&mut entries);
+        let mut sorted = entries.into_iter();
+        blocks
+            .into_iter()
+            .filter_map(|block| match block {
+                Block::Entry(_) => sorted.next().map(Block::Entry),
+                verbatim => Some(verbatim),
+            })
+            .collect()
+    }
+
+    /// Whether the input is already formatted, i.e. formatting it would not change it.
+    pub(crate) fn is_formatted(&self, input: &str) -> Result<bool, String> {
+        Ok(self.format(input)? == input)
+    }
+
+    fn ordered_fields(&self, entry: &RawEntry) -> Vec<(String, RawValue)> {
+        let mut fields: Vec<(String, RawValue)> = entry
+            .fields
+            .iter()
+            .map(|(name, value)| (name.to_lowercase(), value.clone()))
+            .collect();
+        match self.options.field_order {
+            FieldOrder::Source => {}
+            FieldOrder::Alphabetical => fields.sort_by(|(a, _), (b, _)| a.cmp(b)),
+            FieldOrder::Canonical => fields.sort_by_key(|(name, _)| {
+                Self::CANONICAL_ORDER
+                    .iter()
+                    .position(|it| it == name)
+                    .unwrap_or(Self::CANONICAL_ORDER.len())
+            }),
+        }
+        fields
+    }
+
+    /// Sorts stably, keeping the source order of equal entries and putting
+    /// entries without a year or author last.
+    fn sort_entries(&self, entries: &mut [RawEntry]) {
+        match self.options.entry_order {
+            EntryOrder::Source => {}
+            EntryOrder::Key => entries.sort_by_key(|entry| entry.symbol.to_lowercase()),
+            EntryOrder::Year => {
+                entries.sort_by(|a, b| Self::missing_last(Self::year(a), Self::year(b)))
+            }
+            EntryOrder::Author => {
+                entries.sort_by(|a, b| Self::missing_last(Self::author(a), Self::author(b)))
+            }
+        }
+    }
+
+    fn missing_last<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    fn field<'a>(entry: &'a RawEntry, names: &[&str]) -> Option<&'a str> {
+        names.iter().find_map(|name| {
+            entry
+                .fields
+                .iter()
+                .find(|(field_name, _)| field_name.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        })
+    }
+
+    fn year(entry: &RawEntry) -> Option<i32> {
+        Self::field(entry, &["year"])
+            .and_then(|year| Year::parse(&strip_braces(year)).value())
+            .or_else(|| {
+                Self::field(entry, &["date"])
+                    .and_then(|date| Date::parse_date_from_str(&strip_braces(date)).ok())
+                    .and_then(|date| date.year())
+            })
+    }
+
+    fn author(entry: &RawEntry) -> Option<String> {
+        Self::field(entry, &["author", "editor"])
+            .and_then(|names| Person::people_from_str(names).ok())
+            .filter(|people| !people.is_empty())
+            .map(|people| Person::people_to_bib(&people).to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod formatter_test {
+    use super::*;
+    use crate::bib_writer::ValueDelimiter;
+
+    const INPUT: &str = r#"
+        @Article{b2020,
+            Title = {The {XP} Way},
+            year = 2020, month = jan,
+            author = "Smith, Jane",
+        }
+        @book{a2021, title = {Other}, author = {Doe, John}, date = {2019-05}}
+        @misc{c, title = {No Year}}"#;
+
+    #[test]
+    fn format_with_options() {
+        // given
+        let options = FormatOptions {
+            writer: WriterOptions {
+                indent: s!("\t"),
+                align_equals: true,
+                trailing_comma: true,
+                delimiter: ValueDelimiter::Quotes,
+            },
+            field_order: FieldOrder::Canonical,
+            entry_order: EntryOrder::Key,
+        };
+        let expected = "@book{a2021,\n\
+                        \tauthor = \"Doe, John\",\n\
+                        \ttitle  = \"Other\",\n\
+                        \tdate   = \"2019-05\",\n\
+                        }\n\
+                        \n\
+                        @article{b2020,\n\
+                        \tauthor = \"Smith, Jane\",\n\
+                        \ttitle  = \"The {XP} Way\",\n\
+                        \tyear   = 2020,\n\
+                        \tmonth  = jan,\n\
+                        }\n\
+                        \n\
+                        @misc{c,\n\
+                        \ttitle = \"No Year\",\n\
+                        }\n";
+
+        // when
+        let actual = Formatter::with_options(options).format(INPUT);
+
+        // then
+        assert_eq!(actual, Ok(expected.to_string()));
+    }
+
+    #[test]
+    fn sort_entries() {
+        // given
+        [
+            (EntryOrder::Source, ["b2020", "a2021", "c"]),
+            (EntryOrder::Key, ["a2021", "b2020", "c"]),
+            (EntryOrder::Year, ["a2021", "b2020", "c"]),
+            (EntryOrder::Author, ["a2021", "b2020", "c"]),
+        ]
+        .iter()
+        .for_each(|(entry_order, expected)| {
+            // when
+            let formatter = Formatter::with_options(FormatOptions {
+                entry_order: *entry_order,
+                ..FormatOptions::default()
+            });
+            let mut entries = Parser::new(Tokenizer::new(Box::new(INPUT.as_bytes())))
+                .parse_raw()
+                .unwrap();
+            formatter.sort_entries(&mut entries);
+
+            // then
+            let actual: Vec<&str> = entries.iter().map(|it| it.symbol.as_str()).collect();
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn check_formatted() {
+        // given
+        let formatter = Formatter::with_options(FormatOptions::default());
+        let formatted = formatter.format(INPUT).unwrap();
+
+        // when
+        let actual = (
+            formatter.is_formatted(INPUT),
+            formatter.is_formatted(&formatted),
+        );
+
+        // then
+        assert_eq!(actual, (Ok(false), Ok(true)));
+    }
+
+    #[test]
+    fn format_comments_strings_and_preambles() {
+        // given
+        let input = r#"
+            % Exported from DBLP
+            @String{jcp = "J. Comput. Phys."}
+            @preamble{ "\newcommand{\noop}[1]{}" }
+            @comment{ jabref-meta: databaseType:biblatex; }
+
+            @Article{b, journal = jcp, year = 2020}
+            % Trailing note
+            @article{a, journal = jcp}
+        "#;
+        let expected = "% Exported from DBLP\n\
+                        \n\
+                        @String{jcp = \"J. Comput. Phys.\"}\n\
+                        \n\
+                        @preamble{ \"\\newcommand{\\noop}[1]{}\" }\n\
+                        \n\
+                        @comment{ jabref-meta: databaseType:biblatex; }\n\
+                        \n\
+                        @article{a,\n  journal = jcp\n}\n\
+                        \n\
+                        % Trailing note\n\
+                        \n\
+                        @article{b,\n  journal = jcp,\n  year = 2020\n}\n";
+        let formatter = Formatter::with_options(FormatOptions {
+            entry_order: EntryOrder::Key,
+            ..FormatOptions::default()
+        });
+
+        // when
+        let actual = formatter.format(input);
+
+        // then
+        assert_eq!(actual, Ok(expected.to_string()));
+        assert_eq!(formatter.is_formatted(expected), Ok(true));
+    }
+
+    #[test]
+    fn format_concatenation() {
+        // given
+        let input = r#"@article{a, title = {A # B}, note = "Vol. " # jan}"#;
+
+        // when
+        let actual = Formatter::with_options(FormatOptions::default()).format(input);
+
+        // then
+        assert_eq!(
+            actual,
+            Err(s!(
+                "Values joined with '#' are not supported in '@article{a'"
+            ))
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use crate::s;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum Gender {
+    SingularFemale,
+    SingularMale,
+    SingularNeuter,
+    PluralFemale,
+    PluralMale,
+    PluralNeuter,
+    PluralMixed,
+    Other(String),
+}
+
+impl Gender {
+    pub(crate) fn from_str(s: &str) -> Gender {
+        match s.trim() {
+            "sf" => Gender::SingularFemale,
+            "sm" => Gender::SingularMale,
+            "sn" => Gender::SingularNeuter,
+            "pf" => Gender::PluralFemale,
+            "pm" => Gender::PluralMale,
+            "pn" => Gender::PluralNeuter,
+            "pp" => Gender::PluralMixed,
+            other => Gender::Other(s!(other)),
+        }
+    }
+}
+
+impl std::fmt::Display for Gender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Gender::SingularFemale => "sf",
+            Gender::SingularMale => "sm",
+            Gender::SingularNeuter => "sn",
+            Gender::PluralFemale => "pf",
+            Gender::PluralMale => "pm",
+            Gender::PluralNeuter => "pn",
+            Gender::PluralMixed => "pp",
+            Gender::Other(s) => s,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod gender_test {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        // given
+        [
+            ("sf", Gender::SingularFemale),
+            ("sm", Gender::SingularMale),
+            ("sn", Gender::SingularNeuter),
+            ("pf", Gender::PluralFemale),
+            ("pm", Gender::PluralMale),
+            ("pn", Gender::PluralNeuter),
+            ("pp", Gender::PluralMixed),
+            ("female", Gender::Other(s!("female"))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Gender::from_str(input);
+
+            // then
+            assert_eq!(actual, *expected, "{}", input);
+            assert_eq!(actual.to_string(), *input, "{}", input);
+        });
+    }
+
+    #[test]
+    fn parse_trims_whitespace() {
+        // given
+        let input = " pm ";
+
+        // when
+        let actual = Gender::from_str(input);
+
+        // then
+        assert_eq!(actual, Gender::PluralMale);
+    }
+}
@@ -0,0 +1,458 @@
+use crate::date::Date;
+use crate::entry::Entry;
+use crate::entry_field::EntryField;
+use crate::entry_type::EntryType;
+use crate::eprint::EPrint;
+use crate::person::Person;
+use crate::s;
+use crate::year::Year;
+use serde_yaml::{Mapping, Value};
+
+/// Whether a value describes the entry itself or the parent it is published in,
+/// e.g. the journal of an article.
+#[derive(PartialEq, Clone, Copy)]
+enum Side {
+    Item,
+    Parent,
+}
+
+/// Writes entries as a Hayagriva YAML bibliography, as read by Typst. Entries published
+/// in another one get it as their `parent`, from the `crossref` entry if there is one.
+pub(crate) struct HayagrivaWriter;
+
+impl HayagrivaWriter {
+    /// Identifiers, written together as the `serial-number` of the item or its parent.
+    const SERIAL_NUMBERS: [&'static str; 6] = ["doi", "isbn", "issn", "arxiv", "pmid", "pmcid"];
+
+    pub(crate) fn write(entries: &[Entry]) -> String {
+        let bibliography: Mapping = entries
+            .iter()
+            .map(|entry| {
+                (
+                    Value::from(entry.symbol()),
+                    Value::Mapping(Self::entry(entry, entries)),
+                )
+            })
+            .collect();
+        serde_yaml::to_string(&bibliography).unwrap()
+    }
+
+    /// The entry, with `crossref` parents looked up in `entries`.
+    fn entry(entry: &Entry, entries: &[Entry]) -> Mapping {
+        let (item_type, parent_type) = Self::types(entry);
+        let values: Vec<(Side, &'static str, Value)> = entry
+            .fields()
+            .iter()
+            .flat_map(|field| Self::values(entry, field, parent_type.is_some()))
+            .collect();
+        let mut item = Self::typed(item_type, Self::side(&values, Side::Item));
+        let crossref = entry.fields().iter().find_map(|field| match field {
+            EntryField::CrossRef(key) => entries.iter().find(|it| it.symbol() == key),
+            _ => None,
+        });
+        let mut parent = match (parent_type, crossref) {
+            (Some(_), Some(crossref)) => Self::entry(crossref, &[]),
+            (Some(parent_type), None) => Self::typed(parent_type, Mapping::new()),
+            (None, _) => return item,
+        };
+        for (key, value) in Self::side(&values, Side::Parent) {
+            if !parent.contains_key(&key) {
+                parent.insert(key, value);
+            }
+        }
+        if parent.len() > 1 {
+            item.insert(Value::from("parent"), Value::Mapping(parent));
+        }
+        item
+    }
+
+    fn typed(entry_type: &str, values: Mapping) -> Mapping {
+        let mut mapping = Mapping::new();
+        mapping.insert(Value::from("type"), Value::from(entry_type));
+        mapping.extend(values);
+        mapping
+    }
+
+    /// The values of one side, with the first of repeated ones kept and the identifiers grouped.
+    fn side(values: &[(Side, &'static str, Value)], side: Side) -> Mapping {
+        let mut mapping = Mapping::new();
+        let mut serial_numbers = Mapping::new();
+        for (_, name, value) in values.iter().filter(|(it, _, _)| *it == side) {
+            let target = match Self::SERIAL_NUMBERS.contains(name) {
+                true => &mut serial_numbers,
+                false => &mut mapping,
+            };
+            if !target.contains_key(*name) {
+                target.insert(Value::from(*name), value.clone());
+            }
+        }
+        if !serial_numbers.is_empty() {
+            mapping.insert(Value::from("serial-number"), Value::Mapping(serial_numbers));
+        }
+        mapping
+    }
+
+    fn types(entry: &Entry) -> (&'static str, Option<&'static str>) {
+        let subtype = entry.fields().iter().find_map(|field| match field {
+            EntryField::EntrySubtype(subtype) => Some(subtype.to_lowercase()),
+            _ => None,
+        });
+        match entry.entry_type() {
+            EntryType::Article => match subtype.as_deref() {
+                Some("newspaper") => ("article", Some("newspaper")),
+                _ => ("article", Some("periodical")),
+            },
+            EntryType::InProceedings | EntryType::Conference => ("article", Some("proceedings")),
+            EntryType::InCollection | EntryType::SuppCollection => ("anthos", Some("anthology")),
+            EntryType::InBook | EntryType::BookInBook | EntryType::SuppBook => {
+                ("chapter", Some("book"))
+            }
+            EntryType::InReference => ("entry", Some("reference")),
+            EntryType::Book | EntryType::MvBook | EntryType::Manual | EntryType::Commentary => {
+                ("book", None)
+            }
+            EntryType::Collection | EntryType::MvCollection => ("anthology", None),
+            EntryType::Proceedings | EntryType::MvProceedings => ("proceedings", None),
+            EntryType::Reference | EntryType::MvReference => ("reference", None),
+            EntryType::Periodical | EntryType::SuppPeriodical => ("periodical", None),
+            EntryType::Thesis | EntryType::MastersThesis | EntryType::PhdThesis => ("thesis", None),
+            EntryType::Report | EntryType::TechReport => ("report", None),
+            EntryType::Patent => ("patent", None),
+            EntryType::Online | EntryType::Electronic | EntryType::Www => ("web", None),
+            EntryType::Software => ("repository", None),
+            EntryType::Unpublished => ("manuscript", None),
+            EntryType::Audio | EntryType::Music => ("audio", None),
+            EntryType::Video | EntryType::Movie => ("video", None),
+            EntryType::Artwork | EntryType::Image => ("artwork", None),
+            EntryType::Jurisdiction => ("case", None),
+            EntryType::Legislation => ("legislation", None),
+            _ => ("misc", None),
+        }
+    }
+
+    fn values(
+        entry: &Entry,
+        field: &EntryField,
+        has_parent: bool,
+    ) -> Vec<(Side, &'static str, Value)> {
+        let has = |f: fn(&EntryField) -> bool| entry.fields().iter().any(f);
+        let is_article = *entry.entry_type() == EntryType::Article;
+        let parent_or_item = match has_parent {
+            true => Side::Parent,
+            false => Side::Item,
+        };
+        let item = |name: &'static str, value: Value| vec![(Side::Item, name, value)];
+        let parent = |name: &'static str, value: Value| match has_parent {
+            true => vec![(Side::Parent, name, value)],
+            false => vec![],
+        };
+        match field {
+            EntryField::Title(title) => item("title", Value::from(Self::title(entry, title))),
+            EntryField::Author(people) => item("author", Self::names(people)),
+            EntryField::Editor(people) => match is_article {
+                true => item("editor", Self::names(people)),
+                false => vec![(parent_or_item, "editor", Self::names(people))],
+            },
+            EntryField::Translator(people) => {
+                let mut translators = Mapping::new();
+                translators.insert(Value::from("role"), Value::from("translator"));
+                translators.insert(Value::from("names"), Self::names(people));
+                item(
+                    "affiliated",
+                    Value::Sequence(vec![Value::Mapping(translators)]),
+                )
+            }
+            EntryField::BookAuthor(people) => parent("author", Self::names(people)),
+            EntryField::JournalTitle(s) | EntryField::BookTitle(s) => {
+                parent("title", Value::from(s.as_str()))
+            }
+            EntryField::Journal(s) if !has(|f| matches!(f, EntryField::JournalTitle(_))) => {
+                parent("title", Value::from(s.as_str()))
+            }
+            EntryField::Date(date) => Self::date(date)
+                .map(|date| item("date", date))
+                .unwrap_or_default(),
+            EntryField::Year(year) if !has(|f| matches!(f, EntryField::Date(_))) => {
+                match (entry.year_month(), year) {
+                    (Some(date), _) => Self::date(&date)
+                        .map(|date| item("date", date))
+                        .unwrap_or_default(),
+                    (None, Year::Literal(_) | Year::PubState(_) | Year::NoDate) => vec![],
+                    (None, year) => year
+                        .value()
+                        .map(|year| item("date", Value::from(year)))
+                        .unwrap_or_default(),
+                }
+            }
+            EntryField::Pages(pages) => item(
+                "page-range",
+                Value::from(
+                    pages
+                        .iter()
+                        .map(|pages| pages.to_string().replace("--", "-"))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                ),
+            ),
+            EntryField::PageTotal(n) => item("page-total", Value::from(*n)),
+            EntryField::Volume(s) => vec![(parent_or_item, "volume", Value::from(s.as_str()))],
+            EntryField::Number(s) | EntryField::Issue(s) => match is_article {
+                true => parent("issue", Value::from(s.as_str())),
+                false => item("issue", Value::from(s.as_str())),
+            },
+            EntryField::Edition(edition) => {
+                vec![(parent_or_item, "edition", Value::from(edition.to_string()))]
+            }
+            EntryField::Publisher(list) => {
+                vec![(parent_or_item, "publisher", Value::from(list.joined()))]
+            }
+            EntryField::Location(list) | EntryField::Address(list) => {
+                vec![(parent_or_item, "location", Value::from(list.joined()))]
+            }
+            EntryField::Institution(list)
+            | EntryField::School(list)
+            | EntryField::Organization(list) => item("organization", Value::from(list.joined())),
+            EntryField::Type(s) => item("genre", Value::from(s.as_str())),
+            EntryField::Url(url) => {
+                let url_date = entry.fields().iter().find_map(|field| match field {
+                    EntryField::UrlDate(date) => Self::date(date),
+                    _ => None,
+                });
+                match url_date {
+                    Some(date) => {
+                        let mut url_with_date = Mapping::new();
+                        url_with_date.insert(Value::from("value"), Value::from(url.as_str()));
+                        url_with_date.insert(Value::from("date"), date);
+                        item("url", Value::Mapping(url_with_date))
+                    }
+                    None => item("url", Value::from(url.as_str())),
+                }
+            }
+            EntryField::Note(s) => item("note", Value::from(s.as_str())),
+            EntryField::Abstract(s) => item("abstract", Value::from(s.as_str())),
+            EntryField::Doi(doi) => {
+                let mut values = item("doi", Value::from(doi.to_string()));
+                // Entries with a DOI but no url field get the resolver URL of the DOI as their url.
+                if !has(|f| matches!(f, EntryField::Url(_))) {
+                    values.extend(
+                        doi.to_url()
+                            .map(|url| (Side::Item, "url", Value::from(url))),
+                    );
+                }
+                values
+            }
+            EntryField::Isbn(isbn) => {
+                vec![(parent_or_item, "isbn", Value::from(isbn.to_string()))]
+            }
+            EntryField::Issn(issn) => {
+                vec![(parent_or_item, "issn", Value::from(issn.to_string()))]
+            }
+            EntryField::EPrint(eprint) => match eprint {
+                EPrint::ArXiv { .. } => item("arxiv", Value::from(eprint.id())),
+                EPrint::PubMed(id) => item("pmid", Value::from(id.as_str())),
+                EPrint::PubMedCentral(id) => item("pmcid", Value::from(id.as_str())),
+                _ => vec![],
+            },
+            _ => vec![],
+        }
+    }
+
+    fn title(entry: &Entry, title: &str) -> String {
+        let subtitle = entry.fields().iter().find_map(|field| match field {
+            EntryField::Subtitle(subtitle) => Some(subtitle),
+            _ => None,
+        });
+        match subtitle {
+            Some(subtitle) => format!("{}: {}", title, subtitle),
+            None => s!(title),
+        }
+    }
+
+    /// Names as Hayagriva parses them, `Last, First[, Suffix]`.
+    fn names(people: &[Person]) -> Value {
+        Value::Sequence(
+            people
+                .iter()
+                .map(|person| Value::from(person.to_last_first()))
+                .collect(),
+        )
+    }
+
+    /// A year as a number, a more precise date as `YYYY-MM-DD` or `YYYY-MM`. Hayagriva
+    /// has no ranges, so those are given by their start.
+    fn date(date: &Date) -> Option<Value> {
+        match date {
+            Date::Year(year) => Some(Value::from(*year)),
+            Date::YearMonth(..) | Date::YearMonthDay(..) => Some(Value::from(date.to_string())),
+            Date::Range(start, _) => Self::date(start),
+            Date::Month(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod hayagriva_test {
+    use super::*;
+    use crate::entry::Parser;
+    use crate::tokenizer::Tokenizer;
+    use std::io::Cursor;
+
+    #[test]
+    fn write_entries() {
+        // given
+        let input = r#"
+            @article{knuth-1984,
+                author = {Knuth, Donald E. and Ford, Jr., Henry},
+                title = {Literate Programming},
+                journaltitle = {The Computer Journal},
+                volume = {27}, number = {2},
+                pages = {97--111},
+                date = {1984-05},
+                doi = {10.1093/comjnl/27.2.97}, issn = {0010-4620},
+                eprint = {6350001}, eprinttype = {pubmed},
+            }
+            @book{beck-2004,
+                author = {Beck, Kent}, translator = {Roe, Richard},
+                title = {Extreme Programming}, subtitle = {Explained},
+                year = {2004}, month = mar, edition = {2},
+                publisher = {Addison-Wesley}, location = {Boston},
+                url = {https://example.com}, urldate = {2021-01-02},
+                doi = {10.1000/182},
+            }"#;
+        let expected = "knuth-1984:
+  type: article
+  author:
+  - Knuth, Donald E.
+  - Ford, Henry, Jr.
+  title: Literate Programming
+  page-range: 97-111
+  date: 1984-05
+  url: https://doi.org/10.1093/comjnl/27.2.97
+  serial-number:
+    doi: 10.1093/comjnl/27.2.97
+    pmid: '6350001'
+  parent:
+    type: periodical
+    title: The Computer Journal
+    volume: '27'
+    issue: '2'
+    serial-number:
+      issn: 0010-4620
+beck-2004:
+  type: book
+  author:
+  - Beck, Kent
+  affiliated:
+  - role: translator
+    names:
+    - Roe, Richard
+  title: 'Extreme Programming: Explained'
+  date: 2004-03
+  edition: '2'
+  publisher: Addison-Wesley
+  location: Boston
+  url:
+    value: https://example.com
+    date: 2021-01-02
+  serial-number:
+    doi: 10.1000/182
+";
+
+        // when
+        let actual = HayagrivaWriter::write(&parse(input));
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn write_parents() {
+        // given
+        let input = r#"
+            @proceedings{icse,
+                editor = {Doe, John},
+                title = {Proceedings of ICSE},
+                year = {2001}, publisher = {IEEE},
+            }
+            @inproceedings{paper, author = {Roe, Jane}, title = {Paper}, crossref = {icse}, pages = {1--10}}
+            @incollection{chapter,
+                author = {Roe, Jane}, editor = {Doe, John},
+                title = {Chapter}, booktitle = {Anthology},
+                year = {2002}, publisher = {Publisher}, isbn = {978-0-13-405199-4},
+            }
+            @inbook{lone, title = {Lone Chapter}}"#;
+        let expected = "icse:
+  type: proceedings
+  editor:
+  - Doe, John
+  title: Proceedings of ICSE
+  date: 2001
+  publisher: IEEE
+paper:
+  type: article
+  author:
+  - Roe, Jane
+  title: Paper
+  page-range: 1-10
+  parent:
+    type: proceedings
+    editor:
+    - Doe, John
+    title: Proceedings of ICSE
+    date: 2001
+    publisher: IEEE
+chapter:
+  type: anthos
+  author:
+  - Roe, Jane
+  title: Chapter
+  date: 2002
+  parent:
+    type: anthology
+    editor:
+    - Doe, John
+    title: Anthology
+    publisher: Publisher
+    serial-number:
+      isbn: '9780134051994'
+lone:
+  type: chapter
+  title: Lone Chapter
+";
+
+        // when
+        let actual = HayagrivaWriter::write(&parse(input));
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn decode_latex() {
+        // given
+        let input = r#"@misc{mueller,
+            author = {M{\"u}ller, J{\"o}rg},
+            title = {Caf{\'e} \& Bar},
+            url = {https://example.com/caf\_e},
+        }"#;
+        let expected = "mueller:
+  type: misc
+  author:
+  - Müller, Jörg
+  title: Café & Bar
+  url: https://example.com/caf\\_e
+";
+
+        // when
+        let actual = HayagrivaWriter::write(&parse(input));
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    fn parse(input: &str) -> Vec<Entry> {
+        Parser::new(Tokenizer::new(Box::new(Cursor::new(s!(input)))))
+            .parse()
+            .unwrap()
+    }
+}
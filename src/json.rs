@@ -0,0 +1,537 @@
+use crate::date::Date;
+use crate::entry::{Entry, Parser};
+use crate::entry_field::EntryField;
+use crate::entry_type::EntryType;
+use crate::literal_list::LiteralList;
+use crate::pages::Pages;
+use crate::person::Person;
+use crate::s;
+use crate::strings::protect_list_item;
+use crate::year::Year;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Bib(La)TeX entries, as written by `bibtex-parser --format json`.
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(title = "bibtex-parser entries")]
+struct Output {
+    /// The version of this schema, raised on incompatible changes.
+    version: u32,
+    entries: Vec<OutputEntry>,
+}
+
+/// An entry, e.g. `@article{key, ...}`.
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct OutputEntry {
+    /// The citation key.
+    key: String,
+    /// The lower-case entry type, e.g. `article`.
+    r#type: String,
+    /// The key of the `@set` entry listing this entry in its `entryset`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    set: Option<String>,
+    /// The fields keyed by their lower-case name, with the first of repeated fields kept.
+    fields: BTreeMap<String, FieldValue>,
+    /// The number of pages covered by `pages`, unless any of them cannot be counted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pagecount: Option<u32>,
+}
+
+/// A field value, shaped by the field it belongs to.
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum FieldValue {
+    /// Text, e.g. of `title` or `doi`, and fields without a more specific shape.
+    Text(String),
+    /// Numbers, i.e. of `pagetotal` and `sortyear`.
+    Number(i64),
+    /// Literal lists, e.g. `publisher`, keywords and lists of entry keys, e.g. `ids`.
+    /// Truncated literal lists end in `others`.
+    Texts(Vec<String>),
+    /// Name lists, e.g. `author`.
+    Names(Vec<Name>),
+    /// Page ranges, i.e. of `pages`.
+    Pages(Vec<PageRange>),
+    /// Dates, e.g. of `date` or `urldate`.
+    Date(DateValue),
+    /// The `year`.
+    Year(YearValue),
+}
+
+/// A name in its BibTeX parts, e.g. `van Beethoven, Ludwig`. Names written as a single
+/// group, e.g. `{Barnes and Noble}`, only have a `last` part.
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct Name {
+    /// The first and middle names, with initials ending in a dot.
+    first: Option<String>,
+    /// The lower-case words leading the last name, e.g. `van`.
+    von: Option<String>,
+    last: String,
+    /// The suffix, e.g. `Jr.`.
+    jr: Option<String>,
+}
+
+/// A date as ISO 8601 text and its parts. Ranges, e.g. `2004/2006`, have the parts of
+/// their start and their end in `end`, which open-ended ranges, e.g. `2004/`, leave out.
+/// A month alone is written as `--MM`.
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct DateValue {
+    iso: String,
+    year: Option<i32>,
+    month: Option<u8>,
+    day: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<Box<DateValue>>,
+}
+
+/// A year as written, e.g. `2004a` or `n.d.`, and its number, if it has one.
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct YearValue {
+    text: String,
+    year: Option<i32>,
+}
+
+/// A page or page range as written, e.g. `97--111` or `97ff.`, its numeric bounds and the
+/// number of pages it covers. Open ranges have no `end` and no `count`.
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct PageRange {
+    text: String,
+    start: Option<u32>,
+    end: Option<u32>,
+    count: Option<u32>,
+}
+
+/// Writes entries as JSON in a flat, versioned shape described by a JSON Schema.
+pub(crate) struct JsonWriter;
+
+impl JsonWriter {
+    const VERSION: u32 = 1;
+
+    pub(crate) fn write(entries: &[Entry]) -> String {
+        let output = Output {
+            version: Self::VERSION,
+            entries: entries.iter().map(Self::entry).collect(),
+        };
+        serde_json::to_string(&output).unwrap()
+    }
+
+    /// The JSON Schema of the written JSON, as shipped in `schema/entries.schema.json`.
+    pub(crate) fn schema() -> String {
+        format!(
+            "{}\n",
+            serde_json::to_string_pretty(&schemars::schema_for!(Output)).unwrap()
+        )
+    }
+
+    fn entry(entry: &Entry) -> OutputEntry {
+        let mut fields = BTreeMap::new();
+        for (name, value) in entry.fields().iter().flat_map(Self::values) {
+            fields.entry(name).or_insert(value);
+        }
+        let pagecount = entry.fields().iter().find_map(|field| match field {
+            EntryField::Pages(pages) => Pages::total_page_count(pages),
+            _ => None,
+        });
+        OutputEntry {
+            key: s!(entry.symbol()),
+            r#type: entry.entry_type().to_string(),
+            set: entry.set().map(String::from),
+            fields,
+            pagecount,
+        }
+    }
+
+    fn values(field: &EntryField) -> Vec<(String, FieldValue)> {
+        let value = match field {
+            EntryField::Annotator(people)
+            | EntryField::Author(people)
+            | EntryField::BookAuthor(people)
+            | EntryField::Commentator(people)
+            | EntryField::Editor(people)
+            | EntryField::Holder(people)
+            | EntryField::NameA(people)
+            | EntryField::NameB(people)
+            | EntryField::NameC(people)
+            | EntryField::SortName(people)
+            | EntryField::Translator(people) => {
+                FieldValue::Names(people.iter().map(Self::name).collect())
+            }
+            EntryField::Address(list)
+            | EntryField::Institution(list)
+            | EntryField::Language(list)
+            | EntryField::ListA(list)
+            | EntryField::ListB(list)
+            | EntryField::ListC(list)
+            | EntryField::ListD(list)
+            | EntryField::ListE(list)
+            | EntryField::ListF(list)
+            | EntryField::Location(list)
+            | EntryField::Organization(list)
+            | EntryField::OrigLocation(list)
+            | EntryField::OrigPublisher(list)
+            | EntryField::Publisher(list)
+            | EntryField::School(list) => FieldValue::Texts(Self::list_items(list)),
+            EntryField::Groups(keywords) | EntryField::Keywords(keywords) => {
+                FieldValue::Texts(keywords.items().clone())
+            }
+            EntryField::EntrySet(keys)
+            | EntryField::Ids(keys)
+            | EntryField::Related(keys)
+            | EntryField::RelatedOptions(keys)
+            | EntryField::XData(keys) => FieldValue::Texts(keys.clone()),
+            EntryField::Date(date)
+            | EntryField::EventDate(date)
+            | EntryField::EventEndDate(date)
+            | EntryField::Month(date)
+            | EntryField::OrigDate(date)
+            | EntryField::UrlDate(date) => FieldValue::Date(Self::date(date)),
+            EntryField::Pages(pages) => FieldValue::Pages(pages.iter().map(Self::pages).collect()),
+            EntryField::PageTotal(n) => FieldValue::Number(i64::from(*n)),
+            EntryField::SortYear(year) => FieldValue::Number(i64::from(*year)),
+            EntryField::Year(year) => FieldValue::Year(Self::year(year)),
+            field => {
+                return field
+                    .bib_fields()
+                    .into_iter()
+                    .map(|(name, value)| (name, FieldValue::Text(value)))
+                    .collect()
+            }
+        };
+        vec![(field.name(), value)]
+    }
+
+    fn name(person: &Person) -> Name {
+        let (von, last) = match person {
+            Person::Literal(name) => (None, name.clone()),
+            person => person.von_and_last(),
+        };
+        Name {
+            first: person.given_names(),
+            von,
+            last,
+            jr: person.suffix().map(String::from),
+        }
+    }
+
+    fn list_items(list: &LiteralList) -> Vec<String> {
+        let mut items = list.items().clone();
+        if list.and_others() {
+            items.push(s!("others"));
+        }
+        items
+    }
+
+    fn date(date: &Date) -> DateValue {
+        let (start, end) = match date {
+            Date::Range(start, end) => (start.as_ref(), end.as_deref()),
+            date => (date, None),
+        };
+        let (year, month, day) = match start {
+            Date::YearMonthDay(y, m, d) => (Some(*y), Some(*m), Some(*d)),
+            Date::YearMonth(y, m) => (Some(*y), Some(*m), None),
+            Date::Year(y) => (Some(*y), None, None),
+            Date::Month(m) => (None, Some(*m), None),
+            Date::Range(_, _) => (None, None, None),
+        };
+        let iso = match date {
+            Date::Month(m) => format!("--{:02}", m),
+            date => date.to_string(),
+        };
+        DateValue {
+            iso,
+            year,
+            month,
+            day,
+            end: end.map(|end| Box::new(Self::date(end))),
+        }
+    }
+
+    fn year(year: &Year) -> YearValue {
+        YearValue {
+            text: year.to_string(),
+            year: year.value(),
+        }
+    }
+
+    fn pages(pages: &Pages) -> PageRange {
+        let (start, end) = match pages {
+            Pages::Single(page) => (page.value(), page.value()),
+            Pages::Range(start, end) => (start.value(), end.value()),
+            Pages::OpenRange(start) | Pages::Following(start) => (start.value(), None),
+        };
+        PageRange {
+            text: pages.to_string(),
+            start,
+            end,
+            count: pages.page_count(),
+        }
+    }
+}
+
+/// Reads entries from the JSON written by `JsonWriter`, or from the unversioned JSON of the
+/// serialized model, as written with `--legacy-json`.
+pub(crate) struct JsonReader;
+
+impl JsonReader {
+    /// Fields whose list values are separated by commas rather than `and`.
+    const COMMA_SEPARATED_LISTS: [&'static str; 7] = [
+        "entryset",
+        "groups",
+        "ids",
+        "keywords",
+        "related",
+        "relatedoptions",
+        "xdata",
+    ];
+
+    pub(crate) fn read(input: &str) -> Result<Vec<Entry>, String> {
+        let json: Value = serde_json::from_str(input).map_err(|err| err.to_string())?;
+        if json.is_array() {
+            return serde_json::from_value(json).map_err(|err| err.to_string());
+        }
+        let version = json.get("version").and_then(Value::as_u64);
+        if version != Some(u64::from(JsonWriter::VERSION)) {
+            return Err(format!("Unsupported JSON version {:?}", version));
+        }
+        let output: Output = serde_json::from_value(json).map_err(|err| err.to_string())?;
+        output.entries.iter().map(Self::entry).collect()
+    }
+
+    fn entry(entry: &OutputEntry) -> Result<Entry, String> {
+        let fields = entry
+            .fields
+            .iter()
+            .map(|(name, value)| {
+                EntryField::from_field_name_and_text(name, &Self::bib_value(name, value))
+                    .map_err(|err| format!("Entry '{}': {}", entry.key, err))
+            })
+            .collect::<Result<Vec<EntryField>, String>>()?;
+        Ok(Entry::new(
+            EntryType::from_str(&entry.r#type),
+            entry.key.clone(),
+            Parser::combine_eprint(fields),
+        )
+        .with_set(entry.set.clone()))
+    }
+
+    /// The value as written in a `.bib` file, to be parsed like one.
+    fn bib_value(name: &str, value: &FieldValue) -> String {
+        match value {
+            FieldValue::Text(text) => text.clone(),
+            FieldValue::Number(n) => n.to_string(),
+            FieldValue::Texts(items) if Self::COMMA_SEPARATED_LISTS.contains(&name) => {
+                items.join(", ")
+            }
+            FieldValue::Texts(items) => items
+                .iter()
+                .map(|item| protect_list_item(item))
+                .collect::<Vec<String>>()
+                .join(" and "),
+            FieldValue::Names(names) => {
+                Person::people_to_bib(&names.iter().map(Self::person).collect::<Vec<Person>>())
+            }
+            FieldValue::Pages(pages) => pages
+                .iter()
+                .map(|pages| pages.text.as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
+            FieldValue::Date(DateValue {
+                year: None,
+                month: Some(month),
+                end: None,
+                ..
+            }) => month.to_string(),
+            FieldValue::Date(date) => date.iso.clone(),
+            FieldValue::Year(year) => year.text.clone(),
+        }
+    }
+
+    fn person(name: &Name) -> Person {
+        let last_name = match &name.von {
+            Some(von) => format!("{} {}", von, name.last),
+            None => name.last.clone(),
+        };
+        match &name.first {
+            Some(first) => Person::from_name_parts(&last_name, first, name.jr.as_deref()),
+            None => Person::Literal(last_name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod json_test {
+    use super::*;
+    use crate::entry::ParserOptions;
+    use crate::tokenizer::Tokenizer;
+    use serde_json::{json, Value};
+    use std::io::Cursor;
+
+    #[test]
+    fn write_entries() {
+        // given
+        let input = r#"
+            @article{knuth-1984,
+                author = {Knuth, Donald E. and van Beethoven, Jr., Ludwig and {Barnes and Noble}},
+                title = {Literate Programming},
+                publisher = {ACM and others},
+                keywords = {tex, literate},
+                pages = {97--111, xii},
+                pagetotal = {15},
+                date = {1984-05/1985},
+                month = {jan},
+                eprint = {6350001}, eprinttype = {pubmed},
+                year = {1984a},
+                title = {Repeated},
+            }"#;
+        let expected = json!({
+            "version": 1,
+            "entries": [{
+                "key": "knuth-1984",
+                "type": "article",
+                "fields": {
+                    "author": [
+                        { "first": "Donald E.", "von": null, "last": "Knuth", "jr": null },
+                        { "first": "Ludwig", "von": "van", "last": "Beethoven", "jr": "Jr." },
+                        { "first": null, "von": null, "last": "Barnes and Noble", "jr": null },
+                    ],
+                    "title": "Literate Programming",
+                    "publisher": ["ACM", "others"],
+                    "keywords": ["tex", "literate"],
+                    "pages": [
+                        { "text": "97--111", "start": 97, "end": 111, "count": 15 },
+                        { "text": "xii", "start": 12, "end": 12, "count": 1 },
+                    ],
+                    "pagetotal": 15,
+                    "date": {
+                        "iso": "1984-05/1985", "year": 1984, "month": 5, "day": null,
+                        "end": { "iso": "1985", "year": 1985, "month": null, "day": null },
+                    },
+                    "month": { "iso": "--01", "year": null, "month": 1, "day": null },
+                    "eprint": "6350001",
+                    "eprinttype": "pubmed",
+                    "year": { "text": "1984a", "year": 1984 },
+                },
+                "pagecount": 16,
+            }],
+        });
+
+        // when
+        let actual: Value = serde_json::from_str(&JsonWriter::write(&parse(input))).unwrap();
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn write_decoded_text() {
+        // given
+        let input = r#"@book{xp, title = {The {XP} Way \& More}, author = {M{\"u}ller, Hans}}"#;
+        let expected = json!({
+            "title": "The XP Way & More",
+            "author": [{ "first": "Hans", "von": null, "last": "Müller", "jr": null }],
+        });
+
+        // when
+        let actual: Value = serde_json::from_str(&JsonWriter::write(&parse(input))).unwrap();
+
+        // then
+        assert_eq!(actual["entries"][0]["fields"], expected);
+    }
+
+    #[test]
+    fn shipped_schema_is_up_to_date() {
+        // given
+        let shipped = include_str!("../schema/entries.schema.json");
+
+        // when
+        let actual = JsonWriter::schema();
+
+        // then
+        assert_eq!(
+            actual, shipped,
+            "regenerate it with `cargo run -- json-schema > schema/entries.schema.json`"
+        );
+    }
+
+    #[test]
+    fn read_round_trip() {
+        // given
+        let written = JsonWriter::write(&parse(ROUND_TRIP_INPUT));
+
+        // when
+        let actual = JsonReader::read(&written).map(|entries| JsonWriter::write(&entries));
+
+        // then
+        assert_eq!(actual, Ok(written));
+    }
+
+    #[test]
+    fn read_legacy_round_trip() {
+        // given
+        let expected = parse(ROUND_TRIP_INPUT);
+        let written = serde_json::to_string(&expected).unwrap();
+
+        // when
+        let actual = JsonReader::read(&written);
+
+        // then
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn read_unsupported_version() {
+        // given
+        [
+            (
+                r#"{"version": 2, "entries": []}"#,
+                "Unsupported JSON version Some(2)",
+            ),
+            (r#"{"entries": []}"#, "Unsupported JSON version None"),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = JsonReader::read(input);
+
+            // then
+            assert_eq!(actual, Err(s!(*expected)));
+        });
+    }
+
+    const ROUND_TRIP_INPUT: &str = r#"
+        @set{set, entryset = {knuth-1984, beck-2004}}
+        @article{knuth-1984,
+            author = {Knuth, Donald E. and van Beethoven, Jr., Ludwig and {Barnes and Noble}},
+            title = {Literate Programming},
+            publisher = {{Barnes and Noble} and ACM and others},
+            keywords = {tex, literate},
+            ids = {knuth, literate},
+            pages = {97--111, 120ff.},
+            pagetotal = {15},
+            date = {1984-05/1985},
+            month = {may},
+            urldate = {2021-01-02/},
+            eprint = {2101.00001v2}, eprinttype = {arxiv}, eprintclass = {cs.PL},
+            doi = {10.1093/comjnl/27.2.97}, isbn = {0-201-61622-X},
+            year = {ca. 1984},
+            options = {skipbib, useprefix=false},
+            pubstate = {inpress},
+            custom = {Unknown},
+        }
+        @book{beck-2004,
+            editor = {Beck, Kent}, edition = {2}, gender = {sm},
+            title = {Extreme Programming}, year = {2004a}, note = {xdata=knuth-1984-title},
+        }"#;
+
+    fn parse(input: &str) -> Vec<Entry> {
+        let options = ParserOptions {
+            resolve_sets: true,
+            ..ParserOptions::default()
+        };
+        Parser::with_options(Tokenizer::new(Box::new(Cursor::new(s!(input)))), options)
+            .parse()
+            .unwrap()
+    }
+}
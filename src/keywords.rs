@@ -0,0 +1,149 @@
+use crate::s;
+use crate::strings::latex_to_unicode;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Keywords(Vec<String>);
+
+impl Keywords {
+    pub(crate) const DEFAULT_SEPARATORS: [char; 2] = [',', ';'];
+
+    /// The keywords split on any of the `separators` outside braces, e.g. `{SAT, SMT}; BDD`,
+    /// and decoded from LaTeX, keeping the first of those differing only in case.
+    pub(crate) fn from_str(s: &str, separators: &[char]) -> Keywords {
+        let mut items: Vec<String> = vec![String::new()];
+        let mut depth = 0;
+        for c in s.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                c if depth == 0 && separators.contains(&c) => {
+                    items.push(String::new());
+                    continue;
+                }
+                _ => {}
+            }
+            if let Some(item) = items.last_mut() {
+                item.push(c);
+            }
+        }
+        let decoded: Vec<String> = items.iter().map(|item| latex_to_unicode(item)).collect();
+        Self::from_items(&decoded.iter().map(String::as_str).collect::<Vec<&str>>())
+    }
+
+    /// The keywords given as plain text, e.g. the repeated values of a tag in other formats.
+    pub(crate) fn from_items(items: &[&str]) -> Keywords {
+        let mut keywords: Vec<String> = vec![];
+        items
+            .iter()
+            .map(|it| it.trim())
+            .filter(|it| !it.is_empty())
+            .for_each(|keyword| {
+                let lowercase = keyword.to_lowercase();
+                if !keywords.iter().any(|it| it.to_lowercase() == lowercase) {
+                    keywords.push(s!(keyword));
+                }
+            });
+        Keywords(keywords)
+    }
+
+    /// Case-insensitive lookup of a keyword.
+    pub(crate) fn contains(&self, keyword: &str) -> bool {
+        let keyword = keyword.trim().to_lowercase();
+        self.0.iter().any(|it| it.to_lowercase() == keyword)
+    }
+
+    pub(crate) fn items(&self) -> &Vec<String> {
+        &self.0
+    }
+}
+
+/// The keywords joined with `, `, with those containing a separator braced,
+/// e.g. `{model checking, SAT}, verification`.
+impl std::fmt::Display for Keywords {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let separator = format!("{} ", Self::DEFAULT_SEPARATORS[0]);
+        let keywords: Vec<String> = self
+            .0
+            .iter()
+            .map(|keyword| match keyword.contains(Self::DEFAULT_SEPARATORS) {
+                true => format!("{{{}}}", keyword),
+                false => s!(keyword),
+            })
+            .collect();
+        write!(f, "{}", keywords.join(&separator))
+    }
+}
+
+#[cfg(test)]
+mod keywords_test {
+    use super::*;
+
+    #[test]
+    fn parse_from_str() {
+        // given
+        [
+            (
+                "verification; model checking, SAT",
+                vec!["verification", "model checking", "SAT"],
+            ),
+            ("SAT, SAT,, SMT ;", vec!["SAT", "SMT"]),
+            ("SAT, sat; Sat", vec!["SAT"]),
+            (
+                r"{model checking, SAT}; Caf{\'e} \& Bar",
+                vec!["model checking, SAT", "Café & Bar"],
+            ),
+            ("", vec![]),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Keywords::from_str(input, &Keywords::DEFAULT_SEPARATORS);
+
+            // then
+            assert_eq!(
+                actual,
+                Keywords(expected.iter().map(|it| s!(*it)).collect())
+            );
+        });
+    }
+
+    #[test]
+    fn parse_from_str_with_custom_separators() {
+        // given
+        let input = "model checking, SAT / SMT";
+
+        // when
+        let actual = Keywords::from_str(input, &['/']);
+
+        // then
+        assert_eq!(actual, Keywords(vec![s!("model checking, SAT"), s!("SMT")]));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        // given
+        let keywords = Keywords::from_str("model checking, SAT; verification", &[';']);
+
+        // when
+        let actual = keywords.to_string();
+
+        // then
+        assert_eq!(actual, "{model checking, SAT}, verification");
+        assert_eq!(
+            Keywords::from_str(&actual, &Keywords::DEFAULT_SEPARATORS),
+            keywords
+        );
+    }
+
+    #[test]
+    fn contains_case_insensitive() {
+        // given
+        let keywords = Keywords::from_str("Model Checking; SAT", &Keywords::DEFAULT_SEPARATORS);
+
+        // then
+        assert!(keywords.contains("model checking"));
+        assert!(keywords.contains(" sat "));
+        assert!(!keywords.contains("SMT"));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::s;
+use crate::strings::{latex_to_unicode, protect_list_item, split_and_list};
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct LiteralList {
+    items: Vec<String>,
+    and_others: bool,
+}
+
+impl LiteralList {
+    const OTHERS: &'static str = "others";
+
+    /// The items split on `and` outside braces and decoded from LaTeX, e.g. `{ACM} Press`
+    /// to `ACM Press`. Written back to BibTeX unchanged, such a list keeps its braces.
+    pub(crate) fn from_str(s: &str) -> LiteralList {
+        let mut raw_items = split_and_list(s);
+        let and_others = raw_items.last().is_some_and(|it| it == Self::OTHERS);
+        if and_others {
+            raw_items.pop();
+        }
+        LiteralList {
+            items: raw_items.iter().map(|it| latex_to_unicode(it)).collect(),
+            and_others,
+        }
+    }
+
+    /// A list of the given items, e.g. the repeated values of a tag in other formats.
+    pub(crate) fn from_items(items: &[&str]) -> LiteralList {
+        LiteralList {
+            items: items.iter().map(|item| s!(*item)).collect(),
+            and_others: false,
+        }
+    }
+
+    pub(crate) fn items(&self) -> &Vec<String> {
+        &self.items
+    }
+
+    pub(crate) fn and_others(&self) -> bool {
+        self.and_others
+    }
+
+    /// The `n`-th item, counting from zero, as a list of its own.
+    pub(crate) fn nth(&self, n: usize) -> Option<LiteralList> {
+        self.items.get(n).map(|item| LiteralList {
+            items: vec![item.clone()],
+            and_others: false,
+        })
+    }
+
+    /// The list as a single string, e.g. `Paris and Berlin and others`.
+    pub(crate) fn joined(&self) -> String {
+        let mut items = self.items.clone();
+        if self.and_others {
+            items.push(Self::OTHERS.to_string());
+        }
+        items.join(" and ")
+    }
+
+    /// The list as written in a `.bib` file, with items containing `and` braced.
+    pub(crate) fn to_bib(&self) -> String {
+        let mut items: Vec<String> = self
+            .items
+            .iter()
+            .map(|item| match item == Self::OTHERS {
+                true => format!("{{{}}}", item),
+                false => protect_list_item(item),
+            })
+            .collect();
+        if self.and_others {
+            items.push(Self::OTHERS.to_string());
+        }
+        items.join(" and ")
+    }
+}
+
+impl std::fmt::Display for LiteralList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.joined())
+    }
+}
+
+#[cfg(test)]
+mod literal_list_test {
+    use super::*;
+    use crate::s;
+
+    #[test]
+    fn parse_from_str() {
+        // given
+        [
+            (
+                "Addison-Wesley Professional",
+                LiteralList {
+                    items: vec![s!("Addison-Wesley Professional")],
+                    and_others: false,
+                },
+            ),
+            (
+                "{Barnes and Noble} and Penguin",
+                LiteralList {
+                    items: vec![s!("Barnes and Noble"), s!("Penguin")],
+                    and_others: false,
+                },
+            ),
+            (
+                "Paris and Berlin and others",
+                LiteralList {
+                    items: vec![s!("Paris"), s!("Berlin")],
+                    and_others: true,
+                },
+            ),
+            (
+                r"{ACM} Press and Caf{\'e} \& Co",
+                LiteralList {
+                    items: vec![s!("ACM Press"), s!("Café & Co")],
+                    and_others: false,
+                },
+            ),
+            (
+                "{others}",
+                LiteralList {
+                    items: vec![s!("others")],
+                    and_others: false,
+                },
+            ),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = LiteralList::from_str(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn joined() {
+        // given
+        let input = LiteralList::from_str("Paris and {Berlin} and others");
+
+        // when
+        let actual = input.joined();
+
+        // then
+        assert_eq!(actual, "Paris and Berlin and others");
+    }
+}
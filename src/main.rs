@@ -1,20 +1,50 @@
 use std::fs::File;
 use std::io::{stdin, stdout, BufReader, Read, Stdin, Write};
 
-use clap::Parser as ArgParser;
+use clap::{Args as ClapArgs, Parser as ArgParser, Subcommand, ValueEnum};
 
-use crate::entry::Parser;
+use crate::bib_writer::{BibWriter, ValueDelimiter, WriterOptions};
+use crate::csl_json::{CslJsonReader, CslJsonWriter, CslYamlWriter};
+use crate::endnote::{EndNoteXmlReader, EndNoteXmlWriter, ReferReader, ReferWriter};
+use crate::entry::{Entry, Parser, ParserOptions};
+use crate::entry_field::FieldNaming;
+use crate::entry_type::TypeNaming;
+use crate::formatter::{EntryOrder, FieldOrder, FormatOptions, Formatter};
+use crate::hayagriva::HayagrivaWriter;
+use crate::json::{JsonReader, JsonWriter};
+use crate::mods::{ModsReader, ModsWriter};
+use crate::ris::{RisReader, RisWriter};
+use crate::standard_number::IsbnForm;
 use crate::tokenizer::Tokenizer;
 
+mod bib_writer;
+mod crossref;
+mod csl_json;
 mod date;
+mod doi;
 mod edition;
+mod endnote;
 mod entry;
 mod entry_field;
+mod entry_set;
 mod entry_type;
+mod eprint;
+mod formatter;
+mod gender;
+mod hayagriva;
+mod json;
+mod keywords;
+mod literal_list;
+mod mods;
 mod pages;
 mod person;
+mod pub_state;
+mod ris;
+mod standard_number;
 mod strings;
 mod tokenizer;
+mod xdata;
+mod year;
 
 #[derive(ArgParser, Debug)]
 #[command(name = "BibTeX Parser")]
@@ -26,36 +56,334 @@ mod tokenizer;
 for parsing Bib(La)TeX entries and converting them to JSON.
 Without any arguments it takes stdin and outputs to stdout."#
 )]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(help = "Input file. stdin if not set.")]
     infile: Option<String>,
 
     #[arg(short, help = "Output file. stdout if not set.")]
     outfile: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = InputFormat::Bibtex, help = "Input format.")]
+    from: InputFormat,
+
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json, help = "Output format.")]
+    format: OutputFormat,
+
+    #[arg(
+        long,
+        help = "Write --format json as the serialized model, without the versioned schema."
+    )]
+    legacy_json: bool,
+
+    #[arg(
+        long,
+        help = "Detect DOIs embedded in url, note and howpublished fields."
+    )]
+    detect_doi: bool,
+
+    #[arg(
+        long,
+        help = "Rename BibTeX fields to their biblatex equivalents, e.g. address to location."
+    )]
+    normalize_fields: bool,
+
+    #[arg(
+        long,
+        help = "Resolve entry type aliases, e.g. phdthesis to thesis with type = phdthesis."
+    )]
+    normalize_types: bool,
+
+    #[arg(
+        long = "keyword",
+        value_name = "KEYWORD",
+        help = "Output only entries tagged with the keyword. Can be repeated."
+    )]
+    keywords: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "CHARS",
+        default_value = ",;",
+        help = "Characters separating keywords in the keywords and groups fields."
+    )]
+    keyword_separators: String,
+
+    #[arg(
+        long,
+        help = "Merge the fields of the @xdata entries listed in xdata fields."
+    )]
+    resolve_xdata: bool,
+
+    #[arg(
+        long,
+        help = "Leave @xdata entries out of the output. Implies --resolve-xdata."
+    )]
+    drop_xdata: bool,
+
+    #[arg(
+        long,
+        help = "Copy missing fields from the entry named in crossref, e.g. booktitle from proceedings."
+    )]
+    resolve_crossrefs: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Drop entries crossref'd by fewer than N entries. Implies --resolve-crossrefs."
+    )]
+    min_crossrefs: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Link entries to the @set entries listing them in entryset."
+    )]
+    resolve_sets: bool,
+
+    #[arg(
+        long = "isbn",
+        value_enum,
+        value_name = "FORM",
+        help = "Convert ISBNs to ISBN-10 or ISBN-13 where possible."
+    )]
+    isbn_form: Option<IsbnForm>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Rewrite .bib files in a canonical style.
+    Format(FormatArgs),
+    /// Print the JSON Schema of the --format json output.
+    JsonSchema,
+}
+
+#[derive(ClapArgs, Debug)]
+struct FormatArgs {
+    #[arg(help = "Files to format in place. stdin to stdout if not set.")]
+    files: Vec<String>,
+
+    #[arg(
+        long,
+        default_value_t = 2,
+        help = "Number of spaces to indent fields with."
+    )]
+    indent: usize,
+
+    #[arg(long, help = "Indent fields with a tab instead of spaces.")]
+    tabs: bool,
+
+    #[arg(long, help = "Align the = signs of the fields in an entry.")]
+    align: bool,
+
+    #[arg(long, value_enum, default_value_t = FieldOrder::Source, help = "Order of the fields.")]
+    field_order: FieldOrder,
+
+    #[arg(long, value_enum, default_value_t = EntryOrder::Source, help = "Order of the entries.")]
+    sort: EntryOrder,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ValueDelimiter::Braces,
+        help = "Preferred delimiters of field values."
+    )]
+    delimiter: ValueDelimiter,
+
+    #[arg(long, help = "Put a comma after the last field.")]
+    trailing_comma: bool,
+
+    #[arg(
+        long,
+        help = "Do not write, exit with 1 if any input is not formatted."
+    )]
+    check: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum InputFormat {
+    Bibtex,
+    CslJson,
+    Ris,
+    EndnoteXml,
+    #[value(alias = "enw")]
+    Refer,
+    Mods,
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum OutputFormat {
+    Json,
+    Bibtex,
+    CslJson,
+    CslYaml,
+    Ris,
+    EndnoteXml,
+    #[value(alias = "enw")]
+    Refer,
+    Mods,
+    Hayagriva,
 }
 
 fn main() {
     let args = Args::parse();
+    match args.command {
+        Some(Command::Format(format_args)) => return format(format_args),
+        Some(Command::JsonSchema) => return print!("{}", JsonWriter::schema()),
+        None => {}
+    }
+    let options = ParserOptions {
+        detect_doi: args.detect_doi,
+        field_naming: match args.normalize_fields {
+            true => FieldNaming::Biblatex,
+            false => FieldNaming::AsWritten,
+        },
+        type_naming: match args.normalize_types {
+            true => TypeNaming::Biblatex,
+            false => TypeNaming::AsWritten,
+        },
+        keyword_separators: args.keyword_separators.chars().collect(),
+        resolve_xdata: args.resolve_xdata || args.drop_xdata,
+        drop_xdata: args.drop_xdata,
+        resolve_crossrefs: args.resolve_crossrefs || args.min_crossrefs.is_some(),
+        min_crossrefs: args.min_crossrefs,
+        resolve_sets: args.resolve_sets,
+    };
 
-    let mut parser = args
-        .infile
-        .map(open_file_read)
-        .map(|file| file.unwrap())
-        .map(parser_for_file)
-        .unwrap_or(parser_for_stdin());
-
-    let entries = parser.parse().unwrap();
+    let entries = match args.from {
+        InputFormat::Bibtex => {
+            let name = args.infile.clone().unwrap_or(s!("stdin"));
+            let mut parser = match args.infile.map(open_file_read) {
+                Some(file) => parser_for_file(
+                    file.unwrap_or_else(|err| exit_with_error(&name, &err)),
+                    options,
+                ),
+                None => parser_for_stdin(options),
+            };
+            let entries = parser
+                .parse()
+                .unwrap_or_else(|err| exit_with_error(&name, &err));
+            parser
+                .warnings()
+                .iter()
+                .for_each(|warning| eprintln!("Warning: {}", warning));
+            entries
+        }
+        InputFormat::CslJson => read_entries(args.infile, CslJsonReader::read),
+        InputFormat::Ris => read_entries(args.infile, RisReader::read),
+        InputFormat::EndnoteXml => read_entries(args.infile, EndNoteXmlReader::read),
+        InputFormat::Refer => read_entries(args.infile, ReferReader::read),
+        InputFormat::Mods => read_entries(args.infile, ModsReader::read),
+        InputFormat::Json => read_entries(args.infile, JsonReader::read),
+    };
+    let entries = Entry::filter_by_keywords(entries, &args.keywords);
+    let entries = match args.isbn_form {
+        Some(form) => Entry::convert_isbns(entries, form),
+        None => entries,
+    };
 
-    let outfile = args.outfile.map(open_file_write).map(|file| file.unwrap());
+    let outfile = args.outfile.map(|name| {
+        open_file_write(name.clone()).unwrap_or_else(|err| exit_with_error(&name, &err))
+    });
 
     let mut writer: Box<dyn Write> = match outfile {
         Some(file) => Box::new(file),
         None => Box::new(stdout()),
     };
 
-    let json = serde_json::to_string(&entries).unwrap();
+    let output = match args.format {
+        OutputFormat::Json if args.legacy_json => serde_json::to_string(&entries).unwrap(),
+        OutputFormat::Json => JsonWriter::write(&entries),
+        OutputFormat::Bibtex => BibWriter::new().write(&entries),
+        OutputFormat::CslJson => CslJsonWriter::write(&entries),
+        OutputFormat::CslYaml => CslYamlWriter::write(&entries),
+        OutputFormat::Ris => RisWriter::write(&entries),
+        OutputFormat::EndnoteXml => EndNoteXmlWriter::write(&entries),
+        OutputFormat::Refer => ReferWriter::write(&entries),
+        OutputFormat::Mods => ModsWriter::write(&entries),
+        OutputFormat::Hayagriva => HayagrivaWriter::write(&entries),
+    };
+
+    write!(writer, "{}", output).unwrap();
+}
+
+fn format(args: FormatArgs) {
+    let formatter = Formatter::with_options(FormatOptions {
+        writer: WriterOptions {
+            indent: match args.tabs {
+                true => s!("\t"),
+                false => " ".repeat(args.indent),
+            },
+            align_equals: args.align,
+            trailing_comma: args.trailing_comma,
+            delimiter: args.delimiter,
+        },
+        field_order: args.field_order,
+        entry_order: args.sort,
+    });
+
+    if args.files.is_empty() {
+        let mut input = String::new();
+        open_stdin().read_to_string(&mut input).unwrap();
+        match args.check {
+            true => match formatter.is_formatted(&input) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(err) => exit_with_error("stdin", &err),
+            },
+            false => match formatter.format(&input) {
+                Ok(output) => print!("{}", output),
+                Err(err) => exit_with_error("stdin", &err),
+            },
+        }
+        return;
+    }
+
+    // Nothing is written unless every file parses.
+    let mut formatted: Vec<(String, String, String)> = vec![];
+    let mut failed = false;
+    for filename in args.files {
+        let result = std::fs::read_to_string(&filename)
+            .map_err(|err| err.to_string())
+            .and_then(|input| formatter.format(&input).map(|output| (input, output)));
+        match result {
+            Ok((input, output)) => formatted.push((filename, input, output)),
+            Err(err) => {
+                eprintln!("{}: {}", filename, err);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+
+    let mut unformatted = false;
+    for (filename, input, output) in formatted {
+        if output == input {
+            continue;
+        }
+        match args.check {
+            true => {
+                eprintln!("Not formatted: {}", filename);
+                unformatted = true;
+            }
+            false => write!(open_file_write(filename).unwrap(), "{}", output).unwrap(),
+        }
+    }
+
+    if unformatted {
+        std::process::exit(1);
+    }
+}
 
-    write!(writer, "{}", json).unwrap();
+/// Reports the error with the input it occurred in, e.g. `refs.bib: ...`, and exits with 1.
+fn exit_with_error(input: &str, err: &str) -> ! {
+    eprintln!("{}: {}", input, err);
+    std::process::exit(1);
 }
 
 fn open_file_write(filename: String) -> Result<File, String> {
@@ -63,6 +391,7 @@ fn open_file_write(filename: String) -> Result<File, String> {
         .read(false)
         .write(true)
         .create(true)
+        .truncate(true)
         .open(filename)
         .map_err(|err| err.to_string())
 }
@@ -71,21 +400,46 @@ fn open_stdin() -> BufReader<Stdin> {
     BufReader::new(stdin())
 }
 
+/// Reads the entries of a format other than BibTeX, exiting with the error if they are invalid.
+fn read_entries(
+    infile: Option<String>,
+    read: fn(&str) -> Result<Vec<Entry>, String>,
+) -> Vec<Entry> {
+    let name = infile.clone().unwrap_or(s!("stdin"));
+    read(&read_input(infile)).unwrap_or_else(|err| exit_with_error(&name, &err))
+}
+
+fn read_input(infile: Option<String>) -> String {
+    let name = infile.clone().unwrap_or(s!("stdin"));
+    let mut input = String::new();
+    match infile.map(open_file_read) {
+        Some(file) => file.and_then(|mut file| {
+            file.read_to_string(&mut input)
+                .map_err(|err| err.to_string())
+        }),
+        None => open_stdin()
+            .read_to_string(&mut input)
+            .map_err(|err| err.to_string()),
+    }
+    .unwrap_or_else(|err| exit_with_error(&name, &err));
+    input
+}
+
 fn open_file_read(filename: String) -> Result<File, String> {
     File::open(filename).map_err(|err| err.to_string())
 }
 
-fn parser_for_file(file: File) -> Parser {
+fn parser_for_file(file: File, options: ParserOptions) -> Parser {
     let reader = Box::new(BufReader::new(file));
-    parser_for_reader(reader)
+    parser_for_reader(reader, options)
 }
 
-fn parser_for_stdin() -> Parser {
+fn parser_for_stdin(options: ParserOptions) -> Parser {
     let reader = Box::new(open_stdin());
-    parser_for_reader(reader)
+    parser_for_reader(reader, options)
 }
 
-fn parser_for_reader(reader: Box<dyn Read>) -> Parser {
+fn parser_for_reader(reader: Box<dyn Read>, options: ParserOptions) -> Parser {
     let tokenizer = Tokenizer::new(reader);
-    Parser::new(tokenizer)
+    Parser::with_options(tokenizer, options)
 }
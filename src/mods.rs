@@ -0,0 +1,1279 @@
+use crate::date::Date;
+use crate::entry::Entry;
+use crate::entry_field::EntryField;
+use crate::entry_type::EntryType;
+use crate::keywords::Keywords;
+use crate::literal_list::LiteralList;
+use crate::pages::Pages;
+use crate::person::Person;
+use crate::s;
+use crate::standard_number::{Isbn, Issn};
+use crate::strings::escape_xml;
+use crate::year::Year;
+use roxmltree::Node;
+
+const NAMESPACE: &str = "http://www.loc.gov/mods/v3";
+
+/// An XML element with either text or child elements, written indented.
+struct Element {
+    name: &'static str,
+    attributes: Vec<(&'static str, String)>,
+    text: Option<String>,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn new(name: &'static str) -> Element {
+        Element {
+            name,
+            attributes: vec![],
+            text: None,
+            children: vec![],
+        }
+    }
+
+    fn text(name: &'static str, text: &str) -> Element {
+        Element {
+            text: Some(s!(text)),
+            ..Self::new(name)
+        }
+    }
+
+    fn with_attribute(mut self, name: &'static str, value: &str) -> Element {
+        self.attributes.push((name, s!(value)));
+        self
+    }
+
+    fn with_children(mut self, children: Vec<Element>) -> Element {
+        self.children.extend(children);
+        self
+    }
+
+    fn write(&self, depth: usize, xml: &mut String) {
+        let indent = "  ".repeat(depth);
+        let attributes: String = self
+            .attributes
+            .iter()
+            .map(|(name, value)| format!(" {}=\"{}\"", name, escape_xml(value)))
+            .collect();
+        xml.push_str(&format!("{}<{}{}", indent, self.name, attributes));
+        match (&self.text, self.children.is_empty()) {
+            (Some(text), _) => xml.push_str(&format!(">{}</{}>\n", escape_xml(text), self.name)),
+            (None, true) => xml.push_str("/>\n"),
+            (None, false) => {
+                xml.push_str(">\n");
+                self.children
+                    .iter()
+                    .for_each(|child| child.write(depth + 1, xml));
+                xml.push_str(&format!("{}</{}>\n", indent, self.name));
+            }
+        }
+    }
+}
+
+/// Where the element for a field goes: the record itself, its `originInfo`, its `part`,
+/// or the `host` item the record is part of, e.g. the journal of an article, and its `originInfo`.
+#[derive(PartialEq, Clone, Copy)]
+enum Target {
+    Item,
+    Origin,
+    Part,
+    Host,
+    HostOrigin,
+}
+
+/// Writes entries as a MODS collection, as ingested by institutional repositories.
+/// Articles and the parts of books and proceedings get a `host` item holding the
+/// journal or book, its publication details and their place in it.
+pub(crate) struct ModsWriter;
+
+impl ModsWriter {
+    /// The order elements are written in, that of the MODS schema documentation.
+    const ELEMENT_ORDER: [&'static str; 19] = [
+        "titleInfo",
+        "name",
+        "typeOfResource",
+        "genre",
+        "originInfo",
+        "place",
+        "publisher",
+        "dateIssued",
+        "edition",
+        "language",
+        "abstract",
+        "note",
+        "subject",
+        "relatedItem",
+        "identifier",
+        "location",
+        "part",
+        "detail",
+        "extent",
+    ];
+
+    pub(crate) fn write(entries: &[Entry]) -> String {
+        let mut xml = s!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        Element::new("modsCollection")
+            .with_attribute("xmlns", NAMESPACE)
+            .with_children(entries.iter().map(Self::mods).collect())
+            .write(0, &mut xml);
+        xml
+    }
+
+    fn mods(entry: &Entry) -> Element {
+        let has_host = Self::has_host(entry.entry_type());
+        let mut targets: Vec<(Target, Element)> = entry
+            .fields()
+            .iter()
+            .flat_map(|field| Self::elements(entry, field, has_host))
+            .collect();
+        targets.push((Target::Item, Self::type_of_resource(entry.entry_type())));
+        targets
+            .extend(Self::genre(entry).map(|genre| (Target::Item, Element::text("genre", genre))));
+        targets.push((
+            Target::Item,
+            Element::text("identifier", entry.symbol()).with_attribute("type", "citekey"),
+        ));
+        let mut take = |target: Target| -> Vec<Element> {
+            let (taken, rest) = std::mem::take(&mut targets)
+                .into_iter()
+                .partition(|(it, _)| *it == target);
+            targets = rest;
+            taken.into_iter().map(|(_, element)| element).collect()
+        };
+        let origin = Self::container("originInfo", take(Target::Origin));
+        let host_origin = Self::container("originInfo", take(Target::HostOrigin));
+        let part = Self::container("part", take(Target::Part));
+        let mut host = take(Target::Host);
+        let mut item = take(Target::Item);
+        item.extend(origin);
+        match has_host {
+            true => {
+                host.extend(host_origin);
+                host.extend(part);
+                item.push(
+                    Element::new("relatedItem")
+                        .with_attribute("type", "host")
+                        .with_children(Self::sorted(host)),
+                );
+            }
+            false => item.extend(part),
+        }
+        Element::new("mods")
+            .with_attribute("version", "3.8")
+            .with_children(Self::sorted(item))
+    }
+
+    fn has_host(entry_type: &EntryType) -> bool {
+        matches!(
+            entry_type,
+            EntryType::Article
+                | EntryType::InBook
+                | EntryType::BookInBook
+                | EntryType::SuppBook
+                | EntryType::InCollection
+                | EntryType::SuppCollection
+                | EntryType::InProceedings
+                | EntryType::Conference
+                | EntryType::InReference
+        )
+    }
+
+    fn elements(entry: &Entry, field: &EntryField, has_host: bool) -> Vec<(Target, Element)> {
+        let has = |f: fn(&EntryField) -> bool| entry.fields().iter().any(f);
+        let is_article = *entry.entry_type() == EntryType::Article;
+        let host_or = |target: Target| match (has_host, target) {
+            (true, Target::Origin) => Target::HostOrigin,
+            (true, _) => Target::Host,
+            (false, target) => target,
+        };
+        let item = |element: Element| vec![(Target::Item, element)];
+        let host = |element: Element| match has_host {
+            true => vec![(Target::Host, element)],
+            false => vec![],
+        };
+        let items = |target: Target, name: &'static str, items: &[String]| {
+            items
+                .iter()
+                .map(|it| (target, Element::text(name, it)))
+                .collect()
+        };
+        let identifier = |kind: &str, value: String| {
+            vec![(
+                host_or(Target::Item),
+                Element::text("identifier", &value).with_attribute("type", kind),
+            )]
+        };
+        match field {
+            EntryField::Title(title) => item(Self::title_info(
+                title,
+                entry.fields().iter().find_map(|field| match field {
+                    EntryField::Subtitle(s) => Some(s),
+                    _ => None,
+                }),
+            )),
+            EntryField::ShortTitle(s) => {
+                item(Self::title_info(s, None).with_attribute("type", "abbreviated"))
+            }
+            EntryField::Author(people) => Self::names(Target::Item, people, "author"),
+            EntryField::Editor(people) => Self::names(
+                match is_article {
+                    true => Target::Item,
+                    false => host_or(Target::Item),
+                },
+                people,
+                "editor",
+            ),
+            EntryField::Translator(people) => Self::names(Target::Item, people, "translator"),
+            EntryField::BookAuthor(people) if has_host => {
+                Self::names(Target::Host, people, "author")
+            }
+            EntryField::JournalTitle(title) => host(Self::title_info(
+                title,
+                entry.fields().iter().find_map(|field| match field {
+                    EntryField::JournalSubtitle(s) => Some(s),
+                    _ => None,
+                }),
+            )),
+            EntryField::Journal(title) if !has(|f| matches!(f, EntryField::JournalTitle(_))) => {
+                host(Self::title_info(title, None))
+            }
+            EntryField::BookTitle(title) => host(Self::title_info(
+                title,
+                entry.fields().iter().find_map(|field| match field {
+                    EntryField::BookSubtitle(s) => Some(s),
+                    _ => None,
+                }),
+            )),
+            EntryField::ShortJournal(s) => {
+                host(Self::title_info(s, None).with_attribute("type", "abbreviated"))
+            }
+            EntryField::Series(s) => item(
+                Element::new("relatedItem")
+                    .with_attribute("type", "series")
+                    .with_children(vec![Self::title_info(s, None)]),
+            ),
+            EntryField::Date(date) => Self::date_issued(date)
+                .into_iter()
+                .map(|element| (Target::Origin, element))
+                .collect(),
+            EntryField::Year(year) if !has(|f| matches!(f, EntryField::Date(_))) => {
+                match entry.year_month() {
+                    Some(date) => Self::date_issued(&date)
+                        .into_iter()
+                        .map(|element| (Target::Origin, element))
+                        .collect(),
+                    None => {
+                        let date_issued = Element::text("dateIssued", &year.to_string());
+                        vec![(
+                            Target::Origin,
+                            match year {
+                                Year::Numeric(_) => {
+                                    date_issued.with_attribute("encoding", "iso8601")
+                                }
+                                _ => date_issued,
+                            },
+                        )]
+                    }
+                }
+            }
+            EntryField::Location(list) | EntryField::Address(list) => list
+                .items()
+                .iter()
+                .map(|place| {
+                    (
+                        host_or(Target::Origin),
+                        Element::new("place").with_children(vec![Element::text(
+                            "placeTerm",
+                            place,
+                        )
+                        .with_attribute("type", "text")]),
+                    )
+                })
+                .collect(),
+            EntryField::Publisher(list)
+            | EntryField::Institution(list)
+            | EntryField::School(list)
+            | EntryField::Organization(list) => {
+                items(host_or(Target::Origin), "publisher", list.items())
+            }
+            EntryField::Edition(edition) => {
+                vec![(
+                    host_or(Target::Origin),
+                    Element::text("edition", &edition.to_string()),
+                )]
+            }
+            EntryField::Volume(s) => vec![(Target::Part, Self::detail("volume", s))],
+            EntryField::Number(s) | EntryField::Issue(s) => vec![(
+                Target::Part,
+                Self::detail(
+                    match is_article {
+                        true => "issue",
+                        false => "number",
+                    },
+                    s,
+                ),
+            )],
+            EntryField::Pages(pages) => vec![(Target::Part, Self::extent(pages))],
+            EntryField::Language(list) => list
+                .items()
+                .iter()
+                .map(|language| {
+                    (
+                        Target::Item,
+                        Element::new("language").with_children(vec![Element::text(
+                            "languageTerm",
+                            language,
+                        )
+                        .with_attribute("type", "text")]),
+                    )
+                })
+                .collect(),
+            EntryField::Abstract(s) => item(Element::text("abstract", s)),
+            EntryField::Note(s) => item(Element::text("note", s)),
+            EntryField::Keywords(keywords) => keywords
+                .items()
+                .iter()
+                .map(|keyword| {
+                    (
+                        Target::Item,
+                        Element::new("subject")
+                            .with_children(vec![Element::text("topic", keyword)]),
+                    )
+                })
+                .collect(),
+            EntryField::Doi(doi) => vec![(
+                Target::Item,
+                Element::text("identifier", &doi.to_string()).with_attribute("type", "doi"),
+            )],
+            EntryField::Isbn(isbn) => identifier("isbn", isbn.to_string()),
+            EntryField::Issn(issn) => identifier("issn", issn.to_string()),
+            EntryField::Url(url) => {
+                let url = Element::text("url", url);
+                let url_date = entry.fields().iter().find_map(|field| match field {
+                    EntryField::UrlDate(date) => Some(date.to_string()),
+                    _ => None,
+                });
+                item(Element::new("location").with_children(vec![match url_date {
+                    Some(date) => url.with_attribute("dateLastAccessed", &date),
+                    None => url,
+                }]))
+            }
+            _ => vec![],
+        }
+    }
+
+    fn title_info(title: &str, subtitle: Option<&String>) -> Element {
+        let mut children = vec![Element::text("title", title)];
+        children.extend(subtitle.map(|subtitle| Element::text("subTitle", subtitle)));
+        Element::new("titleInfo").with_children(children)
+    }
+
+    fn names(target: Target, people: &[Person], role: &str) -> Vec<(Target, Element)> {
+        people
+            .iter()
+            .map(|person| (target, Self::name(person, role)))
+            .collect()
+    }
+
+    fn name(person: &Person, role: &str) -> Element {
+        let role = Element::new("role").with_children(vec![Element::text("roleTerm", role)
+            .with_attribute("type", "text")
+            .with_attribute("authority", "marcrelator")]);
+        let name = match person {
+            Person::Literal(name) => Element::new("name")
+                .with_attribute("type", "corporate")
+                .with_children(vec![Element::text("namePart", name)]),
+            person => {
+                let mut parts =
+                    vec![Element::text("namePart", person.last_name())
+                        .with_attribute("type", "family")];
+                parts.extend(person.given_names().map(|given| {
+                    Element::text("namePart", &given).with_attribute("type", "given")
+                }));
+                parts.extend(person.suffix().map(|suffix| {
+                    Element::text("namePart", suffix).with_attribute("type", "termsOfAddress")
+                }));
+                Element::new("name")
+                    .with_attribute("type", "personal")
+                    .with_children(parts)
+            }
+        };
+        name.with_children(vec![role])
+    }
+
+    /// The ISO 8601 `dateIssued`, ranges as a start and an end one.
+    fn date_issued(date: &Date) -> Vec<Element> {
+        let date_issued = |date: &Date| {
+            Element::text("dateIssued", &date.to_string()).with_attribute("encoding", "iso8601")
+        };
+        match date {
+            Date::Month(_) => vec![],
+            Date::Range(start, end) => {
+                let mut elements = vec![date_issued(start).with_attribute("point", "start")];
+                elements.extend(
+                    end.iter()
+                        .map(|end| date_issued(end).with_attribute("point", "end")),
+                );
+                elements
+            }
+            date => vec![date_issued(date)],
+        }
+    }
+
+    fn detail(kind: &str, number: &str) -> Element {
+        Element::new("detail")
+            .with_attribute("type", kind)
+            .with_children(vec![Element::text("number", number)])
+    }
+
+    fn extent(pages: &[Pages]) -> Element {
+        let extent = Element::new("extent").with_attribute("unit", "pages");
+        match pages {
+            [Pages::Range(start, end)] => extent.with_children(vec![
+                Element::text("start", &start.to_string()),
+                Element::text("end", &end.to_string()),
+            ]),
+            [Pages::Single(page)] => {
+                extent.with_children(vec![Element::text("start", &page.to_string())])
+            }
+            pages => extent.with_children(vec![Element::text(
+                "list",
+                &pages
+                    .iter()
+                    .map(|pages| pages.to_string().replace("--", "-"))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            )]),
+        }
+    }
+
+    fn container(name: &'static str, children: Vec<Element>) -> Option<Element> {
+        match children.is_empty() {
+            true => None,
+            false => Some(Element::new(name).with_children(Self::sorted(children))),
+        }
+    }
+
+    fn sorted(mut elements: Vec<Element>) -> Vec<Element> {
+        elements.sort_by_key(|element| {
+            Self::ELEMENT_ORDER
+                .iter()
+                .position(|it| *it == element.name)
+                .unwrap_or(Self::ELEMENT_ORDER.len())
+        });
+        elements
+    }
+
+    fn type_of_resource(entry_type: &EntryType) -> Element {
+        let resource = match entry_type {
+            EntryType::Software | EntryType::Dataset | EntryType::DataType => {
+                "software, multimedia"
+            }
+            EntryType::Audio => "sound recording-nonmusical",
+            EntryType::Music => "sound recording-musical",
+            EntryType::Video | EntryType::Movie => "moving image",
+            EntryType::Artwork | EntryType::Image => "still image",
+            _ => "text",
+        };
+        Element::text("typeOfResource", resource)
+    }
+
+    fn genre(entry: &Entry) -> Option<&'static str> {
+        let subtype = entry.fields().iter().find_map(|field| match field {
+            EntryField::EntrySubtype(subtype) => Some(subtype.to_lowercase()),
+            _ => None,
+        });
+        let genre = match entry.entry_type() {
+            EntryType::Article => match subtype.as_deref() {
+                Some("magazine") => "magazine article",
+                Some("newspaper") => "newspaper article",
+                _ => "journal article",
+            },
+            EntryType::Book
+            | EntryType::MvBook
+            | EntryType::Reference
+            | EntryType::MvReference
+            | EntryType::Commentary => "book",
+            EntryType::Collection | EntryType::MvCollection => "edited book",
+            EntryType::InBook
+            | EntryType::BookInBook
+            | EntryType::SuppBook
+            | EntryType::InCollection
+            | EntryType::SuppCollection => "book chapter",
+            EntryType::Proceedings | EntryType::MvProceedings => "conference publication",
+            EntryType::InProceedings | EntryType::Conference => "conference paper",
+            EntryType::InReference => "reference entry",
+            EntryType::Periodical | EntryType::SuppPeriodical => "periodical",
+            EntryType::Thesis | EntryType::MastersThesis | EntryType::PhdThesis => "thesis",
+            EntryType::Report | EntryType::TechReport => "report",
+            EntryType::Manual => "instruction",
+            EntryType::Patent => "patent",
+            EntryType::Online | EntryType::Electronic | EntryType::Www => "web site",
+            EntryType::Software => "software",
+            EntryType::Dataset | EntryType::DataType => "database",
+            EntryType::Unpublished => "unpublished",
+            EntryType::Booklet => "pamphlet",
+            EntryType::Standard => "standard or specification",
+            EntryType::Jurisdiction => "law report or digest",
+            EntryType::Legislation => "legislation",
+            EntryType::Letter => "letter",
+            _ => return None,
+        };
+        Some(genre)
+    }
+}
+
+/// Reads MODS records, single or in a `modsCollection`, into entries.
+pub(crate) struct ModsReader;
+
+impl ModsReader {
+    pub(crate) fn read(input: &str) -> Result<Vec<Entry>, String> {
+        let document = roxmltree::Document::parse(input.trim_start_matches('\u{feff}'))
+            .map_err(|e| format!("Invalid MODS XML: {}", e))?;
+        let mut symbols: Vec<String> = vec![];
+        document
+            .descendants()
+            .filter(|node| node.has_tag_name("mods"))
+            .map(|node| {
+                let entry = Self::entry(node, &symbols)?;
+                symbols.push(s!(entry.symbol()));
+                Ok(entry)
+            })
+            .collect()
+    }
+
+    fn entry(mods: Node, symbols: &[String]) -> Result<Entry, String> {
+        let host =
+            Self::children(mods, "relatedItem").find(|it| it.attribute("type") == Some("host"));
+        let item_and_host: Vec<Node> = std::iter::once(mods).chain(host).collect();
+        let all = |name: &'static str| -> Vec<Node> {
+            item_and_host
+                .iter()
+                .flat_map(|node| Self::children(*node, name))
+                .collect()
+        };
+        let (entry_type, subtype) = match Self::child_text(mods, "genre") {
+            Some(genre) => Self::entry_type(&genre),
+            None => (Self::entry_type_from_host(host), None),
+        };
+        let is_article = entry_type == EntryType::Article;
+        let bib_field = EntryField::from_field_name_and_text;
+
+        let mut fields: Vec<EntryField> = vec![];
+        if let Some(subtype) = subtype {
+            fields.push(EntryField::EntrySubtype(s!(subtype)));
+        }
+        let (mut authors, mut editors, mut translators, mut book_authors) =
+            (vec![], vec![], vec![], vec![]);
+        for name in Self::children(mods, "name") {
+            match Self::role(name).as_deref() {
+                Some("author" | "aut") | None => authors.push(Self::person(name)),
+                Some("editor" | "edt") => editors.push(Self::person(name)),
+                Some("translator" | "trl") => translators.push(Self::person(name)),
+                _ => {}
+            }
+        }
+        for name in host.iter().flat_map(|host| Self::children(*host, "name")) {
+            match Self::role(name).as_deref() {
+                Some("author" | "aut") | None => book_authors.push(Self::person(name)),
+                Some("editor" | "edt") => editors.push(Self::person(name)),
+                _ => {}
+            }
+        }
+        for (people, field) in [
+            (authors, EntryField::Author as fn(Vec<Person>) -> EntryField),
+            (editors, EntryField::Editor),
+            (translators, EntryField::Translator),
+            (book_authors, EntryField::BookAuthor),
+        ] {
+            if !people.is_empty() {
+                fields.push(field(people));
+            }
+        }
+        let (title, subtitle) = Self::title(mods, None);
+        for (name, value) in [("title", title), ("subtitle", subtitle)] {
+            if let Some(value) = value {
+                fields.push(bib_field(name, &value)?);
+            }
+        }
+        if let Some(short) = Self::title(mods, Some("abbreviated")).0 {
+            fields.push(EntryField::ShortTitle(short));
+        }
+        if let Some(host) = host {
+            let (title, subtitle) = Self::title(host, None);
+            let (title_name, subtitle_name) = match is_article {
+                true => ("journaltitle", "journalsubtitle"),
+                false => ("booktitle", "booksubtitle"),
+            };
+            for (name, value) in [(title_name, title), (subtitle_name, subtitle)] {
+                if let Some(value) = value {
+                    fields.push(bib_field(name, &value)?);
+                }
+            }
+            if let Some(short) = Self::title(host, Some("abbreviated")).0 {
+                fields.push(EntryField::ShortJournal(short));
+            }
+        }
+        if let Some(series) = Self::children(mods, "relatedItem")
+            .find(|it| it.attribute("type") == Some("series"))
+            .and_then(|series| Self::title(series, None).0)
+        {
+            fields.push(EntryField::Series(series));
+        }
+
+        let origins = all("originInfo");
+        let origin = |name: &'static str| -> Vec<String> {
+            origins
+                .iter()
+                .flat_map(|origin| Self::children(*origin, name))
+                .map(Self::text)
+                .filter(|it| !it.is_empty())
+                .collect()
+        };
+        fields.extend(Self::issued(
+            &origins
+                .iter()
+                .flat_map(|origin| Self::children(*origin, "dateIssued"))
+                .collect::<Vec<Node>>(),
+        ));
+        let places: Vec<String> = origins
+            .iter()
+            .flat_map(|origin| Self::children(*origin, "place"))
+            .flat_map(|place| Self::children(place, "placeTerm"))
+            .filter(|term| term.attribute("type") != Some("code"))
+            .map(Self::text)
+            .collect();
+        for (items, field) in [
+            (
+                places,
+                EntryField::Location as fn(LiteralList) -> EntryField,
+            ),
+            (origin("publisher"), EntryField::Publisher),
+        ] {
+            if !items.is_empty() {
+                let items: Vec<&str> = items.iter().map(String::as_str).collect();
+                fields.push(field(LiteralList::from_items(&items)));
+            }
+        }
+        if let Some(edition) = origin("edition").first() {
+            fields.push(bib_field("edition", edition)?);
+        }
+
+        let parts = all("part");
+        for detail in parts
+            .iter()
+            .flat_map(|part| Self::children(*part, "detail"))
+        {
+            let number = Self::child_text(detail, "number");
+            match (detail.attribute("type"), number) {
+                (Some("volume"), Some(number)) => fields.push(EntryField::Volume(number)),
+                (Some("issue" | "number"), Some(number)) => fields.push(EntryField::Number(number)),
+                _ => {}
+            }
+        }
+        for extent in parts
+            .iter()
+            .flat_map(|part| Self::children(*part, "extent"))
+        {
+            let pages = match (
+                Self::child_text(extent, "start"),
+                Self::child_text(extent, "end"),
+                Self::child_text(extent, "list"),
+            ) {
+                (Some(start), Some(end), _) => Some(format!("{}--{}", start, end)),
+                (Some(start), None, _) => Some(start),
+                (None, _, list) => list,
+            };
+            if let Some(pages) = pages {
+                fields.push(EntryField::Pages(Pages::pages_from_str(&pages)));
+            }
+        }
+
+        let languages: Vec<String> = Self::children(mods, "language")
+            .filter_map(|language| {
+                let terms: Vec<Node> = Self::children(language, "languageTerm").collect();
+                terms
+                    .iter()
+                    .find(|term| term.attribute("type") == Some("text"))
+                    .or(terms.first())
+                    .map(|term| Self::text(*term))
+            })
+            .collect();
+        if !languages.is_empty() {
+            let languages: Vec<&str> = languages.iter().map(String::as_str).collect();
+            fields.push(EntryField::Language(LiteralList::from_items(&languages)));
+        }
+        if let Some(abstract_) = Self::child_text(mods, "abstract") {
+            fields.push(EntryField::Abstract(abstract_));
+        }
+        let notes: Vec<String> = Self::children(mods, "note").map(Self::text).collect();
+        if !notes.is_empty() {
+            fields.push(EntryField::Note(notes.join("\n")));
+        }
+        let keywords: Vec<String> = Self::children(mods, "subject")
+            .flat_map(|subject| Self::children(subject, "topic"))
+            .map(Self::text)
+            .collect();
+        if !keywords.is_empty() {
+            let keywords: Vec<&str> = keywords.iter().map(String::as_str).collect();
+            fields.push(EntryField::Keywords(Keywords::from_items(&keywords)));
+        }
+
+        let identifiers = all("identifier");
+        let identifier = |kind: &str| {
+            identifiers
+                .iter()
+                .find(|it| it.attribute("type") == Some(kind))
+                .map(|it| Self::text(*it))
+        };
+        if let Some(doi) = identifier("doi") {
+            fields.push(bib_field("doi", &doi)?);
+        }
+        fields.extend(identifier("isbn").map(|isbn| EntryField::Isbn(Isbn::parse(&isbn))));
+        fields.extend(identifier("issn").map(|issn| EntryField::Issn(Issn::parse(&issn))));
+        let url = Self::children(mods, "location")
+            .flat_map(|location| Self::children(location, "url"))
+            .next();
+        if let Some(url) = url {
+            fields.push(EntryField::Url(Self::text(url)));
+            fields.extend(
+                url.attribute("dateLastAccessed")
+                    .and_then(|date| Date::parse_date_from_str(date).ok())
+                    .map(EntryField::UrlDate),
+            );
+        }
+
+        let symbol = identifier("citekey")
+            .or(mods.attribute("ID").map(|id| s!(id)))
+            .unwrap_or_else(|| Entry::generated_symbol(&fields, symbols, "anon"));
+        Ok(Entry::new(entry_type, symbol, fields))
+    }
+
+    fn children<'a, 'input>(
+        node: Node<'a, 'input>,
+        name: &'static str,
+    ) -> impl Iterator<Item = Node<'a, 'input>> {
+        node.children()
+            .filter(move |child| child.has_tag_name(name))
+    }
+
+    fn text(node: Node) -> String {
+        node.descendants()
+            .filter(Node::is_text)
+            .filter_map(|it| it.text())
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    fn child_text(node: Node, name: &'static str) -> Option<String> {
+        Self::children(node, name)
+            .map(Self::text)
+            .find(|it| !it.is_empty())
+    }
+
+    /// The title and subtitle of the `titleInfo` of the given type, its `nonSort` part,
+    /// e.g. `The`, put back in front of the title.
+    fn title(node: Node, kind: Option<&str>) -> (Option<String>, Option<String>) {
+        let title_info = Self::children(node, "titleInfo").find(|it| it.attribute("type") == kind);
+        let part = |name: &'static str| title_info.and_then(|it| Self::child_text(it, name));
+        let title = match (part("nonSort"), part("title")) {
+            (Some(non_sort), Some(title)) => Some(format!("{} {}", non_sort, title)),
+            (_, title) => title,
+        };
+        (title, part("subTitle"))
+    }
+
+    /// The role of the name, by text or by MARC relator code, lowercased.
+    fn role(name: Node) -> Option<String> {
+        Self::children(name, "role")
+            .flat_map(|role| Self::children(role, "roleTerm"))
+            .map(|term| Self::text(term).to_lowercase())
+            .next()
+    }
+
+    fn person(name: Node) -> Person {
+        let parts = |kind: &str| -> Vec<String> {
+            Self::children(name, "namePart")
+                .filter(|part| part.attribute("type") == Some(kind))
+                .map(Self::text)
+                .collect()
+        };
+        let suffix = parts("termsOfAddress");
+        match parts("family").first() {
+            Some(family) => Person::from_name_parts(
+                family,
+                &parts("given").join(" "),
+                suffix.first().map(String::as_str),
+            ),
+            None => {
+                let is_corporate = name.attribute("type") == Some("corporate");
+                let name = Self::children(name, "namePart")
+                    .filter(|part| part.attribute("type").is_none())
+                    .map(Self::text)
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                match name.contains(',') && !is_corporate {
+                    true => Person::from_last_first(&name),
+                    false => Person::Literal(name),
+                }
+            }
+        }
+    }
+
+    /// The ISO 8601 `dateIssued` as a date, or, if not a full one, as a year. A start and
+    /// an end point make a range.
+    fn issued(dates: &[Node]) -> Option<EntryField> {
+        let point = |point: &str| {
+            dates
+                .iter()
+                .find(|date| date.attribute("point") == Some(point))
+                .and_then(|date| Date::parse_date_from_str(&Self::text(*date)).ok())
+        };
+        if let Some(start) = point("start") {
+            return Some(EntryField::Date(Date::Range(
+                Box::new(start),
+                point("end").map(Box::new),
+            )));
+        }
+        let text = Self::text(*dates.first()?);
+        match Date::parse_date_from_str(&text) {
+            Ok(Date::Year(year)) => Some(EntryField::Year(Year::Numeric(year))),
+            Ok(date) => Some(EntryField::Date(date)),
+            Err(_) => Some(EntryField::Year(Year::parse(&text))),
+        }
+    }
+
+    fn entry_type(genre: &str) -> (EntryType, Option<&'static str>) {
+        let genre = genre.to_lowercase();
+        let entry_type = match genre.as_str() {
+            "journal article" | "article" | "magazine article" | "newspaper article" => {
+                EntryType::Article
+            }
+            "book" => EntryType::Book,
+            "edited book" => EntryType::Collection,
+            "book chapter" | "chapter" => EntryType::InCollection,
+            "conference publication" | "proceedings" => EntryType::Proceedings,
+            "conference paper" => EntryType::InProceedings,
+            "reference entry" => EntryType::InReference,
+            "periodical" | "academic journal" | "journal" => EntryType::Periodical,
+            "thesis" | "dissertation" => EntryType::Thesis,
+            "report" | "technical report" => EntryType::Report,
+            "instruction" => EntryType::Manual,
+            "patent" => EntryType::Patent,
+            "web site" | "web page" => EntryType::Online,
+            "software" => EntryType::Software,
+            "database" | "dataset" => EntryType::Dataset,
+            "unpublished" => EntryType::Unpublished,
+            "pamphlet" => EntryType::Booklet,
+            "standard or specification" => EntryType::Standard,
+            "law report or digest" => EntryType::Jurisdiction,
+            "legislation" => EntryType::Legislation,
+            "letter" => EntryType::Letter,
+            _ => EntryType::Misc,
+        };
+        let subtype = match genre.as_str() {
+            "magazine article" => Some("magazine"),
+            "newspaper article" => Some("newspaper"),
+            _ => None,
+        };
+        (entry_type, subtype)
+    }
+
+    /// Without a genre, records in a continuing resource are taken as articles, those in
+    /// another one as chapters.
+    fn entry_type_from_host(host: Option<Node>) -> EntryType {
+        let is_continuing = host.is_some_and(|host| {
+            Self::children(host, "originInfo")
+                .filter_map(|origin| Self::child_text(origin, "issuance"))
+                .any(|issuance| issuance == "continuing" || issuance == "serial")
+                || Self::child_text(host, "genre").is_some_and(|genre| {
+                    ["periodical", "academic journal", "journal"].contains(&genre.as_str())
+                })
+        });
+        match (host, is_continuing) {
+            (Some(_), true) => EntryType::Article,
+            (Some(_), false) => EntryType::InCollection,
+            (None, _) => EntryType::Book,
+        }
+    }
+}
+
+#[cfg(test)]
+mod mods_test {
+    use super::*;
+    use crate::doi::Doi;
+    use crate::entry::Parser;
+    use crate::tokenizer::Tokenizer;
+    use std::io::Cursor;
+
+    /// The child elements MODS allows, for the elements the writer uses.
+    const STRUCTURE: [(&str, &[&str]); 14] = [
+        ("modsCollection", &["mods"]),
+        ("mods", &TOP_LEVEL),
+        ("relatedItem", &TOP_LEVEL),
+        (
+            "titleInfo",
+            &["title", "subTitle", "partNumber", "partName", "nonSort"],
+        ),
+        (
+            "name",
+            &[
+                "namePart",
+                "displayForm",
+                "affiliation",
+                "role",
+                "description",
+            ],
+        ),
+        ("role", &["roleTerm"]),
+        (
+            "originInfo",
+            &["place", "publisher", "dateIssued", "edition", "issuance"],
+        ),
+        ("place", &["placeTerm"]),
+        ("language", &["languageTerm", "scriptTerm"]),
+        (
+            "subject",
+            &[
+                "topic",
+                "geographic",
+                "temporal",
+                "titleInfo",
+                "name",
+                "genre",
+            ],
+        ),
+        ("location", &["physicalLocation", "shelfLocator", "url"]),
+        ("part", &["detail", "extent", "date", "text"]),
+        ("detail", &["number", "caption", "title"]),
+        ("extent", &["start", "end", "total", "list"]),
+    ];
+
+    const TOP_LEVEL: [&str; 20] = [
+        "titleInfo",
+        "name",
+        "typeOfResource",
+        "genre",
+        "originInfo",
+        "language",
+        "physicalDescription",
+        "abstract",
+        "tableOfContents",
+        "targetAudience",
+        "note",
+        "subject",
+        "classification",
+        "relatedItem",
+        "identifier",
+        "location",
+        "accessCondition",
+        "part",
+        "extension",
+        "recordInfo",
+    ];
+
+    #[test]
+    fn read_records() {
+        // given
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <modsCollection xmlns="http://www.loc.gov/mods/v3">
+            <mods ID="knuth84">
+                <titleInfo><nonSort>The</nonSort><title>Literate Programming</title></titleInfo>
+                <name type="personal">
+                    <namePart type="given">Donald</namePart>
+                    <namePart type="given">E.</namePart>
+                    <namePart type="family">Knuth</namePart>
+                    <role><roleTerm type="code" authority="marcrelator">aut</roleTerm></role>
+                </name>
+                <name><namePart>Ford, Henry</namePart></name>
+                <originInfo><dateIssued encoding="w3cdtf">1984-05</dateIssued></originInfo>
+                <relatedItem type="host">
+                    <titleInfo><title>The Computer Journal</title></titleInfo>
+                    <originInfo><issuance>continuing</issuance></originInfo>
+                    <identifier type="issn">0010-4620</identifier>
+                    <part>
+                        <detail type="volume"><number>27</number></detail>
+                        <detail type="issue"><number>2</number></detail>
+                        <extent unit="page"><start>97</start><end>111</end></extent>
+                    </part>
+                </relatedItem>
+                <identifier type="doi">10.1093/comjnl/27.2.97</identifier>
+            </mods>
+            </modsCollection>"#;
+        let expected = vec![Entry::new(
+            EntryType::Article,
+            s!("knuth84"),
+            vec![
+                EntryField::Author(vec![
+                    Person::FirstMiddleLast {
+                        first_name: s!("Donald"),
+                        middle_names: vec![s!("E")],
+                        last_name: s!("Knuth"),
+                    },
+                    Person::FirstLast {
+                        first_name: s!("Henry"),
+                        last_name: s!("Ford"),
+                    },
+                ]),
+                EntryField::Title(s!("The Literate Programming")),
+                EntryField::JournalTitle(s!("The Computer Journal")),
+                EntryField::Date(Date::YearMonth(1984, 5)),
+                EntryField::Volume(s!("27")),
+                EntryField::Number(s!("2")),
+                EntryField::Pages(Pages::pages_from_str("97--111")),
+                EntryField::Doi(Doi::parse("10.1093/comjnl/27.2.97")),
+                EntryField::Issn(Issn::parse("0010-4620")),
+            ],
+        )];
+
+        // when
+        let actual = ModsReader::read(input);
+
+        // then
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn write_record() {
+        // given
+        let input = r#"@incollection{beck-2004,
+            author = {Beck, Kent}, editor = {Doe, John},
+            title = {Extreme Programming}, subtitle = {Explained},
+            booktitle = {Agile Methods},
+            date = {2004-03},
+            pages = {1--5},
+            publisher = {Addison & Wesley},
+        }"#;
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<modsCollection xmlns="http://www.loc.gov/mods/v3">
+  <mods version="3.8">
+    <titleInfo>
+      <title>Extreme Programming</title>
+      <subTitle>Explained</subTitle>
+    </titleInfo>
+    <name type="personal">
+      <namePart type="family">Beck</namePart>
+      <namePart type="given">Kent</namePart>
+      <role>
+        <roleTerm type="text" authority="marcrelator">author</roleTerm>
+      </role>
+    </name>
+    <typeOfResource>text</typeOfResource>
+    <genre>book chapter</genre>
+    <originInfo>
+      <dateIssued encoding="iso8601">2004-03</dateIssued>
+    </originInfo>
+    <relatedItem type="host">
+      <titleInfo>
+        <title>Agile Methods</title>
+      </titleInfo>
+      <name type="personal">
+        <namePart type="family">Doe</namePart>
+        <namePart type="given">John</namePart>
+        <role>
+          <roleTerm type="text" authority="marcrelator">editor</roleTerm>
+        </role>
+      </name>
+      <originInfo>
+        <publisher>Addison &amp; Wesley</publisher>
+      </originInfo>
+      <part>
+        <extent unit="pages">
+          <start>1</start>
+          <end>5</end>
+        </extent>
+      </part>
+    </relatedItem>
+    <identifier type="citekey">beck-2004</identifier>
+  </mods>
+</modsCollection>
+"#;
+
+        // when
+        let actual = ModsWriter::write(&parse(input));
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn decode_latex() {
+        // given
+        let input = r#"@misc{mueller,
+            author = {M{\"u}ller, J{\"o}rg},
+            title = {Caf{\'e} \& Bar},
+            url = {https://example.com/caf\_e},
+        }"#;
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<modsCollection xmlns="http://www.loc.gov/mods/v3">
+  <mods version="3.8">
+    <titleInfo>
+      <title>Café &amp; Bar</title>
+    </titleInfo>
+    <name type="personal">
+      <namePart type="family">Müller</namePart>
+      <namePart type="given">Jörg</namePart>
+      <role>
+        <roleTerm type="text" authority="marcrelator">author</roleTerm>
+      </role>
+    </name>
+    <typeOfResource>text</typeOfResource>
+    <identifier type="citekey">mueller</identifier>
+    <location>
+      <url>https://example.com/caf\_e</url>
+    </location>
+  </mods>
+</modsCollection>
+"#;
+
+        // when
+        let actual = ModsWriter::write(&parse(input));
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn write_corporate_name_and_month() {
+        // given
+        let input = r#"@misc{bn,
+            author = {{Barnes, Noble and Co}},
+            title = {Catalogue},
+            year = 2020, month = mar,
+        }"#;
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<modsCollection xmlns="http://www.loc.gov/mods/v3">
+  <mods version="3.8">
+    <titleInfo>
+      <title>Catalogue</title>
+    </titleInfo>
+    <name type="corporate">
+      <namePart>Barnes, Noble and Co</namePart>
+      <role>
+        <roleTerm type="text" authority="marcrelator">author</roleTerm>
+      </role>
+    </name>
+    <typeOfResource>text</typeOfResource>
+    <originInfo>
+      <dateIssued encoding="iso8601">2020-03</dateIssued>
+    </originInfo>
+    <identifier type="citekey">bn</identifier>
+  </mods>
+</modsCollection>
+"#;
+
+        // when
+        let actual = ModsWriter::write(&parse(input));
+        let read = ModsReader::read(&actual).unwrap();
+
+        // then
+        assert_eq!(actual, expected);
+        assert!(read[0]
+            .fields()
+            .contains(&EntryField::Author(vec![Person::Literal(s!(
+                "Barnes, Noble and Co"
+            ))])));
+        assert!(read[0]
+            .fields()
+            .contains(&EntryField::Date(Date::YearMonth(2020, 3))));
+    }
+
+    #[test]
+    fn write_valid_mods() {
+        // given
+        let input = r#"
+            @article{a,
+                author = {de la Fontaine, Jean and Ford, Jr., Henry and {IEEE}},
+                translator = {Roe, Richard},
+                title = {Title}, shorttitle = {T},
+                journaltitle = {Journal}, shortjournal = {J.},
+                date = {2020-06-01/2020-06-03},
+                volume = {3}, number = {4}, pages = {iv--xii, 15+},
+                doi = {10.1109/IEEESTD.2018.8457469}, issn = {0010-4620},
+                keywords = {a, b}, language = {english},
+                url = {https://example.com}, urldate = {2021-01-02},
+                abstract = {<One> & "two"}, note = {Note},
+            }
+            @book{b, editor = {Doe, John}, title = {Book}, year = {2001}, edition = {2},
+                series = {Series}, volume = {1}, location = {New York and London}}
+            @online{c, title = {Site}, year = {circa 1990}}"#;
+
+        // when
+        let actual = ModsWriter::write(&parse(input));
+
+        // then
+        let document = roxmltree::Document::parse(&actual).unwrap();
+        let root = document.root_element();
+        assert!(root.has_tag_name((NAMESPACE, "modsCollection")));
+        assert_eq!(
+            root.children().filter(|it| it.has_tag_name("mods")).count(),
+            3
+        );
+        root.descendants()
+            .filter(Node::is_element)
+            .for_each(|element| assert_valid(element));
+    }
+
+    #[test]
+    fn round_trip() {
+        // given
+        let input = r#"
+            @article{a,
+                author = {de la Fontaine, Jean and Ford, Jr., Henry and {IEEE}},
+                title = {Title}, subtitle = {Subtitle},
+                journaltitle = {Journal}, shortjournal = {J.},
+                date = {2020-06-01},
+                volume = {3}, number = {4}, pages = {iv--xii, 15+},
+                doi = {10.1109/IEEESTD.2018.8457469}, issn = {0010-4620},
+                keywords = {a, b},
+                url = {https://example.com}, urldate = {2021-01-02},
+            }
+            @inproceedings{b,
+                author = {Doe, Jane}, editor = {Doe, John},
+                title = {Paper}, booktitle = {Proceedings}, series = {Series},
+                date = {2001/2002}, edition = {2},
+                location = {New York and London}, publisher = {Publisher},
+                isbn = {978-0-13-405199-4},
+            }
+            @thesis{c, author = {Roe, Richard}, title = {Thesis}, year = {circa 1990}, institution = {University}}"#;
+        let expected = ModsWriter::write(&parse(input));
+
+        // when
+        let actual = ModsWriter::write(&ModsReader::read(&expected).unwrap());
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    /// Checks the element is in the MODS namespace, has only the children MODS allows,
+    /// and, as an ISO 8601 date, one that parses.
+    fn assert_valid(element: Node) {
+        let name = element.tag_name().name();
+        assert_eq!(element.tag_name().namespace(), Some(NAMESPACE), "{}", name);
+        let children: Vec<&str> = element
+            .children()
+            .filter(Node::is_element)
+            .map(|child| child.tag_name().name())
+            .collect();
+        match STRUCTURE.iter().find(|(it, _)| *it == name) {
+            Some((_, allowed)) => children
+                .iter()
+                .for_each(|child| assert!(allowed.contains(child), "{} in {}", child, name)),
+            None => assert!(children.is_empty(), "{} in {}", children.join(", "), name),
+        }
+        if element.attribute("encoding") == Some("iso8601") {
+            assert!(Date::parse_date_from_str(element.text().unwrap()).is_ok());
+        }
+        if name == "relatedItem" {
+            assert!(["host", "series"].contains(&element.attribute("type").unwrap()));
+        }
+    }
+
+    fn parse(input: &str) -> Vec<Entry> {
+        Parser::new(Tokenizer::new(Box::new(Cursor::new(s!(input)))))
+            .parse()
+            .unwrap()
+    }
+}
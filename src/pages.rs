@@ -1,47 +1,107 @@
 use crate::s;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Debug, Clone, Serialize)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub(crate) enum Pages {
     Single(Page),
     Range(Page, Page),
+    OpenRange(Page),
+    Following(Page),
 }
 
-#[derive(PartialEq, Debug, Clone, Serialize)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub(crate) enum Page {
     Numeric(u32),
+    Roman(String, u32),
+    Prefixed(String, u32),
     Literal(String),
 }
 
 impl Pages {
     const MULTI_SEPARATOR: &'static str = ",";
     const RANGE_SEPARATOR: [&'static str; 3] = ["-", "–", "—"];
+    const OPEN_RANGE_SUFFIX: &'static str = "+";
+    const FOLLOWING_PAGES_SUFFIXES: [&'static str; 2] = ["ff.", "ff"];
+    const FOLLOWING_PAGE_SUFFIXES: [&'static str; 2] = ["f.", "f"];
 
     pub(crate) fn pages_from_str(s: &str) -> Vec<Pages> {
-        s.to_string()
-            .split(Self::MULTI_SEPARATOR)
+        s.split(Self::MULTI_SEPARATOR)
             .map(|it| Self::single_or_range_from_str(it.trim()))
             .collect::<Vec<Pages>>()
     }
 
+    /// Number of pages covered by the pages, `None` for open ranges
+    /// and ranges whose ends cannot be compared.
+    pub(crate) fn page_count(&self) -> Option<u32> {
+        match self {
+            Pages::Single(_) => Some(1),
+            Pages::Range(start, end) => Page::distance(start, end)?.checked_add(1),
+            Pages::OpenRange(_) | Pages::Following(_) => None,
+        }
+    }
+
+    /// Sum of the page counts, `None` if any of the pages cannot be counted.
+    pub(crate) fn total_page_count(pages: &[Pages]) -> Option<u32> {
+        pages
+            .iter()
+            .try_fold(0u32, |total, pages| total.checked_add(pages.page_count()?))
+    }
+
     fn single_or_range_from_str(s: &str) -> Pages {
+        if let Some(pages) = Self::following_from_str(s) {
+            return pages;
+        }
+        if let Some(start) = Self::open_range_start(s) {
+            return Pages::OpenRange(Self::page_from_str(start));
+        }
         let single_or_range = Self::split_range(s);
         match single_or_range.len() {
-            2 => Pages::Range(
-                Self::page_from_str(single_or_range.get(0).unwrap_or(&s!(""))),
-                Self::page_from_str(single_or_range.get(1).unwrap_or(&s!(""))),
-            ),
+            2 => {
+                let start = Self::page_from_str(single_or_range.first().unwrap_or(&s!("")));
+                let end = Self::page_from_str(single_or_range.get(1).unwrap_or(&s!("")));
+                let expanded_end = Page::expand_abbreviated(&start, end);
+                Pages::Range(start, expanded_end)
+            }
             _ => Pages::Single(Self::page_from_str(s)),
         }
     }
 
+    fn following_from_str(s: &str) -> Option<Pages> {
+        let following_pages = Self::FOLLOWING_PAGES_SUFFIXES
+            .iter()
+            .find_map(|suffix| s.strip_suffix(suffix))
+            .map(|it| Self::page_from_str(it.trim()))
+            .filter(|page| page.value().is_some())
+            .map(Pages::Following);
+        let following_page = || {
+            Self::FOLLOWING_PAGE_SUFFIXES
+                .iter()
+                .find_map(|suffix| s.strip_suffix(suffix))
+                .map(|it| Self::page_from_str(it.trim()))
+                .and_then(|page| page.next().map(|next| Pages::Range(page, next)))
+        };
+        following_pages.or_else(following_page)
+    }
+
+    fn open_range_start(s: &str) -> Option<&str> {
+        let start = s.strip_suffix(Self::OPEN_RANGE_SUFFIX).or_else(|| {
+            Self::RANGE_SEPARATOR
+                .iter()
+                .find_map(|range_sep| s.strip_suffix(range_sep))
+                .map(|it| it.trim_end_matches(['-', '–', '—']))
+        })?;
+        match start.trim() {
+            "" => None,
+            trimmed => Some(trimmed),
+        }
+    }
+
     fn split_range(s: &str) -> Vec<String> {
         Self::RANGE_SEPARATOR
             .iter()
             .filter(|range_sep| s.to_string().contains(*range_sep))
             .flat_map(|range_sep| {
-                s.to_string()
-                    .split(range_sep)
+                s.split(range_sep)
                     .filter(|it| !it.is_empty())
                     .map(|it| it.trim().to_string())
                     .collect::<Vec<String>>()
@@ -52,7 +112,157 @@ impl Pages {
     fn page_from_str(s: &str) -> Page {
         match s.to_string().parse::<u32>() {
             Ok(i) => Page::Numeric(i),
-            Err(_) => Page::Literal(s.to_string()),
+            Err(_) => Page::roman_from_str(s)
+                .or_else(|| Page::prefixed_from_str(s))
+                .unwrap_or(Page::Literal(s.to_string())),
+        }
+    }
+}
+
+impl Page {
+    const ROMAN_NUMERALS: [(&'static str, u32); 13] = [
+        ("m", 1000),
+        ("cm", 900),
+        ("d", 500),
+        ("cd", 400),
+        ("c", 100),
+        ("xc", 90),
+        ("l", 50),
+        ("xl", 40),
+        ("x", 10),
+        ("ix", 9),
+        ("v", 5),
+        ("iv", 4),
+        ("i", 1),
+    ];
+
+    pub(crate) fn value(&self) -> Option<u32> {
+        match self {
+            Page::Numeric(n) | Page::Roman(_, n) | Page::Prefixed(_, n) => Some(*n),
+            Page::Literal(_) => None,
+        }
+    }
+
+    /// The page after this one, `None` for literal pages and past the largest page number.
+    fn next(&self) -> Option<Page> {
+        match self {
+            Page::Numeric(n) => Some(Page::Numeric(n.checked_add(1)?)),
+            Page::Roman(s, n) => {
+                let next = n.checked_add(1)?;
+                Some(Page::Roman(Self::to_roman(next, s), next))
+            }
+            Page::Prefixed(p, n) => Some(Page::Prefixed(p.clone(), n.checked_add(1)?)),
+            Page::Literal(_) => None,
+        }
+    }
+
+    fn distance(start: &Page, end: &Page) -> Option<u32> {
+        let comparable = match (start, end) {
+            (Page::Numeric(_), Page::Numeric(_)) | (Page::Roman(_, _), Page::Roman(_, _)) => true,
+            (Page::Prefixed(p1, _), Page::Prefixed(p2, _)) => p1 == p2,
+            _ => false,
+        };
+        match comparable {
+            true => end.value()?.checked_sub(start.value()?),
+            false => None,
+        }
+    }
+
+    /// Expands abbreviated range ends, e.g. the `56` in `1234--56` into `1256`.
+    fn expand_abbreviated(start: &Page, end: Page) -> Page {
+        match (start, &end) {
+            (Page::Numeric(s), Page::Numeric(e)) => Page::Numeric(Self::expand_number(*s, *e)),
+            (Page::Prefixed(p, s), Page::Numeric(e)) => {
+                Page::Prefixed(p.clone(), Self::expand_number(*s, *e))
+            }
+            (Page::Prefixed(p1, s), Page::Prefixed(p2, e)) if p1 == p2 => {
+                Page::Prefixed(p1.clone(), Self::expand_number(*s, *e))
+            }
+            _ => end,
+        }
+    }
+
+    fn expand_number(start: u32, end: u32) -> u32 {
+        let start_digits = start.to_string();
+        let end_digits = end.to_string();
+        if end >= start || end_digits.len() >= start_digits.len() {
+            return end;
+        }
+        let prefix = &start_digits[..start_digits.len() - end_digits.len()];
+        match format!("{}{}", prefix, end_digits).parse::<u32>() {
+            Ok(expanded) if expanded >= start => expanded,
+            _ => end,
+        }
+    }
+
+    fn roman_from_str(s: &str) -> Option<Page> {
+        let lowercase = s.to_lowercase();
+        let is_single_case = s == lowercase || s == s.to_uppercase();
+        if lowercase.is_empty()
+            || !is_single_case
+            || !lowercase.chars().all(|c| "ivxlcdm".contains(c))
+        {
+            return None;
+        }
+        let mut rest = lowercase.as_str();
+        let mut value = 0;
+        while !rest.is_empty() {
+            let (numeral, numeral_value) = Self::ROMAN_NUMERALS
+                .iter()
+                .find(|(numeral, _)| rest.starts_with(numeral))?;
+            value += numeral_value;
+            rest = &rest[numeral.len()..];
+        }
+        match Self::to_roman(value, "") == lowercase {
+            true => Some(Page::Roman(s!(s), value)),
+            false => None,
+        }
+    }
+
+    /// Converts the value to a roman numeral in the letter case of `template`.
+    fn to_roman(value: u32, template: &str) -> String {
+        let mut rest = value;
+        let mut roman = String::new();
+        for (numeral, numeral_value) in Self::ROMAN_NUMERALS {
+            while rest >= numeral_value {
+                roman.push_str(numeral);
+                rest -= numeral_value;
+            }
+        }
+        match template.chars().next().map(char::is_uppercase) {
+            Some(true) => roman.to_uppercase(),
+            _ => roman,
+        }
+    }
+
+    fn prefixed_from_str(s: &str) -> Option<Page> {
+        let split_at = s.find(|c: char| c.is_ascii_digit())?;
+        let (prefix, number) = s.split_at(split_at);
+        let is_prefix_alphabetic = !prefix.is_empty() && prefix.chars().all(char::is_alphabetic);
+        match (is_prefix_alphabetic, number.parse::<u32>()) {
+            (true, Ok(n)) => Some(Page::Prefixed(s!(prefix), n)),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Pages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pages::Single(page) => write!(f, "{}", page),
+            Pages::Range(start, end) => write!(f, "{}--{}", start, end),
+            Pages::OpenRange(start) => write!(f, "{}{}", start, Self::OPEN_RANGE_SUFFIX),
+            Pages::Following(start) => write!(f, "{}{}", start, Self::FOLLOWING_PAGES_SUFFIXES[0]),
+        }
+    }
+}
+
+impl std::fmt::Display for Page {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Page::Numeric(n) => write!(f, "{}", n),
+            Page::Roman(s, _) | Page::Literal(s) => write!(f, "{}", s),
+            Page::Prefixed(prefix, n) => write!(f, "{}{}", prefix, n),
         }
     }
 }
@@ -65,9 +275,16 @@ mod pages_test {
     #[test]
     fn parse_single_from_str() {
         // given
-        vec![
+        [
             ("2", vec![Pages::Single(Page::Numeric(2))]),
-            ("ii", vec![Pages::Single(Page::Literal(s!("ii")))]),
+            ("ii", vec![Pages::Single(Page::Roman(s!("ii"), 2))]),
+            ("XIV", vec![Pages::Single(Page::Roman(s!("XIV"), 14))]),
+            (
+                "e1002345",
+                vec![Pages::Single(Page::Prefixed(s!("e"), 1002345))],
+            ),
+            ("iix", vec![Pages::Single(Page::Literal(s!("iix")))]),
+            ("A.1", vec![Pages::Single(Page::Literal(s!("A.1")))]),
         ]
         .iter()
         .for_each(|(input, expected)| {
@@ -82,7 +299,7 @@ mod pages_test {
     #[test]
     fn parse_numerical_range_from_str() {
         // given
-        vec![
+        [
             "2-4", "2--4", "2---4", "2 - 4", "2 -- 4", "2 --- 4", "2–4", "2—4", "2 – 4", "2 — 4",
         ]
         .iter()
@@ -98,12 +315,28 @@ mod pages_test {
     }
 
     #[test]
-    fn parse_literal_range_from_str() {
+    fn parse_roman_range_from_str() {
         // given
         let input = "ii - iv";
         let expected = vec![Pages::Range(
-            Page::Literal(s!("ii")),
-            Page::Literal(s!("iv")),
+            Page::Roman(s!("ii"), 2),
+            Page::Roman(s!("iv"), 4),
+        )];
+
+        // when
+        let actual = Pages::pages_from_str(input);
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_literal_range_from_str() {
+        // given
+        let input = "A.1 -- A.4";
+        let expected = vec![Pages::Range(
+            Page::Literal(s!("A.1")),
+            Page::Literal(s!("A.4")),
         )];
 
         // when
@@ -118,7 +351,7 @@ mod pages_test {
         // given
         let input = "ii--iv, 12";
         let expected = vec![
-            Pages::Range(Page::Literal(s!("ii")), Page::Literal(s!("iv"))),
+            Pages::Range(Page::Roman(s!("ii"), 2), Page::Roman(s!("iv"), 4)),
             Pages::Single(Page::Numeric(12)),
         ];
 
@@ -129,6 +362,100 @@ mod pages_test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn parse_open_and_following_from_str() {
+        // given
+        [
+            ("12+", Pages::OpenRange(Page::Numeric(12))),
+            ("12--", Pages::OpenRange(Page::Numeric(12))),
+            ("12 –", Pages::OpenRange(Page::Numeric(12))),
+            ("45ff.", Pages::Following(Page::Numeric(45))),
+            ("45 ff", Pages::Following(Page::Numeric(45))),
+            ("45f.", Pages::Range(Page::Numeric(45), Page::Numeric(46))),
+            (
+                "xf.",
+                Pages::Range(Page::Roman(s!("x"), 10), Page::Roman(s!("xi"), 11)),
+            ),
+            ("Cliff", Pages::Single(Page::Literal(s!("Cliff")))),
+            (
+                "4294967295f.",
+                Pages::Single(Page::Literal(s!("4294967295f."))),
+            ),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Pages::pages_from_str(input);
+
+            // then
+            assert_eq!(actual, vec![expected.clone()]);
+        });
+    }
+
+    #[test]
+    fn parse_abbreviated_range_from_str() {
+        // given
+        [
+            (
+                "1234--56",
+                Pages::Range(Page::Numeric(1234), Page::Numeric(1256)),
+            ),
+            (
+                "1234--8",
+                Pages::Range(Page::Numeric(1234), Page::Numeric(1238)),
+            ),
+            (
+                "98--102",
+                Pages::Range(Page::Numeric(98), Page::Numeric(102)),
+            ),
+            (
+                "1299--1",
+                Pages::Range(Page::Numeric(1299), Page::Numeric(1)),
+            ),
+            (
+                "S12--S19",
+                Pages::Range(Page::Prefixed(s!("S"), 12), Page::Prefixed(s!("S"), 19)),
+            ),
+            (
+                "S112--19",
+                Pages::Range(Page::Prefixed(s!("S"), 112), Page::Prefixed(s!("S"), 119)),
+            ),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Pages::pages_from_str(input);
+
+            // then
+            assert_eq!(actual, vec![expected.clone()]);
+        });
+    }
+
+    #[test]
+    fn count_pages() {
+        // given
+        [
+            ("12", Some(1)),
+            ("12--15", Some(4)),
+            ("1234--56", Some(23)),
+            ("ii--iv, 12", Some(4)),
+            ("S12--S19, e1002345", Some(9)),
+            ("12, 45ff.", None),
+            ("12+", None),
+            ("S12--T19", None),
+            ("0--4294967295", None),
+            ("1--4294967295, 1--2", None),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Pages::total_page_count(&Pages::pages_from_str(input));
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
     #[test]
     fn parse_pages_single() {
         // given
@@ -145,7 +472,7 @@ mod pages_test {
     #[test]
     fn split_range() {
         // given
-        vec![
+        [
             "2-4", "2--4", "2---4", "2 - 4", "2 -- 4", "2 --- 4", "2–4", "2—4", "2 – 4", "2 — 4",
         ]
         .iter()
@@ -1,7 +1,8 @@
 use crate::s;
-use serde::Serialize;
+use crate::strings::{latex_to_unicode, protect_list_item, split_and_list, strip_braces};
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Debug, Clone, Serialize)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub(crate) enum Person {
     FirstLast {
         first_name: String,
@@ -12,19 +13,252 @@ pub(crate) enum Person {
         middle_names: Vec<String>,
         last_name: String,
     },
+    /// The `Last, Jr, First` form.
+    Suffixed {
+        first_name: String,
+        middle_names: Vec<String>,
+        last_name: String,
+        suffix: String,
+    },
+    /// A name written without a comma, in the `First von Last` form, e.g. `Ludwig van Beethoven`.
     FullName(String),
+    /// A name written in braces, e.g. `{Barnes and Noble, Inc.}`, kept whole.
+    Literal(String),
 }
 
 impl Person {
-    const NAME_SEPARATOR: &'static str = " and ";
     const FIRST_LAST_SEPARATOR: &'static str = ", ";
 
     pub(crate) fn people_from_str(s: &str) -> Result<Vec<Person>, String> {
-        let people_str = s.splitn(100, Self::NAME_SEPARATOR);
-        people_str.map(Self::person_from_str).collect()
+        split_and_list(s)
+            .iter()
+            .map(|it| Self::braced_or_person_from_str(it))
+            .collect()
+    }
+
+    /// The names as written in a `.bib` file, e.g. `Beck, Kent and {Barnes and Noble}`.
+    pub(crate) fn people_to_bib(people: &[Person]) -> String {
+        people
+            .iter()
+            .map(Person::to_bib)
+            .collect::<Vec<String>>()
+            .join(" and ")
+    }
+
+    /// The last name, with its `von` part, or the whole name of people written in braces.
+    pub(crate) fn last_name(&self) -> &str {
+        match self {
+            Person::FirstLast { last_name, .. }
+            | Person::FirstMiddleLast { last_name, .. }
+            | Person::Suffixed { last_name, .. } => last_name,
+            Person::FullName(name) => Self::split_first_last(name).1,
+            Person::Literal(name) => name,
+        }
+    }
+
+    /// The first and middle names, with the single letters, stored without their dot, as initials.
+    pub(crate) fn given_names(&self) -> Option<String> {
+        let (first_name, middle_names): (&str, Vec<&str>) = match self {
+            Person::FirstLast { first_name, .. } => (first_name, vec![]),
+            Person::FirstMiddleLast {
+                first_name,
+                middle_names,
+                ..
+            }
+            | Person::Suffixed {
+                first_name,
+                middle_names,
+                ..
+            } => (
+                first_name,
+                middle_names.iter().map(String::as_str).collect(),
+            ),
+            Person::FullName(name) => {
+                Self::first_or_first_and_middle(Self::split_first_last(name).0?)
+            }
+            Person::Literal(_) => return None,
+        };
+        let names = std::iter::once(first_name)
+            .chain(middle_names)
+            .map(|name| match name.chars().count() {
+                1 => format!("{}.", name),
+                _ => s!(name),
+            })
+            .collect::<Vec<String>>();
+        Some(names.join(" "))
+    }
+
+    /// The lower-case words leading the last name, e.g. `van` in `van Gogh`, and the rest of it.
+    pub(crate) fn von_and_last(&self) -> (Option<String>, String) {
+        let words: Vec<&str> = self.last_name().split_whitespace().collect();
+        let von_len = words
+            .iter()
+            .take(words.len().saturating_sub(1))
+            .take_while(|word| word.starts_with(char::is_lowercase))
+            .count();
+        match von_len {
+            0 => (None, s!(self.last_name())),
+            n => (Some(words[..n].join(" ")), words[n..].join(" ")),
+        }
+    }
+
+    pub(crate) fn suffix(&self) -> Option<&str> {
+        match self {
+            Person::Suffixed { suffix, .. } => Some(suffix),
+            _ => None,
+        }
+    }
+
+    fn to_bib(&self) -> String {
+        match self {
+            Person::FullName(name) => s!(name),
+            Person::Literal(name) => format!("{{{}}}", name),
+            person => {
+                let mut parts = vec![protect_list_item(person.last_name())];
+                parts.extend(person.suffix().map(protect_list_item));
+                parts.push(protect_list_item(&person.given_names().unwrap_or_default()));
+                parts.join(Self::FIRST_LAST_SEPARATOR)
+            }
+        }
+    }
+
+    /// Splits a `First von Last` name before its `von` part, or else before its last word,
+    /// e.g. `Ludwig van Beethoven` into `Ludwig` and `van Beethoven`.
+    fn split_first_last(name: &str) -> (Option<&str>, &str) {
+        let name = name.trim();
+        let word_starts: Vec<usize> = name
+            .char_indices()
+            .filter(|(i, c)| !c.is_whitespace() && name[..*i].ends_with(char::is_whitespace))
+            .map(|(i, _)| i)
+            .collect();
+        let von_start = word_starts
+            .iter()
+            .find(|i| name[**i..].starts_with(char::is_lowercase));
+        match von_start.or(word_starts.last()) {
+            Some(i) => (Some(name[..*i].trim_end()), &name[*i..]),
+            None => (None, name),
+        }
+    }
+
+    /// A person from the separately given parts of the name, e.g. those of a CSL name.
+    pub(crate) fn from_name_parts(last_name: &str, given: &str, suffix: Option<&str>) -> Person {
+        let (first, middle) = Self::first_or_first_and_middle(given);
+        let first_name = s!(first);
+        let middle_names: Vec<String> = middle.iter().map(|s| s!(*s)).collect();
+        let last_name = s!(last_name);
+        match (suffix, middle_names.is_empty()) {
+            _ if first_name.is_empty() => Person::Literal(last_name),
+            (Some(suffix), _) => Person::Suffixed {
+                first_name,
+                middle_names,
+                last_name,
+                suffix: s!(suffix),
+            },
+            (None, true) => Person::FirstLast {
+                first_name,
+                last_name,
+            },
+            (None, false) => Person::FirstMiddleLast {
+                first_name,
+                middle_names,
+                last_name,
+            },
+        }
+    }
+
+    /// A `Last, First Middle[, Suffix]` name, as written by reference managers.
+    /// Names without a comma, e.g. of organizations, are kept whole.
+    pub(crate) fn from_last_first(name: &str) -> Person {
+        match name.split(',').map(str::trim).collect::<Vec<&str>>()[..] {
+            [last, given] => Self::from_name_parts(last, given, None),
+            [last, given, suffix, ..] => Self::from_name_parts(last, given, Some(suffix)),
+            _ => Person::Literal(s!(name.trim())),
+        }
+    }
+
+    /// The name as `Last, First Middle[, Suffix]`, the inverse of `from_last_first`.
+    pub(crate) fn to_last_first(&self) -> String {
+        match (self.given_names(), self.suffix()) {
+            (Some(given), Some(suffix)) => format!("{}, {}, {}", self.last_name(), given, suffix),
+            (Some(given), None) => format!("{}, {}", self.last_name(), given),
+            (None, _) => s!(self.last_name()),
+        }
+    }
+
+    /// Names fully enclosed in braces, e.g. `{Barnes and Noble, Inc.}`, are kept whole.
+    /// The parts are decoded from LaTeX once split, e.g. `M{\"u}ller` to `Müller`.
+    fn braced_or_person_from_str(s: &str) -> Result<Person, String> {
+        match Self::is_single_group(s) {
+            true => Ok(Person::Literal(latex_to_unicode(s))),
+            false => Self::person_from_str(&strip_braces(s)).map(Person::decoded),
+        }
+    }
+
+    fn decoded(self) -> Person {
+        let decode = |names: Vec<String>| names.iter().map(|name| latex_to_unicode(name)).collect();
+        match self {
+            Person::FirstLast {
+                first_name,
+                last_name,
+            } => Person::FirstLast {
+                first_name: latex_to_unicode(&first_name),
+                last_name: latex_to_unicode(&last_name),
+            },
+            Person::FirstMiddleLast {
+                first_name,
+                middle_names,
+                last_name,
+            } => Person::FirstMiddleLast {
+                first_name: latex_to_unicode(&first_name),
+                middle_names: decode(middle_names),
+                last_name: latex_to_unicode(&last_name),
+            },
+            Person::Suffixed {
+                first_name,
+                middle_names,
+                last_name,
+                suffix,
+            } => Person::Suffixed {
+                first_name: latex_to_unicode(&first_name),
+                middle_names: decode(middle_names),
+                last_name: latex_to_unicode(&last_name),
+                suffix: latex_to_unicode(&suffix),
+            },
+            Person::FullName(name) => Person::FullName(latex_to_unicode(&name)),
+            Person::Literal(name) => Person::Literal(latex_to_unicode(&name)),
+        }
+    }
+
+    fn is_single_group(s: &str) -> bool {
+        if !s.starts_with('{') || !s.ends_with('}') {
+            return false;
+        }
+        let mut depth = 0;
+        for (i, c) in s.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 && i < s.len() - 1 {
+                return false;
+            }
+        }
+        true
     }
 
     fn person_from_str(s: &str) -> Result<Person, String> {
+        if let [last, suffix, first] =
+            s.splitn(3, Self::FIRST_LAST_SEPARATOR).collect::<Vec<_>>()[..]
+        {
+            let (first, middle) = Self::first_or_first_and_middle(first);
+            return Ok(Person::Suffixed {
+                first_name: s!(first),
+                middle_names: middle.iter().map(|s| s!(*s)).collect(),
+                last_name: s!(last),
+                suffix: s!(suffix),
+            });
+        }
         let names_str: Vec<&str> = s.splitn(2, Self::FIRST_LAST_SEPARATOR).collect();
         let (first, middle) = match names_str.get(1) {
             Some(s) => Self::first_or_first_and_middle(s),
@@ -36,7 +270,7 @@ impl Person {
             None => return Err(s!("Could not parse person info")),
         };
 
-        return if middle.is_empty() {
+        if middle.is_empty() {
             Ok(Person::FirstLast {
                 first_name: s!(first),
                 last_name: s!(last),
@@ -47,12 +281,12 @@ impl Person {
                 middle_names: middle.iter().map(|s| s!(*s)).collect(),
                 last_name: s!(last),
             })
-        };
+        }
     }
 
     fn first_or_first_and_middle(s: &str) -> (&str, Vec<&str>) {
         let names_str: Vec<&str> = s
-            .split(|c| c == ' ' || c == '~' || c == '.')
+            .split([' ', '~', '\u{a0}', '.'])
             .filter(|s| !s.is_empty())
             .collect();
         let first_and_tail = names_str.split_first();
@@ -99,6 +333,27 @@ mod person_test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn create_vec_of_people_with_braces_from_str() {
+        // given
+        let input = "{Barnes and Noble, Inc.} and {van} Gogh, Vincent and {A} and {B}";
+        let expected = Ok(vec![
+            Person::Literal(s!("Barnes and Noble, Inc.")),
+            Person::FirstLast {
+                first_name: s!("Vincent"),
+                last_name: s!("van Gogh"),
+            },
+            Person::Literal(s!("A")),
+            Person::Literal(s!("B")),
+        ]);
+
+        // when
+        let actual = Person::people_from_str(input);
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn create_vec_of_one_person_from_str() {
         // given
@@ -115,6 +370,67 @@ mod person_test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn split_names_without_comma() {
+        // given
+        [
+            ("Kent Beck", (Some("Kent"), "Beck")),
+            ("Donald E. Knuth", (Some("Donald E."), "Knuth")),
+            ("Ludwig van Beethoven", (Some("Ludwig"), "van Beethoven")),
+            ("Jean de La Fontaine", (Some("Jean"), "de La Fontaine")),
+            ("Aristotle", (None, "Aristotle")),
+        ]
+        .iter()
+        .for_each(|(input, (given, last))| {
+            // when
+            let person = Person::FullName(s!(*input));
+
+            // then
+            assert_eq!(person.given_names().as_deref(), *given);
+            assert_eq!(person.last_name(), *last);
+        });
+    }
+
+    #[test]
+    fn decode_latex() {
+        // given
+        let input = r#"M{\"u}ller, Hans and {\O}stergaard, J{\o}rn and Donald~E. Knuth and {Caf{\'e} \& Co}"#;
+        let expected = Ok(vec![
+            Person::FirstLast {
+                first_name: s!("Hans"),
+                last_name: s!("Müller"),
+            },
+            Person::FirstLast {
+                first_name: s!("Jørn"),
+                last_name: s!("Østergaard"),
+            },
+            Person::FullName(s!("Donald\u{a0}E. Knuth")),
+            Person::Literal(s!("Café & Co")),
+        ]);
+
+        // when
+        let actual = Person::people_from_str(input);
+
+        // then
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual.unwrap()[2].given_names().as_deref(),
+            Some("Donald E.")
+        );
+    }
+
+    #[test]
+    fn write_people_to_bib() {
+        // given
+        let input = "Kent Beck and Knuth, Donald E. and {Barnes and Noble} and Ford, Jr., Henry";
+
+        // when
+        let actual = Person::people_from_str(input).map(|people| Person::people_to_bib(&people));
+
+        // then
+        assert_eq!(actual, Ok(s!(input)));
+    }
+
     #[test]
     fn single_person_first_last_from_str() {
         let input = "Beck, Kent";
@@ -146,10 +462,27 @@ mod person_test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn single_person_with_suffix_from_str() {
+        let input = "Ford, Jr., Henry";
+        let expected = Ok(Person::Suffixed {
+            first_name: s!("Henry"),
+            middle_names: vec![],
+            last_name: s!("Ford"),
+            suffix: s!("Jr."),
+        });
+
+        // when
+        let actual = Person::person_from_str(input);
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn get_first_and_middle() {
         // given
-        vec![
+        [
             ("Kent", ("Kent", vec![])),
             ("Robert Cecil", ("Robert", vec!["Cecil"])),
             ("Robert C.", ("Robert", vec!["C"])),
@@ -0,0 +1,65 @@
+use crate::s;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum PubState {
+    InPreparation,
+    Submitted,
+    Forthcoming,
+    InPress,
+    Prepublished,
+    Other(String),
+}
+
+impl PubState {
+    pub(crate) fn from_str(s: &str) -> PubState {
+        Self::known_from_str(s).unwrap_or(PubState::Other(s!(s)))
+    }
+
+    pub(crate) fn known_from_str(s: &str) -> Option<PubState> {
+        let normalized = s.trim().to_lowercase().replace(['.', ' ', '-'], "");
+        match normalized.as_str() {
+            "inpreparation" | "inprep" => Some(PubState::InPreparation),
+            "submitted" => Some(PubState::Submitted),
+            "forthcoming" => Some(PubState::Forthcoming),
+            "inpress" => Some(PubState::InPress),
+            "prepublished" => Some(PubState::Prepublished),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PubState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PubState::Other(s) => write!(f, "{}", s),
+            _ => write!(f, "{}", format!("{:?}", self).to_lowercase()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod pub_state_test {
+    use super::*;
+
+    #[test]
+    fn parse_from_str() {
+        // given
+        [
+            ("inpress", PubState::InPress),
+            ("in press", PubState::InPress),
+            ("In Press", PubState::InPress),
+            ("forthcoming", PubState::Forthcoming),
+            ("in prep.", PubState::InPreparation),
+            ("accepted", PubState::Other(s!("accepted"))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = PubState::from_str(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+}
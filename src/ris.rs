@@ -0,0 +1,629 @@
+use crate::date::Date;
+use crate::entry::Entry;
+use crate::entry_field::{EntryField, UnknownField};
+use crate::entry_type::EntryType;
+use crate::keywords::Keywords;
+use crate::literal_list::LiteralList;
+use crate::pages::Pages;
+use crate::person::Person;
+use crate::pub_state::PubState;
+use crate::s;
+use crate::standard_number::Isbn;
+use crate::year::Year;
+
+/// Reads RIS records, as exported by most databases and publishers, into entries.
+/// Tags without a biblatex field are kept as `Other` fields named after the tag.
+pub(crate) struct RisReader;
+
+impl RisReader {
+    /// Tags read into their own fields, or used for the type and key.
+    const MAPPED_TAGS: [&'static str; 39] = [
+        "TY", "ER", "ID", "AU", "A1", "ED", "A2", "A4", "TI", "T1", "T2", "BT", "JF", "JO", "JA",
+        "J2", "T3", "ST", "DA", "PY", "Y1", "Y2", "SP", "EP", "VL", "IS", "CY", "PB", "SN", "DO",
+        "UR", "KW", "AB", "N2", "N1", "LA", "ET", "M3", "CT",
+    ];
+
+    pub(crate) fn read(input: &str) -> Result<Vec<Entry>, String> {
+        let mut symbols: Vec<String> = vec![];
+        Self::records(input)
+            .iter()
+            .map(|record| {
+                let entry = Self::entry(record, &symbols)?;
+                symbols.push(s!(entry.symbol()));
+                Ok(entry)
+            })
+            .collect()
+    }
+
+    /// Splits the input into records of `(tag, value)` pairs. A record starts with `TY`
+    /// and ends with `ER` or the next `TY`, and lines without a tag continue the last value.
+    fn records(input: &str) -> Vec<Vec<(&str, String)>> {
+        let mut records = vec![];
+        let mut record: Option<Vec<(&str, String)>> = None;
+        for line in input.trim_start_matches('\u{feff}').lines() {
+            match Self::tag_line(line) {
+                Some(("TY", value)) => {
+                    records.extend(record.take());
+                    record = Some(vec![("TY", s!(value))]);
+                }
+                Some(("ER", _)) => records.extend(record.take()),
+                Some((tag, value)) => {
+                    if let Some(record) = record.as_mut() {
+                        record.push((tag, s!(value)));
+                    }
+                }
+                None => {
+                    let line = line.trim();
+                    match record.as_mut().and_then(|record| record.last_mut()) {
+                        Some((_, value)) if !line.is_empty() => {
+                            if !value.is_empty() {
+                                value.push(' ');
+                            }
+                            value.push_str(line);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        records.extend(record);
+        records
+    }
+
+    /// The tag and value of a `TG  - value` line. Vendors differ in the spaces around
+    /// the dash, so any number of them is accepted.
+    fn tag_line(line: &str) -> Option<(&str, &str)> {
+        let tag = line.get(..2)?;
+        let mut chars = tag.chars();
+        let is_tag = chars.next().is_some_and(|c| c.is_ascii_uppercase())
+            && chars
+                .next()
+                .is_some_and(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+        let value = line[2..].trim_start().strip_prefix('-')?;
+        match is_tag && (value.is_empty() || value.starts_with(char::is_whitespace)) {
+            true => Some((tag, value.trim())),
+            false => None,
+        }
+    }
+
+    fn entry(record: &[(&str, String)], symbols: &[String]) -> Result<Entry, String> {
+        let values = |tags: &[&str]| -> Vec<&str> {
+            record
+                .iter()
+                .filter(|(tag, value)| tags.contains(tag) && !value.is_empty())
+                .map(|(_, value)| value.as_str())
+                .collect()
+        };
+        let first = |tags: &[&str]| values(tags).first().copied();
+        let (entry_type, subtype) = Self::entry_type(first(&["TY"]).unwrap_or_default());
+        let is_article = entry_type == EntryType::Article;
+        let bib_field = EntryField::from_field_name_and_text;
+
+        let mut fields: Vec<EntryField> = vec![];
+        if let Some(subtype) = subtype {
+            fields.push(EntryField::EntrySubtype(s!(subtype)));
+        }
+        if first(&["TY"]) == Some("INPR") {
+            fields.push(EntryField::PubState(PubState::InPress));
+        }
+        for (tags, field) in [
+            (
+                &["AU", "A1"][..],
+                EntryField::Author as fn(Vec<Person>) -> EntryField,
+            ),
+            (&["ED", "A2"], EntryField::Editor),
+            (&["A4"], EntryField::Translator),
+        ] {
+            let people: Vec<Person> = values(tags)
+                .iter()
+                .map(|name| Person::from_last_first(name))
+                .collect();
+            if !people.is_empty() {
+                fields.push(field(people));
+            }
+        }
+        if let Some(title) = first(&["TI", "T1", "CT"]) {
+            fields.push(bib_field("title", title)?);
+        }
+        match is_article {
+            true => {
+                let journal = first(&["T2", "JF", "JO", "JA", "J2"]);
+                if let Some(journal) = journal {
+                    fields.push(bib_field("journaltitle", journal)?);
+                }
+                if let Some(short) = first(&["J2", "JA"]).filter(|it| Some(*it) != journal) {
+                    fields.push(bib_field("shortjournal", short)?);
+                }
+            }
+            false => {
+                if let Some(book_title) = first(&["T2", "BT"]) {
+                    fields.push(bib_field("booktitle", book_title)?);
+                }
+            }
+        }
+        fields.extend(Self::issued(&values(&["DA"]), &values(&["PY", "Y1"])));
+        if let Some(date) = first(&["Y2"]).and_then(Date::parse_slashed_from_str) {
+            fields.push(EntryField::UrlDate(date));
+        }
+        let pages = match (first(&["SP"]), first(&["EP"])) {
+            (Some(start), Some(end)) => Some(format!("{}--{}", start, end)),
+            (start, _) => start.map(|start| s!(start)),
+        };
+        if let Some(pages) = pages {
+            fields.push(EntryField::Pages(Pages::pages_from_str(&pages)));
+        }
+        for (tags, field_name) in [
+            (&["T3"][..], "series"),
+            (&["ST"], "shorttitle"),
+            (&["VL"], "volume"),
+            (&["IS"], "number"),
+            (&["DO"], "doi"),
+            (&["UR"], "url"),
+            (&["AB", "N2"], "abstract"),
+            (&["ET"], "edition"),
+            (&["M3"], "type"),
+        ] {
+            if let Some(value) = first(tags) {
+                fields.push(bib_field(field_name, value)?);
+            }
+        }
+        for (tags, field) in [
+            (
+                &["CY"][..],
+                EntryField::Location as fn(LiteralList) -> EntryField,
+            ),
+            (&["PB"], EntryField::Publisher),
+            (&["LA"], EntryField::Language),
+        ] {
+            let items = values(tags);
+            if !items.is_empty() {
+                fields.push(field(LiteralList::from_items(&items)));
+            }
+        }
+        let notes = values(&["N1"]);
+        if !notes.is_empty() {
+            fields.push(EntryField::Note(notes.join("\n")));
+        }
+        let keywords = values(&["KW"]);
+        if !keywords.is_empty() {
+            fields.push(EntryField::Keywords(Keywords::from_items(&keywords)));
+        }
+        let (isbn, issn) = Isbn::split_isbn_and_issn(&values(&["SN"]));
+        fields.extend(isbn.map(EntryField::Isbn));
+        fields.extend(issn.map(EntryField::Issn));
+        fields.extend(
+            record
+                .iter()
+                .filter(|(tag, value)| !Self::MAPPED_TAGS.contains(tag) && !value.is_empty())
+                .map(|(tag, value)| EntryField::Other(UnknownField::new(tag, value))),
+        );
+
+        let symbol = first(&["ID"])
+            .map(|id| s!(id))
+            .unwrap_or_else(|| Entry::generated_symbol(&fields, symbols, "ris"));
+        Ok(Entry::new(entry_type, symbol, fields))
+    }
+
+    fn entry_type(tag: &str) -> (EntryType, Option<&'static str>) {
+        let entry_type = match tag {
+            "JOUR" | "JFULL" | "EJOUR" | "ABST" | "INPR" | "MGZN" | "NEWS" => EntryType::Article,
+            "BOOK" | "EBOOK" => EntryType::Book,
+            "EDBOOK" => EntryType::Collection,
+            "CHAP" | "ECHAP" => EntryType::InCollection,
+            "CONF" => EntryType::Proceedings,
+            "CPAPER" => EntryType::InProceedings,
+            "ENCYC" | "DICT" => EntryType::InReference,
+            "SER" => EntryType::Periodical,
+            "THES" => EntryType::Thesis,
+            "RPRT" => EntryType::Report,
+            "PAT" => EntryType::Patent,
+            "ELEC" | "WEB" | "BLOG" => EntryType::Online,
+            "COMP" => EntryType::Software,
+            "DATA" | "DBASE" => EntryType::Dataset,
+            "UNPB" | "MANSCPT" => EntryType::Unpublished,
+            "PAMP" => EntryType::Booklet,
+            "STAND" => EntryType::Standard,
+            "MUSIC" => EntryType::Music,
+            "SOUND" => EntryType::Audio,
+            "MPCT" => EntryType::Movie,
+            "VIDEO" => EntryType::Video,
+            "ART" => EntryType::Artwork,
+            "FIGURE" => EntryType::Image,
+            "CASE" => EntryType::Jurisdiction,
+            "STAT" | "BILL" => EntryType::Legislation,
+            "PCOMM" => EntryType::Letter,
+            _ => EntryType::Misc,
+        };
+        let subtype = match tag {
+            "MGZN" => Some("magazine"),
+            "NEWS" => Some("newspaper"),
+            _ => None,
+        };
+        (entry_type, subtype)
+    }
+
+    /// A `DA` date with a month as the date, otherwise the `PY` year, which some
+    /// vendors write as a full `YYYY/MM/DD/other` date, too.
+    fn issued(dates: &[&str], years: &[&str]) -> Option<EntryField> {
+        let precise = dates
+            .iter()
+            .chain(years)
+            .filter_map(|date| Date::parse_slashed_from_str(date))
+            .find(|date| !matches!(date, Date::Year(_)));
+        if let Some(date) = precise {
+            return Some(EntryField::Date(date));
+        }
+        years
+            .iter()
+            .chain(dates)
+            .next()
+            .map(|year| year.split('/').next().unwrap_or_default())
+            .map(|year| EntryField::Year(Year::parse(year)))
+    }
+}
+
+/// Writes entries as RIS records.
+pub(crate) struct RisWriter;
+
+impl RisWriter {
+    /// The order tags are written in, that of the RIS specification. Other tags follow.
+    const TAG_ORDER: [&'static str; 26] = [
+        "AU", "ED", "A4", "TI", "T2", "J2", "T3", "ST", "PY", "DA", "Y2", "SP", "EP", "VL", "IS",
+        "ET", "CY", "PB", "SN", "DO", "UR", "KW", "AB", "N1", "LA", "M3",
+    ];
+
+    pub(crate) fn write(entries: &[Entry]) -> String {
+        entries
+            .iter()
+            .map(Self::record)
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn record(entry: &Entry) -> String {
+        let mut lines: Vec<(String, String)> = vec![
+            (s!("TY"), s!(Self::type_tag(entry))),
+            (s!("ID"), s!(entry.symbol())),
+        ];
+        let mut tags: Vec<(String, String)> = entry
+            .fields()
+            .iter()
+            .flat_map(|field| Self::tags(entry, field))
+            .collect();
+        tags.sort_by_key(|(tag, _)| {
+            Self::TAG_ORDER
+                .iter()
+                .position(|it| it == tag)
+                .unwrap_or(Self::TAG_ORDER.len())
+        });
+        lines.extend(tags);
+        lines.push((s!("ER"), s!("")));
+        lines
+            .iter()
+            .map(|(tag, value)| format!("{}  - {}\n", tag, value))
+            .collect()
+    }
+
+    fn type_tag(entry: &Entry) -> &'static str {
+        let subtype = entry.fields().iter().find_map(|field| match field {
+            EntryField::EntrySubtype(subtype) => Some(subtype.to_lowercase()),
+            _ => None,
+        });
+        match entry.entry_type() {
+            EntryType::Article => match subtype.as_deref() {
+                Some("magazine") => "MGZN",
+                Some("newspaper") => "NEWS",
+                _ => "JOUR",
+            },
+            EntryType::Book
+            | EntryType::MvBook
+            | EntryType::Reference
+            | EntryType::MvReference
+            | EntryType::Manual
+            | EntryType::Commentary => "BOOK",
+            EntryType::Collection | EntryType::MvCollection => "EDBOOK",
+            EntryType::InBook
+            | EntryType::BookInBook
+            | EntryType::SuppBook
+            | EntryType::InCollection
+            | EntryType::SuppCollection => "CHAP",
+            EntryType::Proceedings | EntryType::MvProceedings => "CONF",
+            EntryType::InProceedings | EntryType::Conference => "CPAPER",
+            EntryType::InReference => "ENCYC",
+            EntryType::Periodical | EntryType::SuppPeriodical => "SER",
+            EntryType::Thesis | EntryType::MastersThesis | EntryType::PhdThesis => "THES",
+            EntryType::Report | EntryType::TechReport => "RPRT",
+            EntryType::Patent => "PAT",
+            EntryType::Online | EntryType::Electronic | EntryType::Www => "ELEC",
+            EntryType::Software => "COMP",
+            EntryType::Dataset | EntryType::DataType => "DATA",
+            EntryType::Unpublished => "UNPB",
+            EntryType::Booklet => "PAMP",
+            EntryType::Standard => "STAND",
+            EntryType::Music => "MUSIC",
+            EntryType::Audio => "SOUND",
+            EntryType::Movie => "MPCT",
+            EntryType::Video => "VIDEO",
+            EntryType::Artwork => "ART",
+            EntryType::Image => "FIGURE",
+            EntryType::Jurisdiction => "CASE",
+            EntryType::Legislation => "STAT",
+            EntryType::Letter => "PCOMM",
+            _ => "GEN",
+        }
+    }
+
+    /// The year as `PY` and the date as `DA`, e.g. `2020/03`.
+    fn date_tags(date: &Date) -> Vec<(String, String)> {
+        let mut tags = vec![];
+        if let Some(year) = date.year() {
+            tags.push((s!("PY"), year.to_string()));
+        }
+        tags.extend(date.to_slashed().map(|date| (s!("DA"), date)));
+        tags
+    }
+
+    fn tags(entry: &Entry, field: &EntryField) -> Vec<(String, String)> {
+        let has = |f: fn(&EntryField) -> bool| entry.fields().iter().any(f);
+        let tag = |tag: &str, value: String| vec![(s!(tag), value)];
+        match field {
+            EntryField::Author(people) => Self::names("AU", people),
+            EntryField::Editor(people) => Self::names("ED", people),
+            EntryField::Translator(people) => Self::names("A4", people),
+            EntryField::Title(title) => tag("TI", Self::title(entry, title)),
+            EntryField::JournalTitle(s) | EntryField::BookTitle(s) => tag("T2", s.clone()),
+            EntryField::Journal(s) if !has(|f| matches!(f, EntryField::JournalTitle(_))) => {
+                tag("T2", s.clone())
+            }
+            EntryField::ShortJournal(s) => tag("J2", s.clone()),
+            EntryField::Series(s) => tag("T3", s.clone()),
+            EntryField::ShortTitle(s) => tag("ST", s.clone()),
+            EntryField::Date(date) => Self::date_tags(date),
+            EntryField::Year(year) if !has(|f| matches!(f, EntryField::Date(_))) => {
+                match entry.year_month() {
+                    Some(date) => Self::date_tags(&date),
+                    None => year
+                        .value()
+                        .map(|year| tag("PY", year.to_string()))
+                        .unwrap_or_default(),
+                }
+            }
+            EntryField::UrlDate(date) => date
+                .to_slashed()
+                .map(|date| tag("Y2", date))
+                .unwrap_or_default(),
+            EntryField::Pages(pages) => match &pages[..] {
+                [Pages::Range(start, end)] => {
+                    [tag("SP", start.to_string()), tag("EP", end.to_string())].concat()
+                }
+                pages => tag(
+                    "SP",
+                    pages
+                        .iter()
+                        .map(|pages| pages.to_string().replace("--", "-"))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                ),
+            },
+            EntryField::Volume(s) => tag("VL", s.clone()),
+            EntryField::Number(s) | EntryField::Issue(s) => tag("IS", s.clone()),
+            EntryField::Location(list) | EntryField::Address(list) => {
+                Self::items("CY", list.items())
+            }
+            EntryField::Publisher(list)
+            | EntryField::Institution(list)
+            | EntryField::School(list)
+            | EntryField::Organization(list) => Self::items("PB", list.items()),
+            EntryField::Language(list) => Self::items("LA", list.items()),
+            EntryField::Keywords(keywords) => Self::items("KW", keywords.items()),
+            EntryField::Isbn(isbn) => tag("SN", isbn.to_string()),
+            EntryField::Issn(issn) => tag("SN", issn.to_string()),
+            EntryField::Doi(doi) => tag("DO", doi.to_string()),
+            EntryField::Url(s) => tag("UR", s.clone()),
+            EntryField::Abstract(s) => tag("AB", s.clone()),
+            EntryField::Note(s) => tag("N1", s.clone()),
+            EntryField::Edition(edition) => tag("ET", edition.to_string()),
+            EntryField::Type(s) => tag("M3", s.clone()),
+            EntryField::Other(other) if Self::is_tag(&field.name()) => {
+                tag(&field.name().to_uppercase(), s!(other.value()))
+            }
+            _ => vec![],
+        }
+    }
+
+    fn title(entry: &Entry, title: &str) -> String {
+        let subtitle = entry.fields().iter().find_map(|field| match field {
+            EntryField::Subtitle(subtitle) => Some(subtitle),
+            _ => None,
+        });
+        match subtitle {
+            Some(subtitle) => format!("{}: {}", title, subtitle),
+            None => s!(title),
+        }
+    }
+
+    fn items(tag: &str, items: &[String]) -> Vec<(String, String)> {
+        items.iter().map(|item| (s!(tag), item.clone())).collect()
+    }
+
+    fn names(tag: &str, people: &[Person]) -> Vec<(String, String)> {
+        people
+            .iter()
+            .map(|person| (s!(tag), person.to_last_first()))
+            .collect()
+    }
+
+    fn is_tag(name: &str) -> bool {
+        name.len() == 2 && RisReader::tag_line(&format!("{}  - ", name.to_uppercase())).is_some()
+    }
+}
+
+#[cfg(test)]
+mod ris_test {
+    use super::*;
+    use crate::doi::Doi;
+    use crate::entry::Parser;
+    use crate::standard_number::Issn;
+    use crate::tokenizer::Tokenizer;
+    use std::io::Cursor;
+
+    #[test]
+    fn read_records() {
+        // given
+        let input = "\u{feff}TY  - JOUR\r\n\
+                     AU  - Knuth, Donald E.\r\n\
+                     AU  - Ford, Henry, Jr.\r\n\
+                     TI  - Literate\r\n\
+                     \x20   Programming\r\n\
+                     JO  - The Computer Journal\r\n\
+                     PY  - 1984/05/01/\r\n\
+                     SP  - 97\r\n\
+                     EP  - 111\r\n\
+                     SN  - 0010-4620 (Print); 1460-2067 (Electronic)\r\n\
+                     DO  - 10.1093/comjnl/27.2.97\r\n\
+                     KW  - literate programming\r\n\
+                     KW  - WEB\r\n\
+                     AN  - 12345\r\n\
+                     ER  - \r\n\
+                     TY - BOOK\n\
+                     PY - 2004\n\
+                     PB - Barnes and Noble\n";
+        let expected = vec![
+            Entry::new(
+                EntryType::Article,
+                s!("knuth1984"),
+                vec![
+                    EntryField::Author(vec![
+                        Person::FirstMiddleLast {
+                            first_name: s!("Donald"),
+                            middle_names: vec![s!("E")],
+                            last_name: s!("Knuth"),
+                        },
+                        Person::Suffixed {
+                            first_name: s!("Henry"),
+                            middle_names: vec![],
+                            last_name: s!("Ford"),
+                            suffix: s!("Jr."),
+                        },
+                    ]),
+                    EntryField::Title(s!("Literate Programming")),
+                    EntryField::JournalTitle(s!("The Computer Journal")),
+                    EntryField::Date(Date::YearMonthDay(1984, 5, 1)),
+                    EntryField::Pages(Pages::pages_from_str("97--111")),
+                    EntryField::Doi(Doi::parse("10.1093/comjnl/27.2.97")),
+                    EntryField::Keywords(Keywords::from_str("literate programming; WEB", &[';'])),
+                    EntryField::Issn(Issn::parse("0010-4620")),
+                    EntryField::Other(UnknownField::new("AN", "12345")),
+                ],
+            ),
+            Entry::new(
+                EntryType::Book,
+                s!("ris2004"),
+                vec![
+                    EntryField::Year(Year::Numeric(2004)),
+                    EntryField::Publisher(LiteralList::from_str("{Barnes and Noble}")),
+                ],
+            ),
+        ];
+
+        // when
+        let actual = RisReader::read(input);
+
+        // then
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn write_record() {
+        // given
+        let input = r#"@incollection{beck-2004,
+            author = {Beck, Kent and Martin, Robert C.},
+            title = {Extreme Programming}, subtitle = {Explained},
+            booktitle = {Agile Methods},
+            date = {2004-03},
+            pages = {1--5},
+            publisher = {Addison-Wesley},
+            isbn = {978-0-13-405199-4},
+        }"#;
+        let expected = "TY  - CHAP\n\
+                        ID  - beck-2004\n\
+                        AU  - Beck, Kent\n\
+                        AU  - Martin, Robert C.\n\
+                        TI  - Extreme Programming: Explained\n\
+                        T2  - Agile Methods\n\
+                        PY  - 2004\n\
+                        DA  - 2004/03\n\
+                        SP  - 1\n\
+                        EP  - 5\n\
+                        PB  - Addison-Wesley\n\
+                        SN  - 9780134051994\n\
+                        ER  - \n";
+
+        // when
+        let actual = RisWriter::write(&parse(input));
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn decode_latex() {
+        // given
+        let input = r#"@article{mueller,
+            author = {M{\"u}ller, J{\"o}rg},
+            title = {Caf{\'e} \& Bar},
+            url = {https://example.com/caf\_e},
+        }"#;
+        let expected = "TY  - JOUR\n\
+                        ID  - mueller\n\
+                        AU  - Müller, Jörg\n\
+                        TI  - Café & Bar\n\
+                        UR  - https://example.com/caf\\_e\n\
+                        ER  - \n";
+
+        // when
+        let actual = RisWriter::write(&parse(input));
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn round_trip() {
+        // given
+        let input = r#"
+            @article{a,
+                author = {de la Fontaine, Jean and Ford, Jr., Henry and {IEEE}},
+                title = {Title},
+                journaltitle = {Journal}, shortjournal = {J.},
+                date = {2020-06-01},
+                volume = {3}, number = {4},
+                pages = {iv--xii, 15+},
+                doi = {10.1109/IEEESTD.2018.8457469},
+                keywords = {a, b},
+                url = {https://example.com},
+                urldate = {2021-01-02},
+            }
+            @book{b,
+                editor = {Doe, John}, title = {Book}, year = {2001}, month = mar,
+                location = {New York and London},
+            }"#;
+        let expected = RisWriter::write(&parse(input));
+
+        // when
+        let read = RisReader::read(&expected).unwrap();
+        let actual = RisWriter::write(&read);
+
+        // then
+        assert_eq!(actual, expected);
+        assert!(read[1]
+            .fields()
+            .contains(&EntryField::Date(Date::YearMonth(2001, 3))));
+    }
+
+    fn parse(input: &str) -> Vec<Entry> {
+        Parser::new(Tokenizer::new(Box::new(Cursor::new(s!(input)))))
+            .parse()
+            .unwrap()
+    }
+}
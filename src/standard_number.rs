@@ -0,0 +1,474 @@
+use crate::s;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum Isbn {
+    Isbn10(String),
+    Isbn13(String),
+    Invalid(String),
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum Issn {
+    Valid(String),
+    Invalid(String),
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum Ismn {
+    Valid(String),
+    Invalid(String),
+}
+
+/// ISO 10444 report number, e.g. `LUTFD2/TFRT--5872--SE`.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum Isrn {
+    Valid(String),
+    Invalid(String),
+}
+
+/// The ISBN form requested with `--isbn`.
+#[derive(PartialEq, Debug, Clone, Copy, ValueEnum)]
+pub(crate) enum IsbnForm {
+    Isbn10,
+    Isbn13,
+}
+
+impl Isbn {
+    const LABELS: [&'static str; 3] = ["isbn-13", "isbn-10", "isbn"];
+
+    pub(crate) fn parse(s: &str) -> Isbn {
+        match compact(s, &Self::LABELS) {
+            Some(compact) if compact.len() == 10 && Self::is_valid_isbn10(&compact) => {
+                Isbn::Isbn10(compact)
+            }
+            Some(compact) if is_valid_ean13(&compact) && Self::has_bookland_prefix(&compact) => {
+                Isbn::Isbn13(compact)
+            }
+            _ => Isbn::Invalid(s!(s.trim())),
+        }
+    }
+
+    /// Reference managers put ISBNs and ISSNs in one field, sometimes several in one
+    /// value, e.g. `0010-4620 (Print); 1460-2067 (Electronic)`. The first of each kind is kept.
+    pub(crate) fn split_isbn_and_issn(values: &[&str]) -> (Option<Isbn>, Option<Issn>) {
+        let numbers: Vec<&str> = values
+            .iter()
+            .flat_map(|value| value.split([';', ',']))
+            .map(|number| number.split('(').next().unwrap_or_default().trim())
+            .filter(|number| !number.is_empty())
+            .collect();
+        let issn = numbers
+            .iter()
+            .map(|number| Issn::parse(number))
+            .find(|issn| matches!(issn, Issn::Valid(_)));
+        let isbn = numbers
+            .iter()
+            .filter(|number| !matches!(Issn::parse(number), Issn::Valid(_)))
+            .map(|number| Isbn::parse(number))
+            .next();
+        (isbn, issn)
+    }
+
+    /// The ISBN in the given form, or unchanged if it has none (invalid, or `979` to ISBN-10).
+    pub(crate) fn to_form(&self, form: IsbnForm) -> Isbn {
+        let converted = match form {
+            IsbnForm::Isbn10 => self.to_isbn10().map(Isbn::Isbn10),
+            IsbnForm::Isbn13 => self.to_isbn13().map(Isbn::Isbn13),
+        };
+        converted.unwrap_or_else(|| self.clone())
+    }
+
+    fn to_isbn13(&self) -> Option<String> {
+        match self {
+            Isbn::Isbn10(isbn) => {
+                let body = format!("978{}", &isbn[..9]);
+                let check = ean13_check_digit(&body);
+                Some(format!("{}{}", body, check))
+            }
+            Isbn::Isbn13(isbn) => Some(isbn.clone()),
+            Isbn::Invalid(_) => None,
+        }
+    }
+
+    fn to_isbn10(&self) -> Option<String> {
+        match self {
+            Isbn::Isbn10(isbn) => Some(isbn.clone()),
+            Isbn::Isbn13(isbn) if isbn.starts_with("978") => {
+                let body = &isbn[3..12];
+                let check = mod11_check_digit(body, 10);
+                Some(format!("{}{}", body, check))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn validation_warning(&self) -> Option<String> {
+        match self {
+            Isbn::Invalid(s) => Some(format!("Invalid ISBN '{}'", s)),
+            _ => None,
+        }
+    }
+
+    fn is_valid_isbn10(isbn: &str) -> bool {
+        let (body, check) = isbn.split_at(9);
+        body.chars().all(|c| c.is_ascii_digit()) && mod11_check_digit(body, 10).to_string() == check
+    }
+
+    fn has_bookland_prefix(isbn: &str) -> bool {
+        isbn.starts_with("978") || isbn.starts_with("979")
+    }
+}
+
+impl Issn {
+    const LABELS: [&'static str; 2] = ["e-issn", "issn"];
+
+    pub(crate) fn parse(s: &str) -> Issn {
+        let Some(compact) = compact(s, &Self::LABELS).filter(|it| it.len() == 8) else {
+            return Issn::Invalid(s!(s.trim()));
+        };
+        let (body, check) = compact.split_at(7);
+        let is_valid = body.chars().all(|c| c.is_ascii_digit())
+            && mod11_check_digit(body, 8).to_string() == check;
+        match is_valid {
+            true => Issn::Valid(format!("{}-{}", &compact[..4], &compact[4..])),
+            false => Issn::Invalid(s!(s.trim())),
+        }
+    }
+
+    pub(crate) fn validation_warning(&self) -> Option<String> {
+        match self {
+            Issn::Invalid(s) => Some(format!("Invalid ISSN '{}'", s)),
+            _ => None,
+        }
+    }
+}
+
+impl Ismn {
+    const LABELS: [&'static str; 1] = ["ismn"];
+    const LEGACY_PREFIX: char = 'M';
+    const EAN_PREFIX: &'static str = "9790";
+
+    pub(crate) fn parse(s: &str) -> Ismn {
+        let Some(compact) = compact(s, &Self::LABELS) else {
+            return Ismn::Invalid(s!(s.trim()));
+        };
+        let ean = match compact.strip_prefix(Self::LEGACY_PREFIX) {
+            Some(rest) if rest.len() == 9 => format!("{}{}", Self::EAN_PREFIX, rest),
+            _ => compact,
+        };
+        match ean.starts_with(Self::EAN_PREFIX) && is_valid_ean13(&ean) {
+            true => Ismn::Valid(ean),
+            false => Ismn::Invalid(s!(s.trim())),
+        }
+    }
+
+    pub(crate) fn validation_warning(&self) -> Option<String> {
+        match self {
+            Ismn::Invalid(s) => Some(format!("Invalid ISMN '{}'", s)),
+            _ => None,
+        }
+    }
+}
+
+impl Isrn {
+    const LABELS: [&'static str; 1] = ["isrn"];
+    const MAX_LENGTH: usize = 36;
+    const MAX_REPORT_CODE_LENGTH: usize = 16;
+    const MAX_SEQUENCE_LENGTH: usize = 14;
+
+    /// Checks the syntax `<report code>--<sequence>[--<country>]`; ISRNs have no check digit.
+    pub(crate) fn parse(s: &str) -> Isrn {
+        let trimmed = s.trim();
+        let number = Self::LABELS
+            .iter()
+            .find_map(|label| {
+                trimmed
+                    .get(..label.len())
+                    .filter(|prefix| prefix.eq_ignore_ascii_case(label))
+                    .map(|_| trimmed[label.len()..].trim_start_matches([' ', ':']))
+            })
+            .unwrap_or(trimmed)
+            .to_uppercase();
+        let parts: Vec<&str> = number.split("--").collect();
+        let is_valid = number.len() <= Self::MAX_LENGTH
+            && match parts.as_slice() {
+                [report_code, sequence] => Self::is_valid_report(report_code, sequence),
+                [report_code, sequence, country] => {
+                    Self::is_valid_report(report_code, sequence)
+                        && country.len() == 2
+                        && country.chars().all(|c| c.is_ascii_uppercase())
+                }
+                _ => false,
+            };
+        match is_valid {
+            true => Isrn::Valid(number),
+            false => Isrn::Invalid(s!(trimmed)),
+        }
+    }
+
+    pub(crate) fn validation_warning(&self) -> Option<String> {
+        match self {
+            Isrn::Invalid(s) => Some(format!("Invalid ISRN '{}'", s)),
+            _ => None,
+        }
+    }
+
+    fn is_valid_report(report_code: &str, sequence: &str) -> bool {
+        Self::is_valid_part(report_code, Self::MAX_REPORT_CODE_LENGTH, &['/', '-'])
+            && Self::is_valid_part(sequence, Self::MAX_SEQUENCE_LENGTH, &['/', '-', '+'])
+    }
+
+    /// Letters and digits, with single separators between them.
+    fn is_valid_part(part: &str, max_length: usize, separators: &[char]) -> bool {
+        let is_separator = |c: char| separators.contains(&c);
+        (1..=max_length).contains(&part.len())
+            && part
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || is_separator(c))
+            && !part.starts_with(is_separator)
+            && !part.ends_with(is_separator)
+            && !part
+                .chars()
+                .zip(part.chars().skip(1))
+                .any(|(a, b)| is_separator(a) && is_separator(b))
+    }
+}
+
+impl std::fmt::Display for Isbn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Isbn::Isbn10(s) | Isbn::Isbn13(s) | Isbn::Invalid(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl std::fmt::Display for Issn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issn::Valid(s) | Issn::Invalid(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl std::fmt::Display for Isrn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Isrn::Valid(s) | Isrn::Invalid(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl std::fmt::Display for Ismn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ismn::Valid(s) | Ismn::Invalid(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Removes the label (e.g. `ISBN:`), hyphens and whitespace, and upper-cases the check character.
+/// `None` if other non-ASCII characters are left, which no valid number has.
+fn compact(s: &str, labels: &[&str]) -> Option<String> {
+    let trimmed = s.trim();
+    let without_label = labels
+        .iter()
+        .find_map(|label| {
+            trimmed
+                .get(..label.len())
+                .filter(|prefix| prefix.eq_ignore_ascii_case(label))
+                .map(|_| &trimmed[label.len()..])
+        })
+        .unwrap_or(trimmed);
+    let compact = without_label
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' ' | ':' | '‐' | '–'))
+        .collect::<String>()
+        .to_uppercase();
+    Some(compact).filter(|it| it.is_ascii())
+}
+
+/// Check character for weighted modulo 11 schemes (ISBN-10, ISSN), where `10` is written as `X`.
+fn mod11_check_digit(body: &str, first_weight: u32) -> char {
+    let sum: u32 = body
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| d * (first_weight - i as u32))
+        .sum();
+    match (11 - sum % 11) % 11 {
+        10 => 'X',
+        d => char::from_digit(d, 10).unwrap_or('0'),
+    }
+}
+
+fn ean13_check_digit(body: &str) -> char {
+    let sum: u32 = body
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { d } else { d * 3 })
+        .sum();
+    char::from_digit((10 - sum % 10) % 10, 10).unwrap_or('0')
+}
+
+fn is_valid_ean13(s: &str) -> bool {
+    s.len() == 13
+        && s.chars().all(|c| c.is_ascii_digit())
+        && s.ends_with(ean13_check_digit(&s[..12]))
+}
+
+#[cfg(test)]
+mod standard_number_test {
+    use super::*;
+
+    #[test]
+    fn parse_isbn() {
+        // given
+        [
+            ("978-0-13-405199-4", Isbn::Isbn13(s!("9780134051994"))),
+            ("ISBN 978 0 13 405199 4", Isbn::Isbn13(s!("9780134051994"))),
+            ("ISBN-13: 978-0134051994", Isbn::Isbn13(s!("9780134051994"))),
+            ("0-321-27865-8", Isbn::Isbn10(s!("0321278658"))),
+            ("0-8044-2957-x", Isbn::Isbn10(s!("080442957X"))),
+            ("978-0-13-405199-5", Isbn::Invalid(s!("978-0-13-405199-5"))),
+            ("0-321-27865-9", Isbn::Invalid(s!("0-321-27865-9"))),
+            ("123", Isbn::Invalid(s!("123"))),
+            ("12345678é", Isbn::Invalid(s!("12345678é"))),
+            (
+                "İSBN 0-321-27865-8",
+                Isbn::Invalid(s!("İSBN 0-321-27865-8")),
+            ),
+            ("0‐321‐27865‐8", Isbn::Isbn10(s!("0321278658"))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Isbn::parse(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn convert_isbn() {
+        // given
+        [
+            (
+                "0-321-27865-8",
+                IsbnForm::Isbn13,
+                Isbn::Isbn13(s!("9780321278654")),
+            ),
+            (
+                "978-0-321-27865-4",
+                IsbnForm::Isbn10,
+                Isbn::Isbn10(s!("0321278658")),
+            ),
+            (
+                "0-321-27865-8",
+                IsbnForm::Isbn10,
+                Isbn::Isbn10(s!("0321278658")),
+            ),
+            (
+                "979-10-90636-07-1",
+                IsbnForm::Isbn10,
+                Isbn::Isbn13(s!("9791090636071")),
+            ),
+            ("123", IsbnForm::Isbn13, Isbn::Invalid(s!("123"))),
+        ]
+        .iter()
+        .for_each(|(input, form, expected)| {
+            // when
+            let actual = Isbn::parse(input).to_form(*form);
+
+            // then
+            assert_eq!(actual, *expected, "{}", input);
+        });
+    }
+
+    #[test]
+    fn parse_isrn() {
+        // given
+        [
+            (
+                "LUTFD2/TFRT--5872--SE",
+                Isrn::Valid(s!("LUTFD2/TFRT--5872--SE")),
+            ),
+            ("ISRN SAND--78-0421", Isrn::Valid(s!("SAND--78-0421"))),
+            (
+                "isrn: kth/na/r--01/19--se",
+                Isrn::Valid(s!("KTH/NA/R--01/19--SE")),
+            ),
+            ("ABC--12+LOCAL", Isrn::Valid(s!("ABC--12+LOCAL"))),
+            (
+                "LUTFD2/TFRT-5872-SE",
+                Isrn::Invalid(s!("LUTFD2/TFRT-5872-SE")),
+            ),
+            ("--5872", Isrn::Invalid(s!("--5872"))),
+            ("ABC/--5872", Isrn::Invalid(s!("ABC/--5872"))),
+            ("ABC--5872--SWE", Isrn::Invalid(s!("ABC--5872--SWE"))),
+            (
+                "ABCDEFGHIJKLMNOPQ--1",
+                Isrn::Invalid(s!("ABCDEFGHIJKLMNOPQ--1")),
+            ),
+            ("ÄBC--1", Isrn::Invalid(s!("ÄBC--1"))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Isrn::parse(input);
+
+            // then
+            assert_eq!(actual, *expected, "{}", input);
+            assert_eq!(
+                actual.validation_warning().is_some(),
+                matches!(expected, Isrn::Invalid(_)),
+                "{}",
+                input
+            );
+        });
+    }
+
+    #[test]
+    fn parse_issn() {
+        // given
+        [
+            ("0378-5955", Issn::Valid(s!("0378-5955"))),
+            ("ISSN 2049-3630", Issn::Valid(s!("2049-3630"))),
+            ("0000006x", Issn::Valid(s!("0000-006X"))),
+            ("2434-561X", Issn::Valid(s!("2434-561X"))),
+            ("0378-5954", Issn::Invalid(s!("0378-5954"))),
+            ("037859é", Issn::Invalid(s!("037859é"))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Issn::parse(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn parse_ismn() {
+        // given
+        [
+            ("979-0-2600-0043-8", Ismn::Valid(s!("9790260000438"))),
+            ("M-2306-7118-7", Ismn::Valid(s!("9790230671187"))),
+            ("M-2306-7118-6", Ismn::Invalid(s!("M-2306-7118-6"))),
+            ("M-2306-711é", Ismn::Invalid(s!("M-2306-711é"))),
+            ("978-0-13-405199-4", Ismn::Invalid(s!("978-0-13-405199-4"))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Ismn::parse(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+}
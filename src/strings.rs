@@ -4,3 +4,345 @@ macro_rules! s {
         String::from($s)
     };
 }
+
+use std::iter::Peekable;
+use std::str::Chars;
+use unicode_normalization::UnicodeNormalization;
+
+const LIST_SEPARATOR: &str = "and";
+
+/// Removes the grouping braces from a value, keeping escaped `\{` and `\}`, command arguments,
+/// e.g. `\v{C}`, and the groups of special characters, e.g. `{\"u}`, to decode them later.
+pub(crate) fn strip_braces(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    let mut kept_depth = 0;
+    while let Some(c) = chars.next() {
+        stripped.push(c);
+        match c {
+            '\\' => {
+                let Some(next) = chars.next() else {
+                    break;
+                };
+                stripped.push(next);
+                if next.is_ascii_alphabetic() {
+                    stripped.extend(std::iter::from_fn(|| {
+                        chars.next_if(char::is_ascii_alphabetic)
+                    }));
+                }
+                if let Some(open) = chars.next_if_eq(&'{') {
+                    stripped.push(open);
+                    kept_depth += 1;
+                }
+            }
+            '{' if kept_depth > 0 || chars.peek() == Some(&'\\') => kept_depth += 1,
+            '}' if kept_depth > 0 => kept_depth -= 1,
+            '{' | '}' => {
+                stripped.pop();
+            }
+            _ => {}
+        }
+    }
+    stripped
+}
+
+/// Splits a name or literal list on `and` surrounded by whitespace,
+/// ignoring separators nested in braces, e.g. `{Barnes and Noble} and Penguin`.
+pub(crate) fn split_and_list(s: &str) -> Vec<String> {
+    fn end_word(word: &mut String, items: &mut Vec<Vec<String>>) {
+        if word.is_empty() {
+            return;
+        }
+        if word.to_lowercase() == LIST_SEPARATOR {
+            items.push(vec![]);
+        } else if let Some(item) = items.last_mut() {
+            item.push(word.clone());
+        }
+        word.clear();
+    }
+
+    let mut items: Vec<Vec<String>> = vec![vec![]];
+    let mut word = String::new();
+    let mut depth = 0;
+
+    for c in s.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                end_word(&mut word, &mut items);
+                continue;
+            }
+            _ => {}
+        }
+        word.push(c);
+    }
+    end_word(&mut word, &mut items);
+
+    items
+        .into_iter()
+        .map(|item| item.join(" "))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Wraps a list item in braces if it would otherwise be split on `and`, e.g. `{Barnes and Noble}`.
+pub(crate) fn protect_list_item(item: &str) -> String {
+    match split_and_list(item).len() {
+        1 => s!(item),
+        _ => format!("{{{}}}", item),
+    }
+}
+
+/// Decodes the LaTeX markup of a value to plain Unicode text for formats without LaTeX,
+/// e.g. `Caf{\'e} \& Bar` to `Café & Bar`. Unknown commands are dropped, keeping their argument.
+pub(crate) fn latex_to_unicode(s: &str) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => decode_command(&mut chars, &mut decoded),
+            '{' | '}' | '$' => {}
+            '~' => decoded.push('\u{a0}'),
+            '-' if chars.next_if_eq(&'-').is_some() => match chars.next_if_eq(&'-') {
+                Some(_) => decoded.push('—'),
+                None => decoded.push('–'),
+            },
+            '`' if chars.next_if_eq(&'`').is_some() => decoded.push('“'),
+            '\'' if chars.next_if_eq(&'\'').is_some() => decoded.push('”'),
+            c => decoded.push(c),
+        }
+    }
+    decoded.nfc().collect()
+}
+
+fn decode_command(chars: &mut Peekable<Chars>, decoded: &mut String) {
+    let mut name: String =
+        std::iter::from_fn(|| chars.next_if(char::is_ascii_alphabetic)).collect();
+    if name.is_empty() {
+        match chars.next() {
+            Some(c) => name.push(c),
+            None => return decoded.push('\\'),
+        }
+    } else {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+    if let Some(mark) = accent_mark(&name) {
+        let argument = accent_argument(chars);
+        let mut argument = argument.chars();
+        match argument.next() {
+            Some('ı') => decoded.push('i'),
+            Some('ȷ') => decoded.push('j'),
+            Some(base) => decoded.push(base),
+            None => {}
+        }
+        decoded.push(mark);
+        decoded.extend(argument);
+    } else if let Some(symbol) = symbol(&name) {
+        decoded.push_str(symbol);
+    } else if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        decoded.push_str(&name);
+    }
+}
+
+/// The accented character, e.g. `e` in `\'e`, `\'{e}` and `\'\i`, decoded.
+fn accent_argument(chars: &mut Peekable<Chars>) -> String {
+    let mut argument = String::new();
+    match chars.next() {
+        Some('{') => {
+            let mut depth = 1;
+            for c in chars.by_ref() {
+                depth += match c {
+                    '{' => 1,
+                    '}' => -1,
+                    _ => 0,
+                };
+                if depth == 0 {
+                    break;
+                }
+                argument.push(c);
+            }
+        }
+        Some('\\') => {
+            argument.push('\\');
+            argument.extend(std::iter::from_fn(|| {
+                chars.next_if(char::is_ascii_alphabetic)
+            }));
+        }
+        Some(c) => argument.push(c),
+        None => {}
+    }
+    latex_to_unicode(&argument)
+}
+
+/// The combining character of an accent command, e.g. U+0301 for `\'`.
+fn accent_mark(name: &str) -> Option<char> {
+    match name {
+        "'" => Some('\u{301}'),
+        "`" => Some('\u{300}'),
+        "^" => Some('\u{302}'),
+        "\"" => Some('\u{308}'),
+        "~" => Some('\u{303}'),
+        "=" => Some('\u{304}'),
+        "." => Some('\u{307}'),
+        "u" => Some('\u{306}'),
+        "v" => Some('\u{30c}'),
+        "H" => Some('\u{30b}'),
+        "r" => Some('\u{30a}'),
+        "c" => Some('\u{327}'),
+        "k" => Some('\u{328}'),
+        "d" => Some('\u{323}'),
+        "b" => Some('\u{331}'),
+        _ => None,
+    }
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    match name {
+        "ss" => Some("ß"),
+        "ae" => Some("æ"),
+        "AE" => Some("Æ"),
+        "oe" => Some("œ"),
+        "OE" => Some("Œ"),
+        "o" => Some("ø"),
+        "O" => Some("Ø"),
+        "aa" => Some("å"),
+        "AA" => Some("Å"),
+        "l" => Some("ł"),
+        "L" => Some("Ł"),
+        "i" => Some("ı"),
+        "j" => Some("ȷ"),
+        "TeX" => Some("TeX"),
+        "LaTeX" => Some("LaTeX"),
+        "BibTeX" => Some("BibTeX"),
+        "textendash" => Some("–"),
+        "textemdash" => Some("—"),
+        "ldots" | "dots" | "textellipsis" => Some("…"),
+        "textbackslash" => Some("\\"),
+        "textasciitilde" => Some("~"),
+        "textasciicircum" => Some("^"),
+        "textunderscore" => Some("_"),
+        "S" => Some("§"),
+        "P" => Some("¶"),
+        "copyright" => Some("©"),
+        "pounds" => Some("£"),
+        "\\" | " " => Some(" "),
+        "," => Some("\u{2009}"),
+        "/" => Some(""),
+        _ => None,
+    }
+}
+
+/// Escapes the characters with a meaning in LaTeX, e.g. `R&D` to `R\&D`, and breaks up the
+/// ligatures, e.g. `--`, so that `latex_to_unicode` gives back the plain text.
+pub(crate) fn unicode_to_latex(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '&' | '%' | '#' | '$' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("{\\textasciitilde}"),
+            '^' => escaped.push_str("{\\textasciicircum}"),
+            '\\' => escaped.push_str("{\\textbackslash}"),
+            '\u{a0}' => escaped.push('~'),
+            '-' | '`' | '\'' if chars.peek() == Some(&c) => {
+                escaped.push(c);
+                escaped.push_str("{}");
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes the characters with a meaning in XML text and attribute values.
+pub(crate) fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod strings_test {
+    use super::*;
+
+    #[test]
+    fn strip_braces_from_str() {
+        // given
+        [
+            ("{XP} Series", "XP Series"),
+            ("{{Nested}} value", "Nested value"),
+            (r"Escaped \{brace\}", r"Escaped \{brace\}"),
+            (
+                r#"{M{\"u}ller} \v{C}ech {\TeX}book"#,
+                r#"M{\"u}ller \v{C}ech {\TeX}book"#,
+            ),
+            ("No braces", "No braces"),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = strip_braces(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn split_and_list_from_str() {
+        // given
+        [
+            ("Paris", vec!["Paris"]),
+            ("Paris and Berlin", vec!["Paris", "Berlin"]),
+            (
+                "Paris AND  Berlin\nand London",
+                vec!["Paris", "Berlin", "London"],
+            ),
+            (
+                "{Barnes and Noble} and Penguin",
+                vec!["{Barnes and Noble}", "Penguin"],
+            ),
+            ("Anderson and Sandberg", vec!["Anderson", "Sandberg"]),
+            ("Paris and others", vec!["Paris", "others"]),
+            ("", vec![]),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = split_and_list(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn latex_to_unicode_from_str() {
+        // given
+        [
+            (r"Caf{\'e} \& Bar", "Café & Bar"),
+            (r#"M{\"u}ller"#, "Müller"),
+            (r"Erd\H{o}s and \v Cech", "Erdős and Čech"),
+            (r#"Fran\c{c}ois na\"{\i}ve"#, "François naïve"),
+            (r"Stra\ss e", "Straße"),
+            (r"The \TeX{}book, 100\% \$5", "The TeXbook, 100% $5"),
+            (r"{The {XP} Way} \emph{now}", "The XP Way now"),
+            ("pages 1--2 --- ``quoted''", "pages 1–2 — “quoted”"),
+            ("D.~E. Knuth", "D.\u{a0}E. Knuth"),
+            ("Plain text", "Plain text"),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = latex_to_unicode(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+}
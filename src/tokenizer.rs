@@ -7,11 +7,14 @@ pub(crate) enum EntryToken {
     Symbol(String),
     FieldName(String),
     Value(String),
+    /// A value written without braces or quotes, e.g. the number `2008` or the macro `jan`.
+    BareValue(String),
 }
 
 pub(crate) struct Tokenizer {
     buffer: Box<dyn Read>,
     current_token_value: String,
+    is_value_delimited: bool,
     tokens: Vec<EntryToken>,
     state: TokenizerState,
     position: Position,
@@ -22,6 +25,7 @@ impl Tokenizer {
         Tokenizer {
             buffer,
             current_token_value: String::new(),
+            is_value_delimited: false,
             tokens: Vec::new(),
             state: Idle,
             position: Position {
@@ -135,20 +139,22 @@ impl Tokenizer {
                 Ok(())
             }
             EntryLiteral::DoubleQuote => {
+                self.is_value_delimited = true;
                 self.transition_keep_value(ReadValue(TokenizerReadValueMode::DoubleQuoted));
                 Ok(())
             }
             EntryLiteral::LeftBrace => {
+                self.is_value_delimited = true;
                 self.transition_keep_value(ReadValue(TokenizerReadValueMode::Braced(0)));
                 Ok(())
             }
             EntryLiteral::Comma => {
-                self.add_token(EntryToken::Value(self.current_token_value.clone()));
+                self.add_token(self.value_token());
                 self.transition(ReadPropertyName);
                 Ok(())
             }
             EntryLiteral::RightBrace => {
-                self.add_token(EntryToken::Value(self.current_token_value.clone()));
+                self.add_token(self.value_token());
                 self.transition(Idle);
                 Ok(())
             }
@@ -181,10 +187,14 @@ impl Tokenizer {
         };
         match literal {
             EntryLiteral::RightBrace => {
+                if brace_level > 0 {
+                    self.current_token_value.push('}');
+                }
                 self.transition_keep_value(ReadValue(Self::right_brace_mode(brace_level)));
                 Ok(())
             }
             EntryLiteral::LeftBrace => {
+                self.current_token_value.push('{');
                 self.transition_keep_value(ReadValue(TokenizerReadValueMode::Braced(
                     brace_level + 1,
                 )));
@@ -198,6 +208,14 @@ impl Tokenizer {
         }
     }
 
+    fn value_token(&self) -> EntryToken {
+        let value = self.current_token_value.clone();
+        match self.is_value_delimited || value.is_empty() {
+            true => EntryToken::Value(value),
+            false => EntryToken::BareValue(value),
+        }
+    }
+
     fn right_brace_mode(brace_level: i32) -> TokenizerReadValueMode {
         match brace_level {
             bl if bl > 0 => TokenizerReadValueMode::Braced(bl - 1),
@@ -290,6 +308,7 @@ impl Tokenizer {
 
     fn transition(&mut self, new_state: TokenizerState) {
         self.current_token_value = String::new();
+        self.is_value_delimited = false;
         self.state = new_state;
     }
 
@@ -409,7 +428,7 @@ mod tokenizer_test {
             EntryToken::FieldName(s!("author")),
             EntryToken::Value(s!("Malan, Ruth")),
             EntryToken::FieldName(s!("year")),
-            EntryToken::Value(s!("2008")),
+            EntryToken::BareValue(s!("2008")),
         ];
 
         // when
@@ -809,7 +828,7 @@ mod tokenizer_test {
                 // given
                 let input = "a b{@}c";
                 let mut tokenizer = tokenizer_for_str(input);
-                let expected = "a b@c";
+                let expected = "a b{@}c";
 
                 // when
                 for _ in 0..7 {
@@ -826,7 +845,7 @@ mod tokenizer_test {
             // given
             let input = "abc,";
             let mut tokenizer = tokenizer_for_str(input);
-            let expected = EntryToken::Value(s!("abc"));
+            let expected = EntryToken::BareValue(s!("abc"));
 
             // when
             for _ in 0..4 {
@@ -852,13 +871,12 @@ mod tokenizer_test {
         }
     }
 
-    #[cfg(test)]
     mod internal {
         use super::*;
 
         #[test]
         fn literals() {
-            vec![
+            [
                 ("@", EntryLiteral::AtSign),
                 ("\n", EntryLiteral::Newline),
                 ("a", EntryLiteral::Alphabetic('a')),
@@ -880,7 +898,7 @@ mod tokenizer_test {
 
         #[test]
         fn position() {
-            vec![
+            [
                 (
                     "aaa",
                     Position {
@@ -932,7 +950,7 @@ mod tokenizer_test {
 
         #[test]
         fn utf8_next_char() {
-            vec![("abc", Some('a')), ("👌", Some('👌')), ("", None)]
+            [("abc", Some('a')), ("👌", Some('👌')), ("", None)]
                 .iter()
                 .for_each(|(input, expected)| {
                     // given
@@ -947,24 +965,20 @@ mod tokenizer_test {
         }
 
         #[test]
-        #[allow(clippy::invalid_utf8_in_unchecked)]
         fn non_valid_utf8_next_char() {
-            unsafe {
-                // given
-                let utf8_buffer = &[255, 254, 253, 252];
-                let input = std::str::from_utf8_unchecked(utf8_buffer);
-                let mut tokenizer = tokenizer_for_str(input);
-                let expected = Error::new(
-                    ErrorKind::InvalidInput,
-                    s!("Cannot decode bytes to UTF-8. Bytes: [ff fe fd fc]"),
-                );
+            // given
+            let utf8_buffer: &'static [u8] = &[255, 254, 253, 252];
+            let mut tokenizer = Tokenizer::new(Box::new(utf8_buffer));
+            let expected = Error::new(
+                ErrorKind::InvalidInput,
+                s!("Cannot decode bytes to UTF-8. Bytes: [ff fe fd fc]"),
+            );
 
-                // when
-                let actual = tokenizer.next_char().unwrap_err();
+            // when
+            let actual = tokenizer.next_char().unwrap_err();
 
-                // then
-                assert_io_error_eq(actual, expected);
-            }
+            // then
+            assert_io_error_eq(actual, expected);
         }
     }
 
@@ -0,0 +1,365 @@
+use crate::entry::Entry;
+use crate::entry_field::EntryField;
+use crate::entry_type::EntryType;
+use crate::s;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A granular `field = {xdata=<key>-<field>[-<index>]}` reference to a single field,
+/// or a single item of a list field, of an `@xdata` entry.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct XDataReference {
+    field: String,
+    entry: String,
+    entry_field: String,
+    index: Option<usize>,
+}
+
+impl XDataReference {
+    const PREFIX: &'static str = "xdata=";
+
+    pub(crate) fn parse(field_name: &str, value: &str) -> Option<XDataReference> {
+        let field = field_name.to_lowercase();
+        let value = value.trim();
+        if field == "xdata" || !value.to_lowercase().starts_with(Self::PREFIX) {
+            return None;
+        }
+        let reference = &value[Self::PREFIX.len()..];
+        let (rest, last) = reference.rsplit_once('-')?;
+        let (entry, entry_field, index) = match last.parse::<usize>() {
+            Ok(index) => {
+                let (entry, entry_field) = rest.rsplit_once('-')?;
+                (entry, entry_field, Some(index))
+            }
+            Err(_) => (rest, last, None),
+        };
+        match entry.is_empty() || entry_field.is_empty() {
+            true => None,
+            false => Some(XDataReference {
+                field,
+                entry: s!(entry),
+                entry_field: entry_field.to_lowercase(),
+                index,
+            }),
+        }
+    }
+
+    /// The name of the field holding the reference.
+    pub(crate) fn field(&self) -> &str {
+        &self.field
+    }
+}
+
+impl std::fmt::Display for XDataReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}-{}", Self::PREFIX, self.entry, self.entry_field)?;
+        match self.index {
+            Some(index) => write!(f, "-{}", index),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Merges the fields of the `@xdata` entries listed in `xdata` fields, and replaces
+/// granular references with the referenced fields. Fields of the entry itself win,
+/// then those of the earlier listed `@xdata` entries.
+pub(crate) struct XDataResolver<'a> {
+    entries: &'a [Entry],
+    index: HashMap<&'a str, usize>,
+    resolved: Vec<Option<Vec<EntryField>>>,
+    path: Vec<usize>,
+    warnings: Vec<(String, String)>,
+}
+
+impl<'a> XDataResolver<'a> {
+    /// Returns the resolved entries and the warnings as `(symbol, warning)` pairs.
+    /// With `drop_xdata`, the `@xdata` entries and the `xdata` fields pointing to them
+    /// are left out of the output.
+    pub(crate) fn resolve(
+        entries: Vec<Entry>,
+        drop_xdata: bool,
+    ) -> (Vec<Entry>, Vec<(String, String)>) {
+        let mut resolver = XDataResolver::new(&entries);
+        let fields: Vec<Vec<EntryField>> = (0..entries.len())
+            .map(|i| resolver.resolve_entry(i))
+            .collect();
+        let warnings = resolver.warnings;
+
+        let entries = entries
+            .into_iter()
+            .zip(fields)
+            .filter(|(entry, _)| !drop_xdata || *entry.entry_type() != EntryType::XData)
+            .map(|(entry, fields)| match drop_xdata {
+                true => entry.with_fields(
+                    fields
+                        .into_iter()
+                        .filter(|f| !matches!(f, EntryField::XData(_)))
+                        .collect(),
+                ),
+                false => entry.with_fields(fields),
+            })
+            .collect();
+        (entries, warnings)
+    }
+
+    fn new(entries: &'a [Entry]) -> XDataResolver<'a> {
+        let mut index = HashMap::new();
+        entries.iter().enumerate().for_each(|(i, entry)| {
+            index.entry(entry.symbol()).or_insert(i);
+        });
+        XDataResolver {
+            entries,
+            index,
+            resolved: vec![None; entries.len()],
+            path: vec![],
+            warnings: vec![],
+        }
+    }
+
+    fn resolve_entry(&mut self, i: usize) -> Vec<EntryField> {
+        if let Some(fields) = &self.resolved[i] {
+            return fields.clone();
+        }
+        let entries = self.entries;
+        let entry = &entries[i];
+        self.path.push(i);
+
+        let mut fields: Vec<EntryField> = vec![];
+        for field in entry.fields() {
+            match field {
+                EntryField::XDataRef(reference) => {
+                    if let Some(field) = self.referenced_field(entry, reference) {
+                        fields.push(field);
+                    }
+                }
+                field => fields.push(field.clone()),
+            }
+        }
+        for key in Self::xdata_keys(entry) {
+            let Some(source) = self.source(entry, key) else {
+                continue;
+            };
+            for field in self.resolve_entry(source) {
+                let is_inherited = !matches!(field, EntryField::XData(_) | EntryField::Ids(_));
+                if is_inherited && !fields.iter().any(|f| f.is_same_field(&field)) {
+                    fields.push(field);
+                }
+            }
+        }
+
+        self.path.pop();
+        self.resolved[i] = Some(fields.clone());
+        fields
+    }
+
+    /// The referenced field, or item of a list field, as the referencing field, which must be
+    /// of the same data type, e.g. `location = {xdata=macmillan-publisher-1}`.
+    fn referenced_field(
+        &mut self,
+        entry: &Entry,
+        reference: &XDataReference,
+    ) -> Option<EntryField> {
+        let source = self.source(entry, &reference.entry)?;
+        let field = self
+            .resolve_entry(source)
+            .into_iter()
+            .find(|f| f.name() == reference.entry_field);
+        let item = match (field, reference.index) {
+            (Some(field), Some(index)) => index.checked_sub(1).and_then(|n| field.nth_item(n)),
+            (field, None) => field,
+            (None, _) => None,
+        };
+        let Some(item) = item else {
+            self.warn(entry, format!("XData reference '{}' not found", reference));
+            return None;
+        };
+        let value = item.bib_fields().into_iter().next().map(|(_, value)| value);
+        let field = value
+            .and_then(|value| EntryField::from_field_name_and_text(&reference.field, &value).ok())
+            .filter(|field| field.data_type() == item.data_type());
+        if field.is_none() {
+            self.warn(
+                entry,
+                format!(
+                    "XData reference '{}' must point to a field of the data type of '{}'",
+                    reference, reference.field
+                ),
+            );
+        }
+        field
+    }
+
+    /// The `@xdata` entry with the `key`, unless it is missing, of another type or part of a cycle.
+    fn source(&mut self, entry: &Entry, key: &str) -> Option<usize> {
+        let Some(source) = self.index.get(key).copied() else {
+            self.warn(entry, format!("XData '{}' not found", key));
+            return None;
+        };
+        if *self.entries[source].entry_type() != EntryType::XData {
+            self.warn(entry, format!("XData '{}' is not an @xdata entry", key));
+            return None;
+        }
+        if self.path.contains(&source) {
+            let cycle = self.cycle(source);
+            self.warn(entry, format!("XData cycle {}", cycle));
+            return None;
+        }
+        Some(source)
+    }
+
+    fn xdata_keys(entry: &Entry) -> Vec<&str> {
+        entry
+            .fields()
+            .iter()
+            .filter_map(|f| match f {
+                EntryField::XData(keys) => Some(keys),
+                _ => None,
+            })
+            .flatten()
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Renders the chain of symbols from `source` back to itself, e.g. `'a' -> 'b' -> 'a'`.
+    fn cycle(&self, source: usize) -> String {
+        let start = self
+            .path
+            .iter()
+            .position(|i| *i == source)
+            .unwrap_or_default();
+        self.path[start..]
+            .iter()
+            .chain([source].iter())
+            .map(|i| format!("'{}'", self.entries[*i].symbol()))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+
+    fn warn(&mut self, entry: &Entry, warning: String) {
+        self.warnings.push((s!(entry.symbol()), warning));
+    }
+}
+
+#[cfg(test)]
+mod xdata_test {
+    use super::*;
+    use crate::entry::{Parser, ParserOptions};
+    use crate::literal_list::LiteralList;
+    use crate::tokenizer::Tokenizer;
+
+    #[test]
+    fn parse_reference() {
+        // given
+        [
+            (
+                ("publisher", "xdata=macmillan-publisher"),
+                Some(XDataReference {
+                    field: s!("publisher"),
+                    entry: s!("macmillan"),
+                    entry_field: s!("publisher"),
+                    index: None,
+                }),
+            ),
+            (
+                ("Location", "xdata=mac-millan-location-2"),
+                Some(XDataReference {
+                    field: s!("location"),
+                    entry: s!("mac-millan"),
+                    entry_field: s!("location"),
+                    index: Some(2),
+                }),
+            ),
+            (("xdata", "xdata=a-b"), None),
+            (("publisher", "xdata=macmillan"), None),
+            (("publisher", "Macmillan"), None),
+        ]
+        .iter()
+        .for_each(|((field_name, value), expected)| {
+            // when
+            let actual = XDataReference::parse(field_name, value);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+
+    #[test]
+    fn merge_nested_xdata() {
+        // given
+        let input = r#"
+            @xdata{macmillan, xdata = {newyork}, publisher = {Macmillan}}
+            @xdata{newyork, location = {New York}, publisher = {Other}}
+            @book{key, title = {Title}, xdata = {macmillan}}"#;
+        let expected = vec![
+            EntryField::Title(s!("Title")),
+            EntryField::Publisher(LiteralList::from_str("Macmillan")),
+            EntryField::Location(LiteralList::from_str("New York")),
+        ];
+
+        // when
+        let (entries, warnings) = parse(input, true);
+
+        // then
+        assert_eq!(entries.len(), 1);
+        assert_eq!(*entries[0].fields(), expected);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn resolve_granular_references() {
+        // given
+        let input = r#"
+            @xdata{places, location = {New York and London}, publisher = {Macmillan}}
+            @book{key,
+                location = {xdata=places-location-2}, address = {xdata=places-missing},
+                organization = {xdata=places-publisher-1}, title = {xdata=places-location},
+            }"#;
+        let expected_warnings = vec![
+            s!("Entry 'key': XData reference 'xdata=places-missing' not found"),
+            s!("Entry 'key': XData reference 'xdata=places-location' must point to a field of the data type of 'title'"),
+        ];
+
+        // when
+        let (entries, warnings) = parse(input, false);
+
+        // then
+        assert_eq!(
+            *entries[1].fields(),
+            vec![
+                EntryField::Location(LiteralList::from_str("London")),
+                EntryField::Organization(LiteralList::from_str("Macmillan")),
+            ]
+        );
+        assert_eq!(warnings, expected_warnings);
+    }
+
+    #[test]
+    fn warn_on_missing_and_cyclic_xdata() {
+        // given
+        let input = r#"
+            @xdata{a, xdata = {b}}
+            @xdata{b, xdata = {a}}
+            @book{key, xdata = {missing}}"#;
+        let expected = vec![
+            s!("Entry 'b': XData cycle 'a' -> 'b' -> 'a'"),
+            s!("Entry 'key': XData 'missing' not found"),
+        ];
+
+        // when
+        let (_, warnings) = parse(input, false);
+
+        // then
+        assert_eq!(warnings, expected);
+    }
+
+    fn parse(input: &'static str, drop_xdata: bool) -> (Vec<Entry>, Vec<String>) {
+        let options = ParserOptions {
+            resolve_xdata: true,
+            drop_xdata,
+            ..ParserOptions::default()
+        };
+        let mut parser = Parser::with_options(Tokenizer::new(Box::new(input.as_bytes())), options);
+        let entries = parser.parse().unwrap();
+        (entries, parser.warnings().clone())
+    }
+}
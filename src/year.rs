@@ -0,0 +1,143 @@
+use crate::pub_state::PubState;
+use crate::s;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum Year {
+    Numeric(i32),
+    Suffixed(i32, String),
+    Circa(i32),
+    Uncertain(i32),
+    PubState(PubState),
+    NoDate,
+    Literal(String),
+}
+
+impl Year {
+    const CIRCA_PREFIXES: [&'static str; 5] = ["circa", "ca.", "ca", "c.", "c"];
+    const CIRCA_SUFFIX: char = '~';
+    const UNCERTAIN_SUFFIX: char = '?';
+    const NO_DATE: [&'static str; 4] = ["nd", "nodate", "sa", "sd"];
+
+    pub(crate) fn parse(s: &str) -> Year {
+        let trimmed = s.trim();
+        let unbracketed = trimmed
+            .strip_prefix('[')
+            .and_then(|it| it.strip_suffix(']'))
+            .unwrap_or(trimmed)
+            .trim();
+
+        Self::parse_numeric(unbracketed)
+            .or_else(|| PubState::known_from_str(unbracketed).map(Year::PubState))
+            .or_else(|| Self::parse_no_date(unbracketed))
+            .or_else(|| Self::parse_circa(unbracketed))
+            .or_else(|| Self::parse_uncertain(unbracketed))
+            .or_else(|| Self::parse_suffixed(unbracketed))
+            .unwrap_or(Year::Literal(s!(trimmed)))
+    }
+
+    /// The year as a number, if known.
+    pub(crate) fn value(&self) -> Option<i32> {
+        match self {
+            Year::Numeric(y) | Year::Suffixed(y, _) | Year::Circa(y) | Year::Uncertain(y) => {
+                Some(*y)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_numeric(s: &str) -> Option<Year> {
+        s.parse::<i32>().ok().map(Year::Numeric)
+    }
+
+    fn parse_no_date(s: &str) -> Option<Year> {
+        let normalized = s.to_lowercase().replace(['.', ' '], "");
+        match Self::NO_DATE.contains(&normalized.as_str()) {
+            true => Some(Year::NoDate),
+            false => None,
+        }
+    }
+
+    fn parse_circa(s: &str) -> Option<Year> {
+        if let Some(year) = s.strip_suffix(Self::CIRCA_SUFFIX) {
+            return year.trim().parse::<i32>().ok().map(Year::Circa);
+        }
+        let lowercase = s.to_lowercase();
+        Self::CIRCA_PREFIXES
+            .iter()
+            .find_map(|prefix| lowercase.strip_prefix(prefix))
+            .and_then(|year| year.trim().parse::<i32>().ok())
+            .map(Year::Circa)
+    }
+
+    fn parse_uncertain(s: &str) -> Option<Year> {
+        s.strip_suffix(Self::UNCERTAIN_SUFFIX)
+            .and_then(|year| year.trim().parse::<i32>().ok())
+            .map(Year::Uncertain)
+    }
+
+    fn parse_suffixed(s: &str) -> Option<Year> {
+        let split_at = s.find(|c: char| !c.is_ascii_digit())?;
+        let (year, suffix) = s.split_at(split_at);
+        match suffix.chars().all(|c| c.is_ascii_lowercase()) {
+            true => year
+                .parse::<i32>()
+                .ok()
+                .map(|y| Year::Suffixed(y, s!(suffix))),
+            false => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Year::Numeric(y) => write!(f, "{}", y),
+            Year::Suffixed(y, suffix) => write!(f, "{}{}", y, suffix),
+            Year::Circa(y) => write!(f, "{} {}", Self::CIRCA_PREFIXES[1], y),
+            Year::Uncertain(y) => write!(f, "{}{}", y, Self::UNCERTAIN_SUFFIX),
+            Year::PubState(state) => write!(f, "{}", state),
+            Year::NoDate => write!(f, "n.d."),
+            Year::Literal(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod year_test {
+    use super::*;
+
+    #[test]
+    fn parse_from_str() {
+        // given
+        [
+            ("2004", Year::Numeric(2004)),
+            (" 2004 ", Year::Numeric(2004)),
+            ("2004a", Year::Suffixed(2004, s!("a"))),
+            ("2004ab", Year::Suffixed(2004, s!("ab"))),
+            ("c. 1890", Year::Circa(1890)),
+            ("ca. 1890", Year::Circa(1890)),
+            ("ca 1890", Year::Circa(1890)),
+            ("circa 1890", Year::Circa(1890)),
+            ("c1890", Year::Circa(1890)),
+            ("1890~", Year::Circa(1890)),
+            ("1890?", Year::Uncertain(1890)),
+            ("[1890?]", Year::Uncertain(1890)),
+            ("in press", Year::PubState(PubState::InPress)),
+            ("forthcoming", Year::PubState(PubState::Forthcoming)),
+            ("n.d.", Year::NoDate),
+            ("N. D.", Year::NoDate),
+            ("s.a.", Year::NoDate),
+            ("Winter 1890/91", Year::Literal(s!("Winter 1890/91"))),
+            ("2004A", Year::Literal(s!("2004A"))),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            // when
+            let actual = Year::parse(input);
+
+            // then
+            assert_eq!(actual, *expected);
+        });
+    }
+}